@@ -0,0 +1,490 @@
+//! Semantic comparison of SDF models and WoT Thing Models.
+//!
+//! Two documents are considered equivalent if they describe the same
+//! affordances and data schemas, regardless of the order of their members,
+//! the prefixes that affordance names receive when the `sdfObject` and
+//! `sdfThing` hierarchy is flattened, and members that are explicitly set to
+//! their default values. All remaining differences are reported per
+//! affordance and per data schema field.
+
+use crate::json_pointer::member_pointer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_with::skip_serializing_none;
+use std::collections::BTreeSet;
+
+const SDF_DEFINITION_KEYWORDS: [&str; 4] = ["sdfProperty", "sdfAction", "sdfEvent", "sdfData"];
+const SDF_GROUP_KEYWORDS: [&str; 2] = ["sdfObject", "sdfThing"];
+const TM_AFFORDANCE_KEYWORDS: [&str; 3] = ["properties", "actions", "events"];
+
+/// Qualities of SDF definitions that are equivalent to their absence, given
+/// as JSON literals.
+const SDF_DEFAULTS: [(&str, &str); 7] = [
+    ("readable", "true"),
+    ("writable", "true"),
+    ("observable", "true"),
+    ("nullable", "true"),
+    ("uniqueItems", "false"),
+    ("minItems", "0"),
+    ("minLength", "0"),
+];
+
+/// Members of Thing Model affordances and data schemas that are equivalent to
+/// their absence, given as JSON literals.
+const TM_DEFAULTS: [(&str, &str); 8] = [
+    ("readOnly", "false"),
+    ("writeOnly", "false"),
+    ("observable", "false"),
+    ("safe", "false"),
+    ("idempotent", "false"),
+    ("minItems", "0"),
+    ("minLength", "0"),
+    ("contentType", "\"application/json\""),
+];
+
+/// Members whose array values are compared regardless of their order.
+const UNORDERED_KEYWORDS: [&str; 3] = ["enum", "required", "sdfRequired"];
+
+/// The way a member differs between two documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DifferenceKind {
+    /// The member is only present in the first document.
+    Removed,
+    /// The member is only present in the second document.
+    Added,
+    /// The member is present in both documents but has different values.
+    Changed,
+}
+
+/// A structural difference between two documents.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Difference {
+    /// A JSON pointer to the affected affordance (or `sdfObject`, `sdfThing`,
+    /// or `sdfData` definition) in the first document, or in the second one
+    /// if the first document lacks it. Absent for model-level members.
+    pub affordance: Option<String>,
+    /// A JSON pointer to the differing member, relative to the affordance or
+    /// to the model root.
+    pub pointer: String,
+    pub kind: DifferenceKind,
+    /// The (normalized) value of the member in the first document.
+    pub first: Option<Value>,
+    /// The (normalized) value of the member in the second document.
+    pub second: Option<Value>,
+}
+
+/// A definition of a normalized document that is compared on its own.
+struct Definition {
+    keyword: &'static str,
+    name: String,
+    pointer: String,
+    value: Value,
+}
+
+/// A document split into its model-level members and its definitions.
+struct NormalizedDocument {
+    root: Value,
+    definitions: Vec<Definition>,
+}
+
+/// Reduces an affordance name to its lowercase alphanumeric characters, so
+/// that `switchOnOff`, `SwitchOnOff`, and `switch_on_off` are treated alike.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|x| x.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_default(key: &str, value: &Value, defaults: &[(&str, &str)]) -> bool {
+    defaults.iter().any(|(default_key, default_value)| {
+        *default_key == key
+            && serde_json::from_str::<Value>(default_value)
+                .is_ok_and(|x| normalize_value(x, &[]) == *value)
+    })
+}
+
+/// Removes default members, represents all numbers as floating point numbers,
+/// and sorts arrays whose order is irrelevant.
+fn normalize_value(value: Value, defaults: &[(&str, &str)]) -> Value {
+    match value {
+        Value::Number(number) => number
+            .as_f64()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        Value::Array(array) => Value::Array(
+            array
+                .into_iter()
+                .map(|x| normalize_value(x, defaults))
+                .collect(),
+        ),
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| {
+                    let mut value = normalize_value(value, defaults);
+                    if let (true, Value::Array(array)) =
+                        (UNORDERED_KEYWORDS.contains(&key.as_str()), &mut value)
+                    {
+                        array.sort_by_key(|x| x.to_string());
+                    }
+                    (key, value)
+                })
+                .filter(|(key, value)| !is_default(key, value, defaults))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn without_members(object: &Map<String, Value>, keywords: &[&str]) -> Value {
+    Value::Object(
+        object
+            .iter()
+            .filter(|(key, _)| !keywords.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    )
+}
+
+fn named_definitions<'a>(
+    object: &'a Map<String, Value>,
+    keyword: &str,
+) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    object
+        .get(keyword)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+/// Collects the definitions of an SDF model, an `sdfObject`, or an
+/// `sdfThing`, naming nested definitions after their ancestors.
+fn collect_sdf_definitions(
+    object: &Map<String, Value>,
+    pointer: &str,
+    prefix: &str,
+    definitions: &mut Vec<Definition>,
+) {
+    for keyword in SDF_DEFINITION_KEYWORDS {
+        let keyword_pointer = member_pointer(pointer, keyword);
+        for (name, value) in named_definitions(object, keyword) {
+            definitions.push(Definition {
+                keyword,
+                name: normalize_name(&format!("{}{}", prefix, name)),
+                pointer: member_pointer(&keyword_pointer, name),
+                value: normalize_value(value.clone(), &SDF_DEFAULTS),
+            });
+        }
+    }
+
+    for keyword in SDF_GROUP_KEYWORDS {
+        let keyword_pointer = member_pointer(pointer, keyword);
+        for (name, value) in named_definitions(object, keyword) {
+            let group = match value.as_object() {
+                Some(group) => group,
+                None => continue,
+            };
+            let group_pointer = member_pointer(&keyword_pointer, name);
+            let group_prefix = format!("{}{}", prefix, name);
+            let nested_keywords = [&SDF_DEFINITION_KEYWORDS[..], &SDF_GROUP_KEYWORDS[..]].concat();
+
+            definitions.push(Definition {
+                keyword,
+                name: normalize_name(&group_prefix),
+                pointer: group_pointer.clone(),
+                value: normalize_value(without_members(group, &nested_keywords), &SDF_DEFAULTS),
+            });
+            collect_sdf_definitions(group, &group_pointer, &group_prefix, definitions);
+        }
+    }
+}
+
+fn normalize_sdf_model(sdf_model: &Value) -> NormalizedDocument {
+    let empty_object = Map::new();
+    let object = sdf_model.as_object().unwrap_or(&empty_object);
+    let root_keywords = [&SDF_DEFINITION_KEYWORDS[..], &SDF_GROUP_KEYWORDS[..]].concat();
+
+    let mut definitions = Vec::new();
+    collect_sdf_definitions(object, "", "", &mut definitions);
+
+    NormalizedDocument {
+        root: normalize_value(without_members(object, &root_keywords), &SDF_DEFAULTS),
+        definitions,
+    }
+}
+
+fn normalize_thing_model(thing_model: &Value) -> NormalizedDocument {
+    let empty_object = Map::new();
+    let object = thing_model.as_object().unwrap_or(&empty_object);
+
+    let mut definitions = Vec::new();
+    for keyword in TM_AFFORDANCE_KEYWORDS {
+        let keyword_pointer = member_pointer("", keyword);
+        for (name, value) in named_definitions(object, keyword) {
+            definitions.push(Definition {
+                keyword,
+                name: normalize_name(name),
+                pointer: member_pointer(&keyword_pointer, name),
+                value: normalize_value(value.clone(), &TM_DEFAULTS),
+            });
+        }
+    }
+
+    NormalizedDocument {
+        root: normalize_value(
+            without_members(object, &TM_AFFORDANCE_KEYWORDS),
+            &TM_DEFAULTS,
+        ),
+        definitions,
+    }
+}
+
+fn is_suffix_match(first: &Definition, second: &Definition) -> bool {
+    first.keyword == second.keyword
+        && (first.name.ends_with(&second.name) || second.name.ends_with(&first.name))
+}
+
+/// Pairs the definitions of two documents, first by their normalized names
+/// and then, for the remaining ones, by unambiguous name suffixes (e.g.,
+/// `onOff` and `switchOnOff`).
+fn match_definitions(
+    first: &[Definition],
+    second: &[Definition],
+) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut first_matches: Vec<Option<usize>> = vec![None; first.len()];
+    let mut second_matched = vec![false; second.len()];
+
+    for (i, definition) in first.iter().enumerate() {
+        let position = second.iter().enumerate().position(|(j, x)| {
+            !second_matched[j] && x.keyword == definition.keyword && x.name == definition.name
+        });
+        if let Some(j) = position {
+            first_matches[i] = Some(j);
+            second_matched[j] = true;
+        }
+    }
+
+    for (i, definition) in first.iter().enumerate() {
+        if first_matches[i].is_some() {
+            continue;
+        }
+        let candidates: Vec<usize> = (0..second.len())
+            .filter(|&j| !second_matched[j] && is_suffix_match(definition, &second[j]))
+            .collect();
+        if let [j] = candidates[..] {
+            let is_unambiguous = (0..first.len())
+                .filter(|&k| first_matches[k].is_none() && is_suffix_match(&first[k], &second[j]))
+                .count()
+                == 1;
+            if is_unambiguous {
+                first_matches[i] = Some(j);
+                second_matched[j] = true;
+            }
+        }
+    }
+
+    first_matches
+        .into_iter()
+        .enumerate()
+        .map(|(i, j)| (Some(i), j))
+        .chain(
+            second_matched
+                .into_iter()
+                .enumerate()
+                .filter(|(_, matched)| !matched)
+                .map(|(j, _)| (None, Some(j))),
+        )
+        .collect()
+}
+
+fn diff_values(
+    differences: &mut Vec<Difference>,
+    affordance: Option<&str>,
+    pointer: &str,
+    first: Option<&Value>,
+    second: Option<&Value>,
+) {
+    let kind = match (first, second) {
+        (Some(Value::Object(first)), Some(Value::Object(second))) => {
+            let keys: BTreeSet<&String> = first.keys().chain(second.keys()).collect();
+            for key in keys {
+                diff_values(
+                    differences,
+                    affordance,
+                    &member_pointer(pointer, key),
+                    first.get(key),
+                    second.get(key),
+                );
+            }
+            return;
+        }
+        (Some(first), Some(second)) if first == second => return,
+        (Some(_), Some(_)) => DifferenceKind::Changed,
+        (Some(_), None) => DifferenceKind::Removed,
+        (None, Some(_)) => DifferenceKind::Added,
+        (None, None) => return,
+    };
+
+    differences.push(Difference {
+        affordance: affordance.map(|x| x.to_string()),
+        pointer: pointer.to_string(),
+        kind,
+        first: first.cloned(),
+        second: second.cloned(),
+    });
+}
+
+fn compare_documents(first: NormalizedDocument, second: NormalizedDocument) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_values(
+        &mut differences,
+        None,
+        "",
+        Some(&first.root),
+        Some(&second.root),
+    );
+
+    for (i, j) in match_definitions(&first.definitions, &second.definitions) {
+        let first_definition = i.map(|i| &first.definitions[i]);
+        let second_definition = j.map(|j| &second.definitions[j]);
+        let affordance = first_definition
+            .or(second_definition)
+            .map(|x| x.pointer.as_str());
+
+        diff_values(
+            &mut differences,
+            affordance,
+            "",
+            first_definition.map(|x| &x.value),
+            second_definition.map(|x| &x.value),
+        );
+    }
+
+    differences
+}
+
+/// Compares two (resolved) SDF models, returning their differences.
+///
+/// Definitions nested in `sdfObject`s and `sdfThing`s are matched by the
+/// concatenation of their ancestors' names and their own.
+pub fn compare_sdf_models(first: &Value, second: &Value) -> Vec<Difference> {
+    compare_documents(normalize_sdf_model(first), normalize_sdf_model(second))
+}
+
+/// Compares two (resolved) WoT Thing Models, returning their differences.
+pub fn compare_thing_models(first: &Value, second: &Value) -> Vec<Difference> {
+    compare_documents(normalize_thing_model(first), normalize_thing_model(second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn normalize_value_test() {
+        let value = json!({
+            "readOnly": false,
+            "writeOnly": true,
+            "minimum": 1,
+            "enum": ["b", "a"],
+            "items": {"minItems": 0, "maximum": 2.0}
+        });
+
+        assert_eq!(
+            normalize_value(value, &TM_DEFAULTS),
+            json!({
+                "writeOnly": true,
+                "minimum": 1.0,
+                "enum": ["a", "b"],
+                "items": {"maximum": 2.0}
+            })
+        );
+    }
+
+    #[test]
+    fn normalize_name_test() {
+        assert_eq!(normalize_name("Switch_onOff"), "switchonoff");
+        assert_eq!(normalize_name("switchOnOff"), "switchonoff");
+    }
+
+    #[test]
+    fn compare_equivalent_sdf_models_test() {
+        let first = json!({
+            "sdfObject": {
+                "switch": {
+                    "sdfProperty": {
+                        "onOff": {"type": "boolean", "writable": true}
+                    }
+                }
+            }
+        });
+        let second = json!({
+            "sdfObject": {
+                "switch": {
+                    "sdfProperty": {
+                        "onOff": {"type": "boolean"}
+                    }
+                }
+            }
+        });
+
+        assert!(compare_sdf_models(&first, &second).is_empty());
+    }
+
+    #[test]
+    fn compare_thing_models_test() {
+        let first = json!({
+            "title": "Lamp",
+            "properties": {
+                "switchOnOff": {"type": "integer", "minimum": 0, "readOnly": false},
+                "brightness": {"type": "integer"}
+            }
+        });
+        let second = json!({
+            "title": "Light",
+            "properties": {
+                "onOff": {"type": "integer", "minimum": 1.0},
+                "color": {"type": "string"}
+            }
+        });
+
+        assert_eq!(
+            compare_thing_models(&first, &second),
+            vec![
+                Difference {
+                    affordance: None,
+                    pointer: "/title".to_string(),
+                    kind: DifferenceKind::Changed,
+                    first: Some(json!("Lamp")),
+                    second: Some(json!("Light")),
+                },
+                Difference {
+                    affordance: Some("/properties/switchOnOff".to_string()),
+                    pointer: "/minimum".to_string(),
+                    kind: DifferenceKind::Changed,
+                    first: Some(json!(0.0)),
+                    second: Some(json!(1.0)),
+                },
+                Difference {
+                    affordance: Some("/properties/brightness".to_string()),
+                    pointer: "".to_string(),
+                    kind: DifferenceKind::Removed,
+                    first: Some(json!({"type": "integer"})),
+                    second: None,
+                },
+                Difference {
+                    affordance: Some("/properties/color".to_string()),
+                    pointer: "".to_string(),
+                    kind: DifferenceKind::Added,
+                    first: None,
+                    second: Some(json!({"type": "string"})),
+                },
+            ]
+        );
+    }
+}
@@ -1,7 +1,32 @@
-use crate::sdf::definitions::SDFModel;
+use crate::comparison::{self, Difference};
+use crate::diagnostics::{report_ignored_members, set_document, sort_diagnostics, Conversion};
+use crate::encoding::{decode, encode, Encoding};
+use crate::error::{from_json_str, from_json_value};
+use crate::lossless::{
+    annotate_sdf_model, annotate_thing_model, has_sdf_annotations, has_wot_annotations,
+    restore_sdf_model, restore_thing_model,
+};
+use crate::sdf::definitions::{SDFMapping, SDFModel};
+use crate::sdf::model_set::SDFModelSet;
+use crate::sdf::sdf_from_td::convert_thing_description;
+use crate::sdf::sdf_from_tm::{convert_composed_thing_model, convert_thing_model, WotSource};
+use crate::sdf::sdf_ref::resolve_sdf_refs;
+use crate::sdf::{validate_json, Violation};
 use crate::wot::definitions::ThingDescription;
 use crate::wot::definitions::ThingModel;
+use crate::wot::mapping::apply_mapping;
+use crate::wot::td_from_sdf::{
+    convert_to_thing_description, convert_to_thing_description_with_mapping, BindingOptions,
+};
+use crate::wot::td_from_tm::{instantiate_thing_model, PlaceholderMap};
+use crate::wot::tm_from_sdf::{convert_to_composed_thing_models, convert_to_thing_model};
+use crate::wot::tm_resolver::{no_loader, resolve_href, resolve_thing_model};
+use crate::wot::units::{translate_units, UnitVocabulary};
+use crate::wot::validation::{validate_thing_description, validate_thing_model};
 use crate::Result;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Write;
 
 fn print<T: serde::Serialize + serde::de::DeserializeOwned>(model: T) -> Result<()> {
     serialize_json::<T>(model).map(|j| println!("{}", j))
@@ -14,14 +39,184 @@ fn print_definition<T: serde::Serialize + serde::de::DeserializeOwned>(
     print(model)
 }
 
+fn format_definition<T: serde::Serialize + serde::de::DeserializeOwned>(
+    json_string: String,
+) -> Result<String> {
+    deserialize_json_string::<T>(json_string).and_then(serialize_json)
+}
+
 fn serialize_json<T: serde::Serialize + serde::de::DeserializeOwned>(model: T) -> Result<String> {
-    serde_json::to_string_pretty(&model).map_err(|e| e.into())
+    // Serializing into a Value first merges members that are emitted twice by
+    // flattened structs (such as the @type of property affordances), which
+    // YAML does not allow.
+    let json_value = serde_json::to_value(model)?;
+    serde_json::to_string_pretty(&json_value).map_err(|e| e.into())
 }
 
 fn deserialize_json_string<T: serde::Serialize + serde::de::DeserializeOwned>(
     json_string: String,
 ) -> Result<T> {
-    serde_json::from_str(json_string.as_str()).map_err(|e| e.into())
+    from_json_str(json_string.as_str())
+}
+
+fn deserialize_json_value<T: serde::Serialize + serde::de::DeserializeOwned>(
+    json_value: serde_json::Value,
+) -> Result<T> {
+    from_json_value(json_value)
+}
+
+/// Deserializes a JSON value into its typed representation, reporting the
+/// members that are ignored because they are unknown or invalid.
+fn deserialize_reported<T: serde::Serialize + serde::de::DeserializeOwned>(
+    json_value: &serde_json::Value,
+) -> Result<Conversion<T>> {
+    let model = deserialize_json_value::<T>(json_value.clone())?;
+    let mut diagnostics = Vec::new();
+    report_ignored_members(
+        &mut diagnostics,
+        json_value,
+        &serde_json::to_value(&model)?,
+        "",
+    );
+
+    Ok(Conversion::new(model, diagnostics))
+}
+
+/// Deserializes a JSON value and converts it using the `convert` function.
+///
+/// The diagnostics of the deserialization and the conversion are listed in
+/// the order of the members of the JSON value they refer to.
+fn convert_json_value<T, U>(
+    json_value: &serde_json::Value,
+    convert: impl FnOnce(T) -> Result<Conversion<U>>,
+) -> Result<Conversion<U>>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let Conversion {
+        output: model,
+        mut diagnostics,
+    } = deserialize_reported::<T>(json_value)?;
+    let mut conversion = convert(model)?;
+
+    diagnostics.append(&mut conversion.diagnostics);
+    sort_diagnostics(&mut diagnostics, json_value, None);
+    conversion.diagnostics = diagnostics;
+
+    Ok(conversion)
+}
+
+/// Converts a resolved SDF model JSON value into a WoT Thing Model.
+fn convert_sdf_model_json(sdf_model_json: &serde_json::Value) -> Result<Conversion<ThingModel>> {
+    convert_json_value(sdf_model_json, |sdf_model: SDFModel| {
+        Ok(convert_to_thing_model(&sdf_model))
+    })
+}
+
+/// Deserializes an SDF model JSON `String` into a JSON value, resolving all
+/// `sdfRef`s it contains.
+fn resolve_sdf_model_json(json_string: &str) -> Result<serde_json::Value> {
+    from_json_str::<serde_json::Value>(json_string).and_then(|x| resolve_sdf_refs(&x))
+}
+
+/// Deserializes a WoT Thing Model JSON `String` into a JSON value, resolving
+/// all `tm:extends` links and `tm:ref`s it contains.
+///
+/// Referenced documents are retrieved as JSON `String`s using the `loader`.
+fn resolve_thing_model_json(
+    json_string: &str,
+    loader: &dyn Fn(&str) -> Result<String>,
+) -> Result<serde_json::Value> {
+    let json_loader =
+        |href: &str| loader(href).and_then(deserialize_json_string::<serde_json::Value>);
+
+    from_json_str::<serde_json::Value>(json_string)
+        .and_then(|x| resolve_thing_model(&x, &json_loader))
+}
+
+/// Serializes a document that has been converted without losing any
+/// information.
+fn serialize_lossless(json_value: serde_json::Value) -> Result<Conversion<String>> {
+    serialize_json::<serde_json::Value>(json_value).map(|x| Conversion::new(x, Vec::new()))
+}
+
+fn serialize_conversion<T: serde::Serialize + serde::de::DeserializeOwned>(
+    conversion: Conversion<T>,
+) -> Result<Conversion<String>> {
+    let output = serialize_json::<T>(conversion.output)?;
+    Ok(Conversion::new(output, conversion.diagnostics))
+}
+
+/// Applies a conversion function to an encoded document, returning the
+/// result in the `output_encoding`.
+///
+/// This way, every `convert_*` function of this module can be used with
+/// documents in encodings other than JSON, such as CBOR. The encoding of the
+/// input is detected automatically unless an `input_encoding` is given.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::{convert_encoded, convert_sdf_to_wot_tm};
+/// use sdf_wot_converter::encoding::{decode, encode, Encoding};
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+/// let cbor = encode(&json_string, Encoding::Cbor).unwrap();
+///
+/// let result = convert_encoded(&cbor, None, Encoding::Cbor, &convert_sdf_to_wot_tm).unwrap();
+/// assert!(decode(&result.output, Encoding::Cbor).is_ok());
+/// ```
+pub fn convert_encoded(
+    input: &[u8],
+    input_encoding: Option<Encoding>,
+    output_encoding: Encoding,
+    conversion_function: &dyn Fn(String) -> Result<Conversion<String>>,
+) -> Result<Conversion<Vec<u8>>> {
+    let input_encoding = input_encoding.unwrap_or_else(|| Encoding::detect(input));
+    let conversion = conversion_function(decode(input, input_encoding)?)?;
+    let output = encode(&conversion.output, output_encoding)?;
+
+    Ok(Conversion::new(output, conversion.diagnostics))
+}
+
+/// Applies a format function to an encoded document and prints the result
+/// in the `output_encoding`, detecting the encoding of the input
+/// automatically unless an `input_encoding` is given.
+///
+/// JSON and YAML documents are printed as text, while CBOR documents are
+/// written to the standard output as raw bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::{format_sdf_definition, print_encoded};
+/// use sdf_wot_converter::encoding::{encode, Encoding};
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+/// let cbor = encode(&json_string, Encoding::Cbor).unwrap();
+///
+/// assert!(print_encoded(&cbor, None, Encoding::Yaml, &format_sdf_definition).is_ok());
+/// ```
+pub fn print_encoded(
+    input: &[u8],
+    input_encoding: Option<Encoding>,
+    output_encoding: Encoding,
+    format_function: &dyn Fn(String) -> Result<String>,
+) -> Result<()> {
+    let input_encoding = input_encoding.unwrap_or_else(|| Encoding::detect(input));
+    let output = encode(
+        &format_function(decode(input, input_encoding)?)?,
+        output_encoding,
+    )?;
+
+    let mut stdout = std::io::stdout();
+    stdout.write_all(&output)?;
+    if output_encoding == Encoding::Json {
+        writeln!(stdout)?;
+    }
+    stdout.flush().map_err(|e| e.into())
 }
 
 /// Deserializes an SDF model, converts it back into a JSON string
@@ -81,9 +276,63 @@ pub fn print_wot_tm_definition(json_string: String) -> Result<()> {
     print_definition::<ThingModel>(json_string)
 }
 
+/// Deserializes an SDF model and converts it back into a pretty-printed JSON
+/// `String`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::format_sdf_definition;
+///
+/// let result = format_sdf_definition(r#"{"sdfProperty":{"foo":{}}}"#.to_string());
+///
+/// assert!(result.unwrap().contains("sdfProperty"));
+/// ```
+pub fn format_sdf_definition(json_string: String) -> Result<String> {
+    format_definition::<SDFModel>(json_string)
+}
+
+/// Deserializes a WoT TD definition and converts it back into a
+/// pretty-printed JSON `String`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::format_wot_td_definition;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/example.td.json").unwrap();
+///
+/// assert!(format_wot_td_definition(json_string).is_ok());
+/// ```
+pub fn format_wot_td_definition(json_string: String) -> Result<String> {
+    format_definition::<ThingDescription>(json_string)
+}
+
+/// Deserializes a WoT TM definition and converts it back into a
+/// pretty-printed JSON `String`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::format_wot_tm_definition;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/example.tm.json").unwrap();
+///
+/// assert!(format_wot_tm_definition(json_string).is_ok());
+/// ```
+pub fn format_wot_tm_definition(json_string: String) -> Result<String> {
+    format_definition::<ThingModel>(json_string)
+}
+
 /// Deserializes an SDF Model JSON `String` and converts it into an WoT Thing Model
 /// JSON `String`.
 ///
+/// All `sdfRef`s contained in the model are resolved before the conversion.
+/// If the model has been created by [`convert_wot_tm_to_sdf_lossless`], the
+/// original Thing Model is restored from its `wot` quality blocks.
+///
 /// # Examples
 ///
 /// ```rust
@@ -95,15 +344,273 @@ pub fn print_wot_tm_definition(json_string: String) -> Result<()> {
 /// let result = convert_sdf_to_wot_tm(json_string);
 /// assert!(result.is_ok());
 /// ```
-pub fn convert_sdf_to_wot_tm(json_string: String) -> Result<String> {
-    deserialize_json_string::<SDFModel>(json_string)
-        .and_then(sdf_to_wot_tm)
-        .and_then(serialize_json::<ThingModel>)
+pub fn convert_sdf_to_wot_tm(json_string: String) -> Result<Conversion<String>> {
+    let sdf_model_json = resolve_sdf_model_json(&json_string)?;
+
+    if has_wot_annotations(&sdf_model_json) {
+        return restore_thing_model(&sdf_model_json).and_then(serialize_lossless);
+    }
+
+    convert_sdf_model_json(&sdf_model_json)
+        .map_err(|e| e.locate_in(&json_string))
+        .and_then(serialize_conversion::<ThingModel>)
+}
+
+/// Deserializes an SDF Model JSON `String` and converts it into an WoT Thing Model
+/// JSON `String` without losing any information.
+///
+/// Qualities that cannot be represented in a Thing Model, including the
+/// `sdfObject` and `sdfThing` hierarchy, are preserved as `sdf:`-prefixed terms.
+/// Converting the result back using [`convert_wot_tm_to_sdf`] restores a model
+/// that is semantically equivalent to the input with all `sdfRef`s resolved.
+/// Therefore, the returned conversion contains no diagnostics.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::{convert_sdf_to_wot_tm_lossless, convert_wot_tm_to_sdf};
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+///
+/// let thing_model = convert_sdf_to_wot_tm_lossless(json_string).unwrap().output;
+/// assert!(thing_model.contains("sdf:info"));
+///
+/// let result = convert_wot_tm_to_sdf(thing_model);
+/// assert!(result.unwrap().output.contains("defaultNamespace"));
+/// ```
+pub fn convert_sdf_to_wot_tm_lossless(json_string: String) -> Result<Conversion<String>> {
+    let sdf_model_json = resolve_sdf_model_json(&json_string)?;
+
+    if has_wot_annotations(&sdf_model_json) {
+        return restore_thing_model(&sdf_model_json).and_then(serialize_lossless);
+    }
+
+    let thing_model = deserialize_json_value::<SDFModel>(sdf_model_json.clone())
+        .map_err(|e| e.locate_in(&json_string))?;
+    let mut thing_model_json = serde_json::to_value(ThingModel::from(thing_model))?;
+    annotate_thing_model(&sdf_model_json, &mut thing_model_json)?;
+
+    serialize_lossless(thing_model_json)
+}
+
+/// Deserializes an SDF Model JSON `String` and converts it into an WoT Thing Model
+/// JSON `String`, enriching it with the protocol binding information of an SDF
+/// mapping file JSON `String`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_sdf_to_wot_tm_with_mapping;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+/// let mapping_json_string =
+///     fs::read_to_string("examples/sdf/example.sdf-mapping.json").unwrap();
+///
+/// let result = convert_sdf_to_wot_tm_with_mapping(json_string, mapping_json_string);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_sdf_to_wot_tm_with_mapping(
+    json_string: String,
+    mapping_json_string: String,
+) -> Result<Conversion<String>> {
+    let sdf_model_json = resolve_sdf_model_json(&json_string)?;
+    let sdf_mapping = deserialize_json_string::<SDFMapping>(mapping_json_string)?;
+
+    let mut conversion =
+        convert_sdf_model_json(&sdf_model_json).map_err(|e| e.locate_in(&json_string))?;
+    apply_mapping(&sdf_model_json, &mut conversion.output, &sdf_mapping)?;
+
+    serialize_conversion::<ThingModel>(conversion)
+}
+
+/// Deserializes an SDF Model JSON `String` and converts it into an WoT Thing Model
+/// JSON `String`, using a set of additional SDF models for resolving namespaced
+/// `sdfRef`s.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_sdf_to_wot_tm_with_model_set;
+/// use sdf_wot_converter::sdf::model_set::SDFModelSet;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+/// let model_set = SDFModelSet::from_paths(&["examples/sdf"]).unwrap();
+///
+/// let result = convert_sdf_to_wot_tm_with_model_set(json_string, &model_set);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_sdf_to_wot_tm_with_model_set(
+    json_string: String,
+    model_set: &SDFModelSet,
+) -> Result<Conversion<String>> {
+    from_json_str::<serde_json::Value>(&json_string)
+        .and_then(|x| model_set.resolve(&x))
+        .and_then(|x| convert_sdf_model_json(&x))
+        .map_err(|e| e.locate_in(&json_string))
+        .and_then(serialize_conversion::<ThingModel>)
+}
+
+/// Converts all models of an SDF model set into WoT Thing Model JSON `String`s.
+///
+/// Returns the names of the models together with the conversion results. The
+/// diagnostics refer to the models by their names.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_sdf_model_set_to_wot_tm;
+/// use sdf_wot_converter::sdf::model_set::SDFModelSet;
+///
+/// let model_set = SDFModelSet::from_paths(&[
+///     "examples/sdf/example.sdf.json",
+///     "examples/sdf/sdfobject-level-refs.sdf.json",
+/// ])
+/// .unwrap();
+///
+/// let result = convert_sdf_model_set_to_wot_tm(&model_set);
+/// assert_eq!(result.unwrap().output.len(), 2);
+/// ```
+pub fn convert_sdf_model_set_to_wot_tm(
+    model_set: &SDFModelSet,
+) -> Result<Conversion<Vec<(String, String)>>> {
+    let mut thing_models = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for (name, sdf_model) in model_set.resolve_all()? {
+        let mut conversion =
+            convert_sdf_model_json(&sdf_model).and_then(serialize_conversion::<ThingModel>)?;
+
+        set_document(&mut conversion.diagnostics, &name);
+        diagnostics.append(&mut conversion.diagnostics);
+        thing_models.push((name, conversion.output));
+    }
+
+    Ok(Conversion::new(thing_models, diagnostics))
+}
+
+/// Deserializes an SDF Model JSON `String` and converts it into a set of composed
+/// WoT Thing Models, one for every sdfObject and sdfThing, which are referenced
+/// by a top-level Thing Model using `tm:submodel` links.
+///
+/// Returns the file names of the Thing Models (`<name>.tm.json`, with `root_name`
+/// being used for the top-level model) together with their JSON `String`s.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_sdf_to_composed_wot_tms;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+///
+/// let result = convert_sdf_to_composed_wot_tms(json_string, "example").unwrap();
+/// assert_eq!(result.output[0].0, "example.tm.json");
+/// assert_eq!(result.output[1].0, "example_Switch.tm.json");
+/// ```
+pub fn convert_sdf_to_composed_wot_tms(
+    json_string: String,
+    root_name: &str,
+) -> Result<Conversion<Vec<(String, String)>>> {
+    let conversion = resolve_sdf_model_json(&json_string)
+        .and_then(|x| {
+            convert_json_value(&x, |sdf_model: SDFModel| {
+                convert_to_composed_thing_models(&sdf_model, root_name)
+            })
+        })
+        .map_err(|e| e.locate_in(&json_string))?;
+
+    let thing_models = conversion
+        .output
+        .into_iter()
+        .map(|(name, thing_model)| {
+            serialize_json::<ThingModel>(thing_model)
+                .map(|thing_model| (format!("{}.tm.json", name), thing_model))
+        })
+        .collect::<Result<Vec<(String, String)>>>()?;
+
+    Ok(Conversion::new(thing_models, conversion.diagnostics))
+}
+
+/// Deserializes an SDF model JSON `String` and converts it into a WoT Thing
+/// Description JSON `String`.
+///
+/// Forms for all affordances are generated using the base URI, protocol, and
+/// security scheme given by the `options`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_sdf_to_wot_td;
+/// use sdf_wot_converter::wot::td_from_sdf::{BindingOptions, Protocol};
+/// use sdf_wot_converter::wot::units::UnitVocabulary;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+/// let options = BindingOptions {
+///     base: "coap://example.org/switch/".to_string(),
+///     protocol: Protocol::Coap,
+///     security_scheme: "nosec".to_string(),
+///     units: UnitVocabulary::Symbol,
+/// };
+///
+/// let result = convert_sdf_to_wot_td(json_string, &options);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_sdf_to_wot_td(
+    json_string: String,
+    options: &BindingOptions,
+) -> Result<Conversion<String>> {
+    resolve_sdf_model_json(&json_string)
+        .and_then(|x| {
+            convert_json_value(&x, |sdf_model: SDFModel| {
+                convert_to_thing_description(sdf_model, options)
+            })
+        })
+        .map_err(|e| e.locate_in(&json_string))
+        .and_then(serialize_conversion)
+}
+
+/// Translates the SenML unit symbols of a WoT Thing Model JSON `String` into
+/// IRIs of the given `vocabulary`.
+///
+/// This way, the units of every Thing Model converted from SDF can be
+/// expressed using QUDT or OM IRIs. The prefix of the vocabulary is added to
+/// the `@context` if needed. Members that are not part of a Thing Model, like
+/// the `sdf:`-prefixed terms of lossless conversions, are not preserved.
+/// Thing Descriptions converted from SDF are translated according to the
+/// `units` of their [`BindingOptions`] instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::{convert_sdf_to_wot_tm, translate_wot_units};
+/// use sdf_wot_converter::wot::units::UnitVocabulary;
+///
+/// let json_string = r#"{"sdfProperty": {"power": {"type": "number", "unit": "W"}}}"#;
+/// let thing_model = convert_sdf_to_wot_tm(json_string.to_string()).unwrap().output;
+///
+/// let result = translate_wot_units(thing_model, UnitVocabulary::Om).unwrap();
+/// assert!(result.contains("om:watt"));
+/// ```
+pub fn translate_wot_units(json_string: String, vocabulary: UnitVocabulary) -> Result<String> {
+    let mut thing_model = deserialize_json_string::<ThingModel>(json_string)?;
+    translate_units(&mut thing_model, vocabulary);
+
+    serialize_json::<ThingModel>(thing_model)
 }
 
 /// Deserializes a WoT Thing Model JSON `String` and converts it into an SDF Model
 /// JSON `String`.
 ///
+/// `tm:ref`s pointing into the Thing Model itself are resolved before the
+/// conversion. Use [`convert_wot_tm_to_sdf_with_loader`] for Thing Models
+/// that extend or reference other Thing Models. If the Thing Model has been
+/// created by [`convert_sdf_to_wot_tm_lossless`], the original SDF model is
+/// restored from its `sdf:`-prefixed terms.
+///
 /// # Examples
 ///
 /// ```rust
@@ -115,40 +622,461 @@ pub fn convert_sdf_to_wot_tm(json_string: String) -> Result<String> {
 /// let result = convert_wot_tm_to_sdf(json_string);
 /// assert!(result.is_ok());
 /// ```
-pub fn convert_wot_tm_to_sdf(json_string: String) -> Result<String> {
-    deserialize_json_string::<ThingModel>(json_string)
-        .and_then(wot_tm_to_sdf)
-        .and_then(serialize_json::<SDFModel>)
+pub fn convert_wot_tm_to_sdf(json_string: String) -> Result<Conversion<String>> {
+    let loader = |href: &str| no_loader(href).map(|x| x.to_string());
+    convert_wot_tm_to_sdf_with_loader(json_string, &loader)
+}
+
+/// Deserializes a WoT Thing Model JSON `String` and converts it into an SDF Model
+/// JSON `String`, resolving `tm:extends` links and `tm:ref`s beforehand.
+///
+/// Extended and referenced Thing Models are retrieved as JSON `String`s using
+/// the `loader`, which is called with the `href` of the respective link or the
+/// document part of the `tm:ref`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_wot_tm_to_sdf_with_loader;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/extends/dimmer.tm.json").unwrap();
+/// let loader = |href: &str| {
+///     fs::read_to_string(format!("examples/wot/extends/{}", href)).map_err(|e| e.into())
+/// };
+///
+/// let result = convert_wot_tm_to_sdf_with_loader(json_string, &loader);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_wot_tm_to_sdf_with_loader(
+    json_string: String,
+    loader: &dyn Fn(&str) -> Result<String>,
+) -> Result<Conversion<String>> {
+    let thing_model_json = resolve_thing_model_json(&json_string, loader)?;
+
+    if has_sdf_annotations(&thing_model_json) {
+        return restore_sdf_model(&thing_model_json).and_then(serialize_lossless);
+    }
+
+    convert_json_value(&thing_model_json, |thing_model: ThingModel| {
+        Ok(convert_thing_model(thing_model, WotSource::ThingModel))
+    })
+    .map_err(|e| e.locate_in(&json_string))
+    .and_then(serialize_conversion::<SDFModel>)
+}
+
+/// Deserializes a WoT Thing Model JSON `String` and converts it into an SDF Model
+/// JSON `String` without losing any information.
+///
+/// Members that cannot be represented in an SDF model are preserved in `wot`
+/// quality blocks of the model and its definitions. Converting the result back
+/// using [`convert_sdf_to_wot_tm`] restores a Thing Model that is semantically
+/// equivalent to the input. Therefore, the returned conversion contains no
+/// diagnostics.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::{convert_sdf_to_wot_tm, convert_wot_tm_to_sdf_lossless};
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/example.tm.json").unwrap();
+///
+/// let sdf_model = convert_wot_tm_to_sdf_lossless(json_string).unwrap().output;
+/// assert!(sdf_model.contains("\"wot\""));
+///
+/// let result = convert_sdf_to_wot_tm(sdf_model);
+/// assert!(result.unwrap().output.contains("tm:ThingModel"));
+/// ```
+pub fn convert_wot_tm_to_sdf_lossless(json_string: String) -> Result<Conversion<String>> {
+    let loader = |href: &str| no_loader(href).map(|x| x.to_string());
+    convert_wot_tm_to_sdf_lossless_with_loader(json_string, &loader)
+}
+
+/// Deserializes a WoT Thing Model JSON `String` and converts it into an SDF Model
+/// JSON `String` without losing any information, resolving `tm:extends` links
+/// and `tm:ref`s beforehand using the `loader`.
+///
+/// See [`convert_wot_tm_to_sdf_lossless`] and [`convert_wot_tm_to_sdf_with_loader`]
+/// for details.
+pub fn convert_wot_tm_to_sdf_lossless_with_loader(
+    json_string: String,
+    loader: &dyn Fn(&str) -> Result<String>,
+) -> Result<Conversion<String>> {
+    let thing_model_json = resolve_thing_model_json(&json_string, loader)?;
+
+    if has_sdf_annotations(&thing_model_json) {
+        return restore_sdf_model(&thing_model_json).and_then(serialize_lossless);
+    }
+
+    let sdf_model = deserialize_json_value::<ThingModel>(thing_model_json.clone())
+        .map_err(|e| e.locate_in(&json_string))?;
+    let mut sdf_model_json = serde_json::to_value(SDFModel::from(sdf_model))?;
+    annotate_sdf_model(&thing_model_json, &mut sdf_model_json)?;
+
+    serialize_lossless(sdf_model_json)
+}
+
+/// Deserializes a composed WoT Thing Model JSON `String` and converts it into an
+/// SDF Model JSON `String`.
+///
+/// The submodels referenced via `tm:submodel` links are retrieved as JSON
+/// `String`s using the `loader`, which is called with the `href` of the
+/// respective link, and converted into sdfObjects or sdfThings.
+/// `tm:extends` links and `tm:ref`s are resolved using the same `loader`.
+/// Relative `href`s are resolved against the document containing the link, so
+/// that the `loader` is always called with `href`s relative to the input.
+/// The diagnostics of the submodels refer to them by their `href`s.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_composed_wot_tm_to_sdf;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/composed/lamp.tm.json").unwrap();
+/// let loader = |href: &str| {
+///     fs::read_to_string(format!("examples/wot/composed/{}", href)).map_err(|e| e.into())
+/// };
+///
+/// let result = convert_composed_wot_tm_to_sdf(json_string, &loader);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_composed_wot_tm_to_sdf(
+    json_string: String,
+    loader: &dyn Fn(&str) -> Result<String>,
+) -> Result<Conversion<String>> {
+    let submodel_jsons = RefCell::new(HashMap::new());
+    let thing_model_loader = |href: &str| {
+        let submodel_loader = |x: &str| loader(&resolve_href(href, x));
+        let json_string = loader(href)?;
+        let thing_model_json = resolve_thing_model_json(&json_string, &submodel_loader)
+            .map_err(|e| e.locate_in(&json_string))?;
+        let conversion = deserialize_reported::<ThingModel>(&thing_model_json)
+            .map_err(|e| e.locate_in(&json_string))?;
+        submodel_jsons
+            .borrow_mut()
+            .insert(href.to_string(), thing_model_json);
+        Ok(conversion)
+    };
+
+    let thing_model_json = resolve_thing_model_json(&json_string, loader)?;
+    let Conversion {
+        output: thing_model,
+        mut diagnostics,
+    } = deserialize_reported::<ThingModel>(&thing_model_json)
+        .map_err(|e| e.locate_in(&json_string))?;
+    let mut conversion = convert_composed_thing_model(thing_model, &thing_model_loader)?;
+    diagnostics.append(&mut conversion.diagnostics);
+
+    // The diagnostics of every submodel are listed in the order of its members.
+    sort_diagnostics(&mut diagnostics, &thing_model_json, None);
+    let submodel_jsons = submodel_jsons.into_inner();
+    for submodel_diagnostics in diagnostics.chunk_by_mut(|x, y| x.document == y.document) {
+        let document = submodel_diagnostics[0].document.clone();
+        if let Some(submodel_json) = document.as_ref().and_then(|x| submodel_jsons.get(x)) {
+            sort_diagnostics(submodel_diagnostics, submodel_json, document.as_deref());
+        }
+    }
+
+    serialize_conversion(Conversion::new(conversion.output, diagnostics))
+}
+
+/// Deserializes an SDF model JSON `String` and converts it into a WoT Thing
+/// Description JSON `String`, using the forms and Thing-level information of an
+/// SDF mapping file JSON `String` instead of the generated ones where given.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_sdf_to_wot_td_with_mapping;
+/// use sdf_wot_converter::wot::td_from_sdf::{BindingOptions, Protocol};
+/// use sdf_wot_converter::wot::units::UnitVocabulary;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+/// let mapping_json_string =
+///     fs::read_to_string("examples/sdf/example.sdf-mapping.json").unwrap();
+/// let options = BindingOptions {
+///     base: "coap://example.org/".to_string(),
+///     protocol: Protocol::Coap,
+///     security_scheme: "nosec".to_string(),
+///     units: UnitVocabulary::Symbol,
+/// };
+///
+/// let result = convert_sdf_to_wot_td_with_mapping(json_string, mapping_json_string, &options);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_sdf_to_wot_td_with_mapping(
+    json_string: String,
+    mapping_json_string: String,
+    options: &BindingOptions,
+) -> Result<Conversion<String>> {
+    let sdf_mapping = deserialize_json_string::<SDFMapping>(mapping_json_string)?;
+
+    resolve_sdf_model_json(&json_string)
+        .and_then(|x| {
+            convert_json_value(&x, |sdf_model: SDFModel| {
+                convert_to_thing_description_with_mapping(sdf_model, options, &sdf_mapping)
+            })
+        })
+        .map_err(|e| e.locate_in(&json_string))
+        .and_then(serialize_conversion)
+}
+
+/// Deserializes a WoT Thing Description JSON `String` and converts it into an
+/// SDF Model JSON `String` and an SDF mapping file JSON `String`.
+///
+/// The affordances are converted like those of a Thing Model, while the forms
+/// and security information of the Thing Description are exported into the
+/// mapping file.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_wot_td_to_sdf;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/example.td.json").unwrap();
+///
+/// let result = convert_wot_td_to_sdf(json_string);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_wot_td_to_sdf(json_string: String) -> Result<Conversion<(String, String)>> {
+    let thing_description = from_json_str::<serde_json::Value>(&json_string)?;
+    let Conversion {
+        output: (sdf_model, sdf_mapping),
+        diagnostics,
+    } = convert_thing_description(&thing_description).map_err(|e| e.locate_in(&json_string))?;
+
+    let output = (
+        serialize_json::<SDFModel>(sdf_model)?,
+        serialize_json::<SDFMapping>(sdf_mapping)?,
+    );
+    Ok(Conversion::new(output, diagnostics))
+}
+
+/// Deserializes a WoT Thing Model JSON `String` and instantiates a WoT Thing
+/// Description JSON `String` from it.
+///
+/// All `{{PLACEHOLDER}}`s of the Thing Model are substituted with the values
+/// of the `placeholders` map. Local `tm:ref`s are resolved beforehand.
+/// Returns an error listing all unresolved placeholders, forms without an
+/// `href`, and affordances without any forms.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::convert_wot_tm_to_td;
+/// use serde_json::json;
+/// use std::collections::HashMap;
+///
+/// let json_string = r#"
+///     {
+///         "@context": ["https://www.w3.org/2019/wot/td/v1"],
+///         "@type": "tm:ThingModel",
+///         "title": "Lamp",
+///         "properties": {
+///             "status": {
+///                 "type": "boolean",
+///                 "forms": [{"href": "{{BASE_URI}}/status"}]
+///             }
+///         }
+///     }
+/// "#;
+/// let mut placeholders = HashMap::new();
+/// placeholders.insert("BASE_URI".to_string(), json!("https://example.org"));
+///
+/// let result = convert_wot_tm_to_td(json_string.to_string(), &placeholders);
+/// assert!(result.is_ok());
+/// ```
+pub fn convert_wot_tm_to_td(
+    json_string: String,
+    placeholders: &PlaceholderMap,
+) -> Result<Conversion<String>> {
+    let thing_model = deserialize_json_string::<serde_json::Value>(json_string)
+        .and_then(|x| resolve_thing_model(&x, &no_loader))?;
+    instantiate_thing_model(&thing_model, placeholders).and_then(serialize_conversion)
+}
+
+/// Deserializes an SDF model JSON `String` and validates it against the rules
+/// of the SDF specification, returning all violations that have been found.
+///
+/// Besides the semantic constraints, this includes the structure defined by
+/// the CDDL definitions of SDF, such as unknown qualities or values of the
+/// wrong type. Inputs that are not valid JSON are rejected with an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::validate_sdf_model;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+///
+/// let result = validate_sdf_model(json_string);
+/// assert!(result.unwrap().is_empty());
+/// ```
+pub fn validate_sdf_model(json_string: String) -> Result<Vec<Violation>> {
+    deserialize_json_string::<serde_json::Value>(json_string).map(|x| validate_json(&x))
 }
 
-/// Converts an SDF model to a WoT Thing Model.
-fn sdf_to_wot_tm(sdf_model: SDFModel) -> Result<ThingModel> {
-    Ok(ThingModel::from(sdf_model))
+/// Deserializes a WoT Thing Description JSON `String` and validates it against
+/// the assertions of the TD specification, returning all violations that have
+/// been found.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::validate_wot_td;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/example.td.json").unwrap();
+///
+/// let result = validate_wot_td(json_string);
+/// assert!(result.unwrap().is_empty());
+/// ```
+pub fn validate_wot_td(json_string: String) -> Result<Vec<Violation>> {
+    deserialize_json_string::<serde_json::Value>(json_string)
+        .map(|x| validate_thing_description(&x))
 }
 
-/// Converts a WoT Thing Model to an SDF model.
-fn wot_tm_to_sdf(thing_model: ThingModel) -> Result<SDFModel> {
-    Ok(SDFModel::from(thing_model))
+/// Deserializes a WoT Thing Model JSON `String` and validates it against the
+/// assertions of the TD specification, returning all violations that have been
+/// found.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::validate_wot_tm;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/example.tm.json").unwrap();
+///
+/// let result = validate_wot_tm(json_string);
+/// assert!(result.unwrap().is_empty());
+/// ```
+pub fn validate_wot_tm(json_string: String) -> Result<Vec<Violation>> {
+    let loader = |href: &str| no_loader(href).map(|x| x.to_string());
+    validate_wot_tm_with_loader(json_string, &loader)
+}
+
+/// Deserializes a WoT Thing Model JSON `String` and validates it against the
+/// assertions of the TD specification after resolving its `tm:extends` links
+/// and `tm:ref`s, which are retrieved as JSON `String`s using the `loader`.
+pub fn validate_wot_tm_with_loader(
+    json_string: String,
+    loader: &dyn Fn(&str) -> Result<String>,
+) -> Result<Vec<Violation>> {
+    resolve_thing_model_json(&json_string, loader).map(|x| validate_thing_model(&x))
+}
+
+/// Compares two SDF model JSON `String`s, resolving all `sdfRef`s beforehand,
+/// and returns the differences between them.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::compare_sdf_models;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+///
+/// let result = compare_sdf_models(json_string.clone(), json_string);
+/// assert!(result.unwrap().is_empty());
+/// ```
+pub fn compare_sdf_models(first: String, second: String) -> Result<Vec<Difference>> {
+    let first_json = resolve_sdf_model_json(&first)?;
+    let second_json = resolve_sdf_model_json(&second)?;
+    deserialize_json_value::<SDFModel>(first_json.clone()).map_err(|e| e.locate_in(&first))?;
+    deserialize_json_value::<SDFModel>(second_json.clone()).map_err(|e| e.locate_in(&second))?;
+
+    Ok(comparison::compare_sdf_models(&first_json, &second_json))
+}
+
+/// Compares two WoT Thing Model JSON `String`s, resolving `tm:extends` links
+/// and `tm:ref`s beforehand, and returns the differences between them.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::compare_wot_tms;
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/wot/example.tm.json").unwrap();
+///
+/// let result = compare_wot_tms(json_string.clone(), json_string);
+/// assert!(result.unwrap().is_empty());
+/// ```
+pub fn compare_wot_tms(first: String, second: String) -> Result<Vec<Difference>> {
+    let loader = |href: &str| no_loader(href).map(|x| x.to_string());
+    let first_json = resolve_thing_model_json(&first, &loader)?;
+    let second_json = resolve_thing_model_json(&second, &loader)?;
+    deserialize_json_value::<ThingModel>(first_json.clone()).map_err(|e| e.locate_in(&first))?;
+    deserialize_json_value::<ThingModel>(second_json.clone()).map_err(|e| e.locate_in(&second))?;
+
+    Ok(comparison::compare_thing_models(&first_json, &second_json))
+}
+
+/// Compares an SDF model JSON `String` with a WoT Thing Model JSON `String`
+/// and returns the differences between them.
+///
+/// The SDF model is converted into a Thing Model beforehand, so the reported
+/// differences refer to the converted document in place of the SDF model.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::converter::{compare_sdf_with_wot_tm, convert_sdf_to_wot_tm};
+/// use std::fs;
+///
+/// let json_string = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+/// let thing_model = convert_sdf_to_wot_tm(json_string.clone()).unwrap().output;
+///
+/// let result = compare_sdf_with_wot_tm(json_string, thing_model);
+/// assert!(result.unwrap().is_empty());
+/// ```
+pub fn compare_sdf_with_wot_tm(sdf_model: String, thing_model: String) -> Result<Vec<Difference>> {
+    let converted_thing_model = convert_sdf_to_wot_tm(sdf_model)?.output;
+    compare_wot_tms(converted_thing_model, thing_model)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostics::{Diagnostic, DiagnosticKind};
 
     #[test]
-    fn test_sdf_to_wot_tm() {
-        let output = sdf_to_wot_tm(SDFModel::default());
-        assert!(output.is_ok());
-    }
+    fn test_serialize_conversion() {
+        let diagnostics = vec![Diagnostic::new(
+            "/foo",
+            DiagnosticKind::Dropped,
+            "bar".to_string(),
+        )];
+        let conversion = serialize_conversion(Conversion::new(
+            ThingModel::from(SDFModel::default()),
+            diagnostics.clone(),
+        ))
+        .unwrap();
 
-    #[test]
-    fn test_wot_tm_to_sdf() {
-        let output = wot_tm_to_sdf(ThingModel::default());
-        assert!(output.is_ok());
+        assert!(conversion.output.contains("@context"));
+        assert_eq!(conversion.diagnostics, diagnostics);
     }
 
     #[test]
     fn print_td_test() {
         assert!(print(ThingDescription::default()).is_ok());
     }
+
+    #[test]
+    fn format_wot_tm_definition_test() {
+        let json_string = r#"{
+            "@context": "https://www.w3.org/2022/wot/td/v1.1",
+            "properties": {"status": {"@type": "saref:OnOffState", "type": "string"}}
+        }"#;
+        let formatted = format_wot_tm_definition(json_string.to_string()).unwrap();
+
+        assert_eq!(formatted.matches("@type").count(), 1);
+        assert!(encode(&formatted, Encoding::Yaml).is_ok());
+    }
 }
@@ -0,0 +1,287 @@
+//! Diagnostics reporting information that is lost or altered during a
+//! conversion.
+//!
+//! The converters report every member of their input that they drop,
+//! approximate, or default together with a JSON pointer into the input.
+//! Members that are not part of the typed definitions of a model are already
+//! dropped when deserializing it and are found by comparing the input with its
+//! typed representation (see [`report_ignored_members`]).
+
+use crate::json_pointer::member_pointer;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+
+/// The kind of change a conversion has applied to a member of its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticKind {
+    /// The member has no equivalent in the output and has been omitted.
+    Dropped,
+    /// The member has been mapped to a construct with slightly different
+    /// semantics.
+    Approximated,
+    /// Missing information has been filled in with a default value.
+    Defaulted,
+}
+
+/// A single piece of information that has been lost or altered during a
+/// conversion.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// The name of the input document the diagnostic refers to if the
+    /// conversion involves more than one document.
+    pub document: Option<String>,
+    /// A JSON pointer to the affected member of the input.
+    pub pointer: String,
+    pub kind: DiagnosticKind,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(pointer: &str, kind: DiagnosticKind, message: String) -> Self {
+        Diagnostic {
+            document: None,
+            pointer: pointer.to_string(),
+            kind,
+            message,
+        }
+    }
+}
+
+/// The output of a conversion together with the diagnostics describing the
+/// information lost on the way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion<T> {
+    pub output: T,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl<T> Conversion<T> {
+    pub fn new(output: T, diagnostics: Vec<Diagnostic>) -> Self {
+        Conversion {
+            output,
+            diagnostics,
+        }
+    }
+
+    /// Transforms the output of the conversion, keeping its diagnostics.
+    pub fn map<U, F: FnOnce(T) -> U>(self, function: F) -> Conversion<U> {
+        Conversion {
+            output: function(self.output),
+            diagnostics: self.diagnostics,
+        }
+    }
+}
+
+/// Assigns the name of the document they refer to to a list of diagnostics.
+pub(crate) fn set_document(diagnostics: &mut [Diagnostic], document: &str) {
+    for diagnostic in diagnostics {
+        diagnostic.document = Some(document.to_string());
+    }
+}
+
+pub(crate) fn report(
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    kind: DiagnosticKind,
+    message: String,
+) {
+    diagnostics.push(Diagnostic::new(pointer, kind, message));
+}
+
+/// Reports a member that has no equivalent in the `target` format as dropped.
+pub(crate) fn report_dropped(
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    key: &str,
+    target: &str,
+) {
+    report(
+        diagnostics,
+        &member_pointer(pointer, key),
+        DiagnosticKind::Dropped,
+        format!("\"{}\" cannot be represented in {}", key, target),
+    );
+}
+
+/// Reports all members of the `input` that are missing from the serialization
+/// of its `typed` representation as dropped.
+///
+/// These members are either unknown to the typed definitions or have values
+/// the definitions do not accept, which is why they are ignored when
+/// deserializing the input.
+pub(crate) fn report_ignored_members(
+    diagnostics: &mut Vec<Diagnostic>,
+    input: &Value,
+    typed: &Value,
+    pointer: &str,
+) {
+    match (input, typed) {
+        (Value::Object(input), Value::Object(typed)) => {
+            for (key, member) in input {
+                let member_pointer = member_pointer(pointer, key);
+                match typed.get(key) {
+                    Some(typed_member) => {
+                        report_ignored_members(diagnostics, member, typed_member, &member_pointer)
+                    }
+                    None => report(
+                        diagnostics,
+                        &member_pointer,
+                        DiagnosticKind::Dropped,
+                        format!("\"{}\" is unknown or invalid and has been ignored", key),
+                    ),
+                }
+            }
+        }
+        (Value::Array(input), Value::Array(typed)) => {
+            for (index, (item, typed_item)) in input.iter().zip(typed).enumerate() {
+                let item_pointer = format!("{}/{}", pointer, index);
+                report_ignored_members(diagnostics, item, typed_item, &item_pointer);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Assigns every member of a JSON value its position in document order.
+fn collect_positions(value: &Value, pointer: String, positions: &mut HashMap<String, usize>) {
+    let position = positions.len();
+    positions.insert(pointer.clone(), position);
+
+    match value {
+        Value::Object(object) => {
+            for (key, member) in object {
+                collect_positions(member, member_pointer(&pointer, key), positions);
+            }
+        }
+        Value::Array(array) => {
+            for (index, item) in array.iter().enumerate() {
+                collect_positions(item, format!("{}/{}", pointer, index), positions);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Sorts diagnostics by the position of the members they refer to within the
+/// `input` (named `document`, if any), so that they are listed in document
+/// order.
+///
+/// Diagnostics referring to members that do not exist in the input (e.g.,
+/// defaulted ones) are placed at their closest existing parent, while
+/// diagnostics referring to other documents are moved to the end.
+pub(crate) fn sort_diagnostics(
+    diagnostics: &mut [Diagnostic],
+    input: &Value,
+    document: Option<&str>,
+) {
+    let mut positions = HashMap::new();
+    collect_positions(input, String::new(), &mut positions);
+
+    let get_position = |diagnostic: &Diagnostic| {
+        if diagnostic.document.as_deref() != document {
+            return usize::MAX;
+        }
+
+        let mut pointer = diagnostic.pointer.as_str();
+        loop {
+            if let Some(position) = positions.get(pointer) {
+                return *position;
+            }
+            match pointer.rfind('/') {
+                Some(index) => pointer = &pointer[..index],
+                None => return 0,
+            }
+        }
+    };
+
+    diagnostics.sort_by_cached_key(get_position);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn get_pointers(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|x| x.pointer.as_str()).collect()
+    }
+
+    #[test]
+    fn report_ignored_members_test() {
+        let input = json!({
+            "foo": 1,
+            "bar/baz": 2,
+            "qux": [{"a": 1, "b": 2}, {"c": 3}]
+        });
+        let typed = json!({"foo": 1, "qux": [{"a": 1}, {"c": 3}]});
+        let mut diagnostics = Vec::new();
+
+        report_ignored_members(&mut diagnostics, &input, &typed, "/sdfProperty");
+
+        assert_eq!(
+            get_pointers(&diagnostics),
+            vec!["/sdfProperty/bar~1baz", "/sdfProperty/qux/0/b"]
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "\"bar/baz\" is unknown or invalid and has been ignored"
+        );
+    }
+
+    #[test]
+    fn sort_diagnostics_test() {
+        let input = json!({
+            "info": {"title": "foo", "copyright": "bar"},
+            "sdfProperty": {"baz": {"type": "string"}},
+            "sdfProduct": {}
+        });
+        let mut diagnostics: Vec<Diagnostic> = [
+            "/sdfProduct",
+            "/sdfProperty/baz/type",
+            "/security",
+            "/sdfProperty/baz",
+            "/info/copyright",
+        ]
+        .iter()
+        .map(|x| Diagnostic::new(x, DiagnosticKind::Dropped, String::new()))
+        .collect();
+        diagnostics[0].document = Some("qux.tm.json".to_string());
+
+        sort_diagnostics(&mut diagnostics, &input, None);
+
+        assert_eq!(
+            get_pointers(&diagnostics),
+            vec![
+                "/security",
+                "/info/copyright",
+                "/sdfProperty/baz",
+                "/sdfProperty/baz/type",
+                "/sdfProduct"
+            ]
+        );
+
+        sort_diagnostics(&mut diagnostics, &input, Some("qux.tm.json"));
+        assert_eq!(get_pointers(&diagnostics)[0], "/sdfProduct");
+    }
+
+    #[test]
+    fn serialize_diagnostic_test() {
+        let mut diagnostics = vec![Diagnostic::new(
+            "/security",
+            DiagnosticKind::Defaulted,
+            "foo".to_string(),
+        )];
+
+        assert_eq!(
+            serde_json::to_value(&diagnostics).unwrap(),
+            json!([{"pointer": "/security", "kind": "defaulted", "message": "foo"}])
+        );
+
+        set_document(&mut diagnostics, "bar.tm.json");
+        assert_eq!(diagnostics[0].document.as_deref(), Some("bar.tm.json"));
+    }
+}
@@ -0,0 +1,209 @@
+//! Encodings of SDF and WoT documents besides JSON.
+//!
+//! The converters operate on JSON `String`s. Documents in other encodings are
+//! translated into JSON before a conversion and the results are translated
+//! back afterwards, so that the serde types in [`crate::sdf::definitions`] and
+//! [`crate::wot::definitions`] apply to all encodings unchanged.
+//!
+//! The order of the keys of a document is preserved in all encodings.
+
+use crate::{ConversionError, Result};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// The encoding of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Json,
+    /// The Concise Binary Object Representation
+    /// ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)).
+    Cbor,
+    /// YAML 1.2, which allows models to be authored with comments.
+    Yaml,
+}
+
+impl Encoding {
+    /// Determines the encoding of a document from its content.
+    ///
+    /// JSON documents describing a model start with an object (optionally
+    /// preceded by whitespace), while the first byte of a CBOR document can
+    /// never be an opening brace. Since CBOR maps and arrays start with bytes
+    /// that cannot start a UTF-8 sequence, all other text is treated as YAML.
+    pub fn detect(bytes: &[u8]) -> Encoding {
+        match bytes.iter().find(|x| !x.is_ascii_whitespace()) {
+            Some(b'{') | Some(b'[') | None => Encoding::Json,
+            Some(_) if std::str::from_utf8(bytes).is_ok() => Encoding::Yaml,
+            Some(_) => Encoding::Cbor,
+        }
+    }
+
+    /// Determines the encoding of a document from the extension of its path,
+    /// if known.
+    pub fn from_path(path: &str) -> Option<Encoding> {
+        if path.ends_with(".cbor") {
+            Some(Encoding::Cbor)
+        } else if path.ends_with(".yaml") || path.ends_with(".yml") {
+            Some(Encoding::Yaml)
+        } else if path.ends_with(".json") {
+            Some(Encoding::Json)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the file extension of documents in this encoding.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Encoding::Json => "json",
+            Encoding::Cbor => "cbor",
+            Encoding::Yaml => "yaml",
+        }
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = ConversionError;
+
+    fn from_str(encoding: &str) -> Result<Self> {
+        match encoding.to_lowercase().as_str() {
+            "json" => Ok(Encoding::Json),
+            "cbor" => Ok(Encoding::Cbor),
+            "yaml" | "yml" => Ok(Encoding::Yaml),
+            _ => Err(ConversionError::Unsupported(format!(
+                "Unsupported encoding \"{}\"!",
+                encoding
+            ))),
+        }
+    }
+}
+
+/// Translates an encoded document into a JSON `String`.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::encoding::{decode, encode, Encoding};
+///
+/// let cbor = encode(r#"{"sdfProperty": {}}"#, Encoding::Cbor).unwrap();
+///
+/// assert_eq!(decode(&cbor, Encoding::Cbor).unwrap(), r#"{"sdfProperty":{}}"#);
+/// ```
+pub fn decode(bytes: &[u8], encoding: Encoding) -> Result<String> {
+    match encoding {
+        Encoding::Json => {
+            String::from_utf8(bytes.to_vec()).map_err(|error| ConversionError::Parse {
+                message: format!("Invalid UTF-8 in JSON document: {}", error),
+                line: None,
+                column: None,
+                pointer: None,
+            })
+        }
+        Encoding::Cbor => {
+            let value: Value = ciborium::de::from_reader(bytes)?;
+            serde_json::to_string(&value).map_err(|e| e.into())
+        }
+        Encoding::Yaml => {
+            let mut value: serde_yaml::Value = serde_yaml::from_slice(bytes)?;
+            value.apply_merge()?;
+            serde_json::to_string(&value).map_err(|e| e.into())
+        }
+    }
+}
+
+/// Translates a JSON `String` into the given encoding.
+pub fn encode(json_string: &str, encoding: Encoding) -> Result<Vec<u8>> {
+    match encoding {
+        Encoding::Json => Ok(json_string.as_bytes().to_vec()),
+        Encoding::Cbor => {
+            let value: Value = serde_json::from_str(json_string)?;
+            let mut bytes = Vec::new();
+            ciborium::ser::into_writer(&value, &mut bytes)?;
+            Ok(bytes)
+        }
+        Encoding::Yaml => {
+            let value: serde_yaml::Value = serde_json::from_str(json_string)?;
+            serde_yaml::to_string(&value)
+                .map(String::into_bytes)
+                .map_err(|e| e.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_test() {
+        assert_eq!(Encoding::detect(b"  \n{}"), Encoding::Json);
+        assert_eq!(Encoding::detect(b""), Encoding::Json);
+        assert_eq!(Encoding::detect(&[0xa0]), Encoding::Cbor);
+        assert_eq!(Encoding::detect(b"# Comment\nfoo: bar"), Encoding::Yaml);
+    }
+
+    #[test]
+    fn from_path_test() {
+        assert_eq!(Encoding::from_path("foo.sdf.cbor"), Some(Encoding::Cbor));
+        assert_eq!(Encoding::from_path("foo.tm.json"), Some(Encoding::Json));
+        assert_eq!(Encoding::from_path("foo.tm.yml"), Some(Encoding::Yaml));
+        assert_eq!(Encoding::from_path("foo"), None);
+    }
+
+    #[test]
+    fn cbor_round_trip_test() {
+        let json_string = r#"{"foo":[1,2.5,"bar",true,null]}"#;
+        let cbor = encode(json_string, Encoding::Cbor).unwrap();
+
+        assert_eq!(Encoding::detect(&cbor), Encoding::Cbor);
+        assert_eq!(decode(&cbor, Encoding::Cbor).unwrap(), json_string);
+    }
+
+    #[test]
+    fn yaml_round_trip_test() {
+        let json_string = r#"{"foo":{"zoo":[1,2.5,"bar",true,null],"baz":"qux"}}"#;
+        let yaml = encode(json_string, Encoding::Yaml).unwrap();
+
+        assert_eq!(Encoding::detect(&yaml), Encoding::Yaml);
+        assert_eq!(decode(&yaml, Encoding::Yaml).unwrap(), json_string);
+    }
+
+    #[test]
+    fn yaml_decode_test() {
+        let yaml = b"# The switch
+sdfObject:
+  Switch:
+    sdfProperty:
+      value: &value
+        type: boolean
+      otherValue:
+        <<: *value
+        description: Another value
+";
+
+        assert_eq!(
+            decode(yaml, Encoding::Yaml).unwrap(),
+            r#"{"sdfObject":{"Switch":{"sdfProperty":{"value":{"type":"boolean"},"otherValue":{"description":"Another value","type":"boolean"}}}}}"#
+        );
+    }
+
+    #[test]
+    fn invalid_yaml_test() {
+        assert_eq!(
+            decode(b"foo: bar: baz", Encoding::Yaml),
+            Err(ConversionError::Parse {
+                message: "mapping values are not allowed in this context".to_string(),
+                line: Some(1),
+                column: Some(9),
+                pointer: None
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_cbor_test() {
+        assert!(matches!(
+            decode(&[0xbf, 0x61], Encoding::Cbor),
+            Err(ConversionError::Parse { .. })
+        ));
+    }
+}
@@ -0,0 +1,335 @@
+//! The error type returned by the conversion functions of this crate.
+
+use crate::json_pointer::{escape_pointer_token, locate_pointer};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{error, fmt, io};
+
+/// An error that occurred while reading, resolving, or converting a model.
+///
+/// The variants allow callers to distinguish malformed input from unresolvable
+/// references, inapplicable mapping files, and constructs that cannot be
+/// converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The input is not valid JSON or does not have the expected structure.
+    Parse {
+        /// A description of the problem.
+        message: String,
+        /// The (1-based) line of the problem if the input was parsed from text.
+        line: Option<usize>,
+        /// The (1-based) column of the problem if the input was parsed from text.
+        column: Option<usize>,
+        /// A JSON pointer to the offending value, if known.
+        pointer: Option<String>,
+    },
+    /// An `sdfRef`, a `tm:ref`, or a `tm:extends` or `tm:submodel` link could
+    /// not be resolved.
+    Reference(String),
+    /// An entry of an SDF mapping file could not be applied.
+    Mapping(String),
+    /// The input is well-formed but cannot be converted, e.g., because it
+    /// contains unresolved placeholders or lacks required information.
+    Invalid(String),
+    /// The input or the requested conversion uses an unsupported construct,
+    /// such as an unknown protocol or security scheme.
+    Unsupported(String),
+    /// A document could not be read, written, or fetched.
+    Io(String),
+}
+
+impl ConversionError {
+    /// Adds the position of the offending value within `json_string` to a
+    /// parse error that only refers to it by a JSON pointer, e.g., because it
+    /// was deserialized from a JSON value parsed from `json_string`.
+    ///
+    /// The error is returned unchanged if the pointer does not exist in the
+    /// text, e.g., because the value has been added by resolving a reference.
+    pub(crate) fn locate_in(self, json_string: &str) -> Self {
+        match self {
+            ConversionError::Parse {
+                message,
+                line: None,
+                column: None,
+                pointer: Some(pointer),
+            } => {
+                let position = locate_pointer(json_string, &pointer);
+                ConversionError::Parse {
+                    message,
+                    line: position.map(|x| x.0),
+                    column: position.map(|x| x.1),
+                    pointer: Some(pointer),
+                }
+            }
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::Parse {
+                message,
+                line,
+                column,
+                pointer,
+            } => {
+                write!(f, "{}", message)?;
+                if let Some(pointer) = pointer {
+                    write!(f, " at \"{}\"", pointer)?;
+                }
+                if let (Some(line), Some(column)) = (line, column) {
+                    write!(f, " (line {}, column {})", line, column)?;
+                }
+                Ok(())
+            }
+            ConversionError::Reference(message)
+            | ConversionError::Mapping(message)
+            | ConversionError::Invalid(message)
+            | ConversionError::Unsupported(message)
+            | ConversionError::Io(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for ConversionError {}
+
+impl From<serde_json::Error> for ConversionError {
+    fn from(error: serde_json::Error) -> Self {
+        create_parse_error(&error, None)
+    }
+}
+
+impl From<io::Error> for ConversionError {
+    fn from(error: io::Error) -> Self {
+        ConversionError::Io(error.to_string())
+    }
+}
+
+impl From<ciborium::de::Error<io::Error>> for ConversionError {
+    fn from(error: ciborium::de::Error<io::Error>) -> Self {
+        match error {
+            ciborium::de::Error::Io(error) if error.kind() == io::ErrorKind::UnexpectedEof => {
+                ConversionError::Parse {
+                    message: "Unexpected end of CBOR document".to_string(),
+                    line: None,
+                    column: None,
+                    pointer: None,
+                }
+            }
+            ciborium::de::Error::Io(error) => error.into(),
+            ciborium::de::Error::Syntax(offset) => ConversionError::Parse {
+                message: format!("Invalid CBOR at byte offset {}", offset),
+                line: None,
+                column: None,
+                pointer: None,
+            },
+            ciborium::de::Error::Semantic(offset, message) => ConversionError::Parse {
+                message: match offset {
+                    Some(offset) => format!("{} at byte offset {}", message, offset),
+                    None => message,
+                },
+                line: None,
+                column: None,
+                pointer: None,
+            },
+            ciborium::de::Error::RecursionLimitExceeded => ConversionError::Parse {
+                message: "CBOR document is nested too deeply".to_string(),
+                line: None,
+                column: None,
+                pointer: None,
+            },
+        }
+    }
+}
+
+impl From<serde_yaml::Error> for ConversionError {
+    fn from(error: serde_yaml::Error) -> Self {
+        let location = error.location();
+
+        ConversionError::Parse {
+            // The location is reported separately.
+            message: match &location {
+                Some(location) => error.to_string().replacen(
+                    &format!(" at line {} column {}", location.line(), location.column()),
+                    "",
+                    1,
+                ),
+                None => error.to_string(),
+            },
+            line: location.as_ref().map(|x| x.line()),
+            column: location.as_ref().map(|x| x.column()),
+            pointer: None,
+        }
+    }
+}
+
+impl From<ciborium::ser::Error<io::Error>> for ConversionError {
+    fn from(error: ciborium::ser::Error<io::Error>) -> Self {
+        match error {
+            ciborium::ser::Error::Io(error) => error.into(),
+            ciborium::ser::Error::Value(message) => ConversionError::Invalid(message),
+        }
+    }
+}
+
+fn create_parse_error(error: &serde_json::Error, pointer: Option<String>) -> ConversionError {
+    if error.is_io() {
+        return ConversionError::Io(error.to_string());
+    }
+
+    // serde_json reports unknown positions as line 0.
+    let (line, column) = match error.line() {
+        0 => (None, None),
+        line => (Some(line), Some(error.column())),
+    };
+
+    let message = error.to_string();
+    let position_suffix = format!(" at line {} column {}", error.line(), error.column());
+    let message = message
+        .strip_suffix(&position_suffix)
+        .unwrap_or(&message)
+        .to_string();
+
+    ConversionError::Parse {
+        message,
+        line,
+        column,
+        pointer,
+    }
+}
+
+fn create_pointer(path: &serde_path_to_error::Path) -> Option<String> {
+    let mut pointer = String::new();
+
+    for segment in path.iter() {
+        match segment {
+            serde_path_to_error::Segment::Seq { index } => pointer.push_str(&format!("/{}", index)),
+            serde_path_to_error::Segment::Map { key } => {
+                pointer.push_str(&format!("/{}", escape_pointer_token(key)))
+            }
+            serde_path_to_error::Segment::Enum { .. } => continue,
+            serde_path_to_error::Segment::Unknown => break,
+        }
+    }
+
+    match pointer.is_empty() {
+        true => None,
+        false => Some(pointer),
+    }
+}
+
+fn convert_path_error(error: serde_path_to_error::Error<serde_json::Error>) -> ConversionError {
+    let pointer = create_pointer(error.path());
+    create_parse_error(error.inner(), pointer)
+}
+
+/// Deserializes a JSON string, reporting the position and the JSON pointer of
+/// the offending value on failure.
+pub(crate) fn from_json_str<T: DeserializeOwned>(json_string: &str) -> Result<T, ConversionError> {
+    let mut deserializer = serde_json::Deserializer::from_str(json_string);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(convert_path_error)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserializes a JSON value, reporting the JSON pointer of the offending
+/// value on failure.
+pub(crate) fn from_json_value<T: DeserializeOwned>(value: Value) -> Result<T, ConversionError> {
+    serde_path_to_error::deserialize(value).map_err(convert_path_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Test {
+        #[allow(dead_code)]
+        items: Vec<u8>,
+    }
+
+    #[test]
+    fn syntax_error_test() {
+        let error = from_json_str::<Value>("{\n  \"foo\": [1, , 2]\n}").unwrap_err();
+
+        assert!(matches!(
+            error,
+            ConversionError::Parse {
+                line: Some(2),
+                column: Some(_),
+                ..
+            }
+        ));
+        assert!(error.to_string().contains("(line 2, column"));
+    }
+
+    #[test]
+    fn trailing_characters_test() {
+        assert!(from_json_str::<Value>("{} {}").is_err());
+    }
+
+    #[test]
+    fn data_error_pointer_test() {
+        let error = from_json_str::<Test>("{\"items\": [1, \"two\"]}").unwrap_err();
+
+        match error {
+            ConversionError::Parse { line, pointer, .. } => {
+                assert_eq!(line, Some(1));
+                assert_eq!(pointer.as_deref(), Some("/items/1"));
+            }
+            _ => panic!("Expected a parse error, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn value_error_pointer_test() {
+        let error = from_json_value::<Test>(json!({"items": [-1]})).unwrap_err();
+
+        assert_eq!(
+            error,
+            ConversionError::Parse {
+                message: "invalid value: integer `-1`, expected u8".to_string(),
+                line: None,
+                column: None,
+                pointer: Some("/items/0".to_string()),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid value: integer `-1`, expected u8 at \"/items/0\""
+        );
+    }
+
+    #[test]
+    fn locate_in_test() {
+        let json_string = "{\n  \"items\": [1, -1]\n}";
+        let error = from_json_value::<Test>(serde_json::from_str(json_string).unwrap())
+            .unwrap_err()
+            .locate_in(json_string);
+
+        assert!(matches!(
+            error,
+            ConversionError::Parse {
+                line: Some(2),
+                column: Some(16),
+                ..
+            }
+        ));
+
+        let error = from_json_value::<Test>(json!({"items": [-1]}))
+            .unwrap_err()
+            .locate_in("{}");
+
+        assert!(matches!(error, ConversionError::Parse { line: None, .. }));
+    }
+
+    #[test]
+    fn io_error_test() {
+        let error = ConversionError::from(io::Error::new(io::ErrorKind::NotFound, "not found"));
+
+        assert_eq!(error, ConversionError::Io("not found".to_string()));
+    }
+}
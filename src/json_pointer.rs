@@ -0,0 +1,184 @@
+//! Helpers for building and parsing [JSON pointers](https://www.rfc-editor.org/rfc/rfc6901),
+//! which are used to refer to definitions within SDF models and WoT documents.
+
+/// Escapes `~` and `/` within a reference token of a JSON pointer.
+pub(crate) fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Reverts the escaping of a reference token of a JSON pointer.
+pub(crate) fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Returns the pointer to the member `key` of the object `pointer` refers to.
+pub(crate) fn member_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, escape_pointer_token(key))
+}
+
+/// Returns the pointer to the definition `name` within the `quality` (e.g.,
+/// `sdfProperty` or `properties`) of the object `pointer` refers to.
+pub(crate) fn definition_pointer(pointer: &str, quality: &str, name: &str) -> String {
+    member_pointer(&member_pointer(pointer, quality), name)
+}
+
+/// Returns the (1-based) line and column at which the value `pointer` refers to
+/// starts within the JSON text `json_string`, if the value exists.
+pub(crate) fn locate_pointer(json_string: &str, pointer: &str) -> Option<(usize, usize)> {
+    let bytes = json_string.as_bytes();
+    let mut index = skip_whitespace(bytes, 0);
+
+    for token in pointer.split('/').skip(1) {
+        let token = unescape_pointer_token(token);
+        index = match bytes.get(index)? {
+            b'{' => locate_member(json_string, index, &token)?,
+            b'[' => locate_element(bytes, index, token.parse().ok()?)?,
+            _ => return None,
+        };
+    }
+
+    let preceding = &json_string[..index];
+    let line = preceding.matches('\n').count() + 1;
+    let line_start = preceding.rfind('\n').map_or(0, |x| x + 1);
+    let column = preceding[line_start..].chars().count() + 1;
+
+    Some((line, column))
+}
+
+/// Returns the index of the value of the member `key` of the object starting
+/// at `index`.
+fn locate_member(json_string: &str, index: usize, key: &str) -> Option<usize> {
+    let bytes = json_string.as_bytes();
+    let mut index = skip_whitespace(bytes, index + 1);
+
+    while *bytes.get(index)? == b'"' {
+        let key_end = skip_string(bytes, index)?;
+        let member_key = serde_json::from_str::<String>(&json_string[index..key_end]).ok()?;
+        index = skip_whitespace(bytes, key_end);
+        if *bytes.get(index)? != b':' {
+            return None;
+        }
+        index = skip_whitespace(bytes, index + 1);
+
+        if member_key == key {
+            return Some(index);
+        }
+
+        index = skip_whitespace(bytes, skip_value(bytes, index)?);
+        if *bytes.get(index)? != b',' {
+            return None;
+        }
+        index = skip_whitespace(bytes, index + 1);
+    }
+
+    None
+}
+
+/// Returns the index of the element `position` of the array starting at
+/// `index`.
+fn locate_element(bytes: &[u8], index: usize, position: usize) -> Option<usize> {
+    let mut index = skip_whitespace(bytes, index + 1);
+
+    for _ in 0..position {
+        index = skip_whitespace(bytes, skip_value(bytes, index)?);
+        if *bytes.get(index)? != b',' {
+            return None;
+        }
+        index = skip_whitespace(bytes, index + 1);
+    }
+
+    match bytes.get(index)? {
+        b']' => None,
+        _ => Some(index),
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], index: usize) -> usize {
+    let whitespace = bytes[index.min(bytes.len())..]
+        .iter()
+        .take_while(|x| x.is_ascii_whitespace())
+        .count();
+    index + whitespace
+}
+
+/// Returns the index following the string starting at `index`.
+fn skip_string(bytes: &[u8], index: usize) -> Option<usize> {
+    let mut index = index + 1;
+
+    loop {
+        match bytes.get(index)? {
+            b'\\' => index += 2,
+            b'"' => return Some(index + 1),
+            _ => index += 1,
+        }
+    }
+}
+
+/// Returns the index following the value starting at `index`.
+fn skip_value(bytes: &[u8], index: usize) -> Option<usize> {
+    match bytes.get(index)? {
+        b'"' => skip_string(bytes, index),
+        b'{' | b'[' => {
+            let mut index = index + 1;
+            let mut depth = 1;
+            while depth > 0 {
+                match bytes.get(index)? {
+                    b'"' => {
+                        index = skip_string(bytes, index)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => depth -= 1,
+                    _ => {}
+                }
+                index += 1;
+            }
+            Some(index)
+        }
+        _ => {
+            let length = bytes[index..]
+                .iter()
+                .take_while(|x| !matches!(x, b',' | b'}' | b']') && !x.is_ascii_whitespace())
+                .count();
+            Some(index + length)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_pointer_token_test() {
+        assert_eq!(escape_pointer_token("foo"), "foo");
+        assert_eq!(escape_pointer_token("a/b~c"), "a~1b~0c");
+        assert_eq!(unescape_pointer_token("a~1b~0c"), "a/b~c");
+        assert_eq!(unescape_pointer_token("~01"), "~1");
+        assert_eq!(
+            unescape_pointer_token(&escape_pointer_token("~1/~0")),
+            "~1/~0"
+        );
+    }
+
+    #[test]
+    fn member_pointer_test() {
+        assert_eq!(member_pointer("", "sdfObject"), "/sdfObject");
+        assert_eq!(member_pointer("/sdfObject", "a/b"), "/sdfObject/a~1b");
+    }
+
+    #[test]
+    fn locate_pointer_test() {
+        let json_string = "{\n  \"a/b\": {\"c\": [1, {\"d\": \"x]\"}, \"y\"]},\n  \"é\": \"z\"\n}";
+
+        assert_eq!(locate_pointer(json_string, ""), Some((1, 1)));
+        assert_eq!(locate_pointer(json_string, "/a~1b"), Some((2, 10)));
+        assert_eq!(locate_pointer(json_string, "/a~1b/c/0"), Some((2, 17)));
+        assert_eq!(locate_pointer(json_string, "/a~1b/c/1/d"), Some((2, 26)));
+        assert_eq!(locate_pointer(json_string, "/a~1b/c/2"), Some((2, 33)));
+        assert_eq!(locate_pointer(json_string, "/é"), Some((3, 8)));
+        assert_eq!(locate_pointer(json_string, "/a~1b/c/3"), None);
+        assert_eq!(locate_pointer(json_string, "/a~1b/e"), None);
+        assert_eq!(locate_pointer(json_string, "/é/f"), None);
+    }
+}
@@ -5,11 +5,20 @@
 //! The converter is both usable as a library that can be built upon in other WoT
 //! and SDF related projects as well as a tool for the command line.
 
-use std::{error, result};
+use std::result;
 
+pub mod comparison;
 pub mod converter;
+pub mod diagnostics;
+pub mod encoding;
+pub mod error;
+mod json_pointer;
+mod lossless;
 pub mod sdf;
 pub mod wot;
 
-pub type Result<T> = result::Result<T, Error>;
-type Error = Box<dyn error::Error>;
+pub use error::ConversionError;
+
+/// The result of the functions of this crate, failing with a [`ConversionError`]
+/// that indicates the kind of problem encountered.
+pub type Result<T> = result::Result<T, ConversionError>;
@@ -0,0 +1,709 @@
+//! Lossless round-tripping between SDF models and WoT Thing Models.
+//!
+//! Information that has no native counterpart in the target format is stashed
+//! in extension annotations, from which the original document is restored when
+//! converting back:
+//!
+//! - In Thing Models, the qualities of an SDF definition are stored as
+//!   `sdf:`-prefixed terms of the affordance it has been converted into, or of
+//!   the Thing Model itself for model-level qualities and the `sdfObject` and
+//!   `sdfThing` hierarchy. The term `sdf:pointer` records the location of
+//!   affordances that are nested within `sdfObject`s.
+//! - In SDF models, the members of a Thing Model or one of its affordances are
+//!   stored in a `wot` quality block of the corresponding definition.
+//!
+//! Only members that are lost or altered by converting the native parts of the
+//! document back are stashed. Members that the conversion back adds although
+//! they have not been present in the original are listed in `sdf:omit` and the
+//! `omit` member of the `wot` block, respectively.
+
+use crate::error::from_json_value;
+use crate::json_pointer::{escape_pointer_token, unescape_pointer_token};
+use crate::sdf::definitions::SDFModel;
+use crate::wot::definitions::{Context, ThingModel};
+use crate::wot::mapping::add_context_prefix;
+use crate::wot::tm_from_sdf::{get_prefixed_key, SDF_VOCABULARY};
+use crate::{ConversionError, Result};
+use serde_json::{Map, Value};
+
+const SDF_PREFIX: &str = "sdf:";
+const SDF_POINTER_TERM: &str = "sdf:pointer";
+const SDF_OMIT_TERM: &str = "sdf:omit";
+const WOT_QUALITY: &str = "wot";
+const WOT_OMIT_MEMBER: &str = "omit";
+const WOT_NAMESPACE: (&str, &str) = ("wot", "https://www.w3.org/2019/wot/td#");
+
+/// The SDF affordance keywords and the corresponding Thing Model members.
+const AFFORDANCE_KINDS: [(&str, &str); 3] = [
+    ("sdfProperty", "properties"),
+    ("sdfAction", "actions"),
+    ("sdfEvent", "events"),
+];
+
+/// The levels of an SDF model, which differ in the definitions that are
+/// converted into Thing Model affordances.
+#[derive(Clone, Copy)]
+enum Level {
+    Model,
+    Object,
+    Thing,
+}
+
+impl Level {
+    fn has_affordances(self) -> bool {
+        !matches!(self, Level::Thing)
+    }
+
+    fn nested_levels(self) -> &'static [(&'static str, Level)] {
+        match self {
+            Level::Object => &[],
+            Level::Model | Level::Thing => {
+                &[("sdfObject", Level::Object), ("sdfThing", Level::Thing)]
+            }
+        }
+    }
+}
+
+/// An SDF affordance definition together with its location.
+struct SdfAffordance {
+    keyword: &'static str,
+    member: &'static str,
+    key: String,
+    /// The name of the affordance in a Thing Model converted from the SDF model.
+    name: String,
+    pointer: String,
+}
+
+/// The members of a definition that are lost or altered by a conversion.
+#[derive(Default)]
+struct Extension {
+    members: Map<String, Value>,
+    omitted: Vec<String>,
+}
+
+fn affordance_pointer(pointer: &str, keyword: &str, key: &str) -> String {
+    format!("{}/{}/{}", pointer, keyword, escape_pointer_token(key))
+}
+
+fn into_object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(object) => object,
+        _ => Map::new(),
+    }
+}
+
+fn members<'a>(object: &'a Map<String, Value>, key: &str) -> Vec<(&'a String, &'a Value)> {
+    object
+        .get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Compares two JSON values, treating numbers as equal if they have the same
+/// value regardless of their representation (e.g., `1` and `1.0`).
+pub(crate) fn is_equivalent(first: &Value, second: &Value) -> bool {
+    match (first, second) {
+        (Value::Number(first), Value::Number(second)) => first.as_f64() == second.as_f64(),
+        (Value::Array(first), Value::Array(second)) => {
+            first.len() == second.len()
+                && first.iter().zip(second).all(|(x, y)| is_equivalent(x, y))
+        }
+        (Value::Object(first), Value::Object(second)) => {
+            first.len() == second.len()
+                && first
+                    .iter()
+                    .all(|(key, x)| second.get(key).is_some_and(|y| is_equivalent(x, y)))
+        }
+        _ => first == second,
+    }
+}
+
+/// Determines the members of `original` that are missing from or differ in
+/// `converted` and the members of `converted` that `original` lacks.
+fn diff_members(original: &Map<String, Value>, converted: &Map<String, Value>) -> Extension {
+    let members = original
+        .iter()
+        .filter(|(key, value)| {
+            !converted
+                .get(key.as_str())
+                .is_some_and(|x| is_equivalent(value, x))
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let omitted = converted
+        .keys()
+        .filter(|key| !original.contains_key(key.as_str()))
+        .cloned()
+        .collect();
+
+    Extension { members, omitted }
+}
+
+fn apply_extension(object: &mut Map<String, Value>, extension: Extension) {
+    for key in extension.omitted {
+        object.shift_remove(&key);
+    }
+    object.extend(extension.members);
+}
+
+fn read_omitted(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|x| x.as_str().map(String::from))
+        .collect()
+}
+
+fn insert_sdf_terms(object: &mut Map<String, Value>, extension: Extension) {
+    for (key, value) in extension.members {
+        object.insert(format!("{}{}", SDF_PREFIX, key), value);
+    }
+    if !extension.omitted.is_empty() {
+        object.insert(SDF_OMIT_TERM.to_string(), Value::from(extension.omitted));
+    }
+}
+
+fn read_sdf_terms(object: &Map<String, Value>) -> Extension {
+    let members = object
+        .iter()
+        .filter(|(key, _)| key.as_str() != SDF_POINTER_TERM && key.as_str() != SDF_OMIT_TERM)
+        .filter_map(|(key, value)| {
+            key.strip_prefix(SDF_PREFIX)
+                .map(|key| (key.to_string(), value.clone()))
+        })
+        .collect();
+
+    Extension {
+        members,
+        omitted: read_omitted(object.get(SDF_OMIT_TERM)),
+    }
+}
+
+fn insert_wot_block(object: &mut Map<String, Value>, extension: Extension) {
+    let mut block = extension.members;
+    if !extension.omitted.is_empty() {
+        block.insert(WOT_OMIT_MEMBER.to_string(), Value::from(extension.omitted));
+    }
+    if !block.is_empty() {
+        object.insert(WOT_QUALITY.to_string(), Value::Object(block));
+    }
+}
+
+fn read_wot_block(object: &Map<String, Value>) -> Extension {
+    let mut members = object
+        .get(WOT_QUALITY)
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let omitted = read_omitted(members.shift_remove(WOT_OMIT_MEMBER).as_ref());
+
+    Extension { members, omitted }
+}
+
+/// Collects the affordances of an SDF model that are converted into Thing
+/// Model affordances, named in the same way as during the conversion.
+fn collect_affordances(
+    definitions: &Map<String, Value>,
+    pointer: &str,
+    level: Level,
+    prefix: Option<String>,
+    affordances: &mut Vec<SdfAffordance>,
+) {
+    if level.has_affordances() {
+        for (keyword, member) in AFFORDANCE_KINDS {
+            for (key, _) in members(definitions, keyword) {
+                affordances.push(SdfAffordance {
+                    keyword,
+                    member,
+                    key: key.clone(),
+                    name: get_prefixed_key(prefix.clone(), key.clone()),
+                    pointer: affordance_pointer(pointer, keyword, key),
+                });
+            }
+        }
+    }
+
+    for (keyword, nested_level) in level.nested_levels() {
+        for (key, definition) in members(definitions, keyword) {
+            if let Some(definition) = definition.as_object() {
+                collect_affordances(
+                    definition,
+                    &affordance_pointer(pointer, keyword, key),
+                    *nested_level,
+                    Some(get_prefixed_key(prefix.clone(), key.clone())),
+                    affordances,
+                );
+            }
+        }
+    }
+}
+
+/// Removes all definitions that are converted into Thing Model affordances,
+/// leaving the hierarchy and qualities of the `sdfObject`s and `sdfThing`s.
+fn strip_affordances(definitions: &mut Map<String, Value>, level: Level) {
+    if level.has_affordances() {
+        for (keyword, _) in AFFORDANCE_KINDS {
+            definitions.shift_remove(keyword);
+        }
+    }
+
+    for (keyword, nested_level) in level.nested_levels() {
+        let nested_definitions = definitions
+            .get_mut(*keyword)
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flat_map(|x| x.values_mut())
+            .filter_map(Value::as_object_mut);
+        for definition in nested_definitions {
+            strip_affordances(definition, *nested_level);
+        }
+    }
+}
+
+fn without_members(object: &Map<String, Value>, keys: &[&str]) -> Map<String, Value> {
+    object
+        .iter()
+        .filter(|(key, _)| !keys.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+fn convert_to_sdf_model(thing_model: Map<String, Value>) -> Result<Map<String, Value>> {
+    let sdf_model = SDFModel::from(from_json_value::<ThingModel>(Value::Object(thing_model))?);
+    Ok(into_object(serde_json::to_value(sdf_model)?))
+}
+
+fn convert_to_thing_model(sdf_model: Map<String, Value>) -> Result<Map<String, Value>> {
+    let thing_model = ThingModel::from(from_json_value::<SDFModel>(Value::Object(sdf_model))?);
+    Ok(into_object(serde_json::to_value(thing_model)?))
+}
+
+/// Converts a Thing Model affordance into an SDF definition by embedding it
+/// into an otherwise empty Thing Model.
+fn convert_affordance_to_sdf(
+    context: Option<&Value>,
+    keyword: &str,
+    member: &str,
+    name: &str,
+    affordance: &Value,
+) -> Result<Map<String, Value>> {
+    let mut thing_model = Map::new();
+    if let Some(context) = context {
+        thing_model.insert("@context".to_string(), context.clone());
+    }
+    let affordances = vec![(name.to_string(), affordance.clone())]
+        .into_iter()
+        .collect();
+    thing_model.insert(member.to_string(), Value::Object(affordances));
+
+    let mut sdf_model = convert_to_sdf_model(thing_model)?;
+    let definition = sdf_model
+        .get_mut(keyword)
+        .and_then(Value::as_object_mut)
+        .and_then(|x| x.shift_remove(name));
+    Ok(definition.map(into_object).unwrap_or_default())
+}
+
+/// Converts an SDF affordance definition into a Thing Model affordance by
+/// embedding it into an otherwise empty SDF model.
+fn convert_affordance_to_thing_model(
+    keyword: &str,
+    member: &str,
+    key: &str,
+    definition: &Value,
+) -> Result<Map<String, Value>> {
+    let definitions = vec![(key.to_string(), definition.clone())]
+        .into_iter()
+        .collect();
+    let sdf_model = vec![(keyword.to_string(), Value::Object(definitions))]
+        .into_iter()
+        .collect();
+
+    let mut thing_model = convert_to_thing_model(sdf_model)?;
+    let affordance = thing_model
+        .get_mut(member)
+        .and_then(Value::as_object_mut)
+        .and_then(|x| x.shift_remove(key));
+    Ok(affordance.map(into_object).unwrap_or_default())
+}
+
+fn affordance_members() -> Vec<&'static str> {
+    AFFORDANCE_KINDS.iter().map(|(_, member)| *member).collect()
+}
+
+fn affordance_keywords() -> Vec<&'static str> {
+    AFFORDANCE_KINDS
+        .iter()
+        .map(|(keyword, _)| *keyword)
+        .chain(["sdfObject", "sdfThing"])
+        .collect()
+}
+
+/// Converts the model-level members of a Thing Model into an SDF model.
+fn convert_thing_model_level(thing_model: &Map<String, Value>) -> Result<Map<String, Value>> {
+    let sdf_model = convert_to_sdf_model(without_members(thing_model, &affordance_members()))?;
+    Ok(without_members(&sdf_model, &affordance_keywords()))
+}
+
+/// Converts the model-level qualities of an SDF model into a Thing Model.
+fn convert_sdf_model_level(sdf_model: &Map<String, Value>) -> Result<Map<String, Value>> {
+    let thing_model = convert_to_thing_model(without_members(sdf_model, &affordance_keywords()))?;
+    Ok(without_members(&thing_model, &affordance_members()))
+}
+
+fn object_at<'a>(value: &'a Value, pointer: &str) -> Option<&'a Map<String, Value>> {
+    value.pointer(pointer).and_then(Value::as_object)
+}
+
+/// Inserts a definition into an SDF model at the location a JSON pointer
+/// refers to, creating missing parent definitions.
+fn insert_at_pointer(
+    sdf_model: &mut Map<String, Value>,
+    pointer: &str,
+    definition: Map<String, Value>,
+) -> Result<()> {
+    let invalid_pointer_error = || {
+        ConversionError::Invalid(format!(
+            "Cannot restore an SDF definition at \"{}\"!",
+            pointer
+        ))
+    };
+
+    let mut tokens: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(unescape_pointer_token)
+        .collect();
+    let key = tokens.pop().ok_or_else(invalid_pointer_error)?;
+
+    let mut parent = sdf_model;
+    for token in tokens {
+        parent = parent
+            .entry(token)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(invalid_pointer_error)?;
+    }
+    parent.insert(key, Value::Object(definition));
+
+    Ok(())
+}
+
+/// Adds `sdf:` terms to a Thing Model that has been converted from an SDF
+/// model, preserving everything the Thing Model cannot represent natively.
+pub(crate) fn annotate_thing_model(sdf_model: &Value, thing_model: &mut Value) -> Result<()> {
+    let (sdf_object, thing_model) = match (sdf_model.as_object(), thing_model.as_object_mut()) {
+        (Some(sdf_object), Some(thing_model)) => (sdf_object, thing_model),
+        _ => return Ok(()),
+    };
+
+    if let Some(context) = thing_model.get_mut("@context") {
+        let mut typed_context = from_json_value::<Context>(context.clone())?;
+        add_context_prefix(&mut typed_context, SDF_VOCABULARY.0, SDF_VOCABULARY.1);
+        *context = serde_json::to_value(typed_context)?;
+    }
+
+    let mut affordances = Vec::new();
+    collect_affordances(sdf_object, "", Level::Model, None, &mut affordances);
+    let context = thing_model.get("@context").cloned();
+
+    for affordance in affordances {
+        let original = object_at(sdf_model, &affordance.pointer)
+            .cloned()
+            .unwrap_or_default();
+        let tm_affordance = match thing_model
+            .get_mut(affordance.member)
+            .and_then(|x| x.get_mut(&affordance.name))
+            .and_then(Value::as_object_mut)
+        {
+            Some(tm_affordance) => tm_affordance,
+            None => continue,
+        };
+
+        let converted = convert_affordance_to_sdf(
+            context.as_ref(),
+            affordance.keyword,
+            affordance.member,
+            &affordance.name,
+            &Value::Object(tm_affordance.clone()),
+        )?;
+        insert_sdf_terms(tm_affordance, diff_members(&original, &converted));
+
+        if affordance.pointer != affordance_pointer("", affordance.keyword, &affordance.name) {
+            tm_affordance.insert(
+                SDF_POINTER_TERM.to_string(),
+                Value::String(format!("#{}", affordance.pointer)),
+            );
+        }
+    }
+
+    let mut skeleton = sdf_object.clone();
+    strip_affordances(&mut skeleton, Level::Model);
+    let converted = convert_thing_model_level(thing_model)?;
+    insert_sdf_terms(thing_model, diff_members(&skeleton, &converted));
+
+    Ok(())
+}
+
+fn has_sdf_terms(object: &Map<String, Value>) -> bool {
+    object.keys().any(|key| key.starts_with(SDF_PREFIX))
+}
+
+/// Determines whether a Thing Model contains `sdf:` terms added by
+/// [`annotate_thing_model`].
+pub(crate) fn has_sdf_annotations(thing_model: &Value) -> bool {
+    let thing_model = match thing_model.as_object() {
+        Some(thing_model) => thing_model,
+        None => return false,
+    };
+
+    has_sdf_terms(thing_model)
+        || AFFORDANCE_KINDS.iter().any(|(_, member)| {
+            members(thing_model, member)
+                .iter()
+                .filter_map(|(_, affordance)| affordance.as_object())
+                .any(has_sdf_terms)
+        })
+}
+
+/// Restores the SDF model a Thing Model annotated by [`annotate_thing_model`]
+/// has been converted from.
+pub(crate) fn restore_sdf_model(thing_model: &Value) -> Result<Value> {
+    let thing_model = match thing_model.as_object() {
+        Some(thing_model) => thing_model,
+        None => return Ok(Value::Object(Map::new())),
+    };
+
+    let mut sdf_model = convert_thing_model_level(thing_model)?;
+    apply_extension(&mut sdf_model, read_sdf_terms(thing_model));
+
+    let context = thing_model.get("@context");
+    for (keyword, member) in AFFORDANCE_KINDS {
+        for (name, affordance) in members(thing_model, member) {
+            let mut definition =
+                convert_affordance_to_sdf(context, keyword, member, name, affordance)?;
+            let affordance = affordance.as_object().cloned().unwrap_or_default();
+            apply_extension(&mut definition, read_sdf_terms(&affordance));
+
+            let pointer = match affordance.get(SDF_POINTER_TERM).and_then(Value::as_str) {
+                Some(pointer) => pointer.trim_start_matches('#').to_string(),
+                None => affordance_pointer("", keyword, name),
+            };
+            insert_at_pointer(&mut sdf_model, &pointer, definition)?;
+        }
+    }
+
+    Ok(Value::Object(sdf_model))
+}
+
+/// Adds `wot` quality blocks to an SDF model that has been converted from a
+/// Thing Model, preserving everything the SDF model cannot represent natively.
+pub(crate) fn annotate_sdf_model(thing_model: &Value, sdf_model: &mut Value) -> Result<()> {
+    let (thing_model, sdf_object) = match (thing_model.as_object(), sdf_model.as_object_mut()) {
+        (Some(thing_model), Some(sdf_object)) => (thing_model, sdf_object),
+        _ => return Ok(()),
+    };
+
+    if let Some(namespace) = sdf_object
+        .entry("namespace")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+    {
+        namespace
+            .entry(WOT_NAMESPACE.0)
+            .or_insert_with(|| Value::String(WOT_NAMESPACE.1.to_string()));
+    }
+
+    let mut affordances = Vec::new();
+    collect_affordances(sdf_object, "", Level::Model, None, &mut affordances);
+
+    for affordance in affordances {
+        let original = thing_model
+            .get(affordance.member)
+            .and_then(|x| x.get(&affordance.key))
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(definition) = sdf_model
+            .pointer_mut(&affordance.pointer)
+            .and_then(Value::as_object_mut)
+        {
+            let converted = convert_affordance_to_thing_model(
+                affordance.keyword,
+                affordance.member,
+                &affordance.key,
+                &Value::Object(definition.clone()),
+            )?;
+            insert_wot_block(definition, diff_members(&original, &converted));
+        }
+    }
+
+    if let Some(sdf_object) = sdf_model.as_object_mut() {
+        let original = without_members(thing_model, &affordance_members());
+        let converted = convert_sdf_model_level(sdf_object)?;
+        insert_wot_block(sdf_object, diff_members(&original, &converted));
+    }
+
+    Ok(())
+}
+
+/// Determines whether an SDF model contains `wot` quality blocks added by
+/// [`annotate_sdf_model`].
+pub(crate) fn has_wot_annotations(sdf_model: &Value) -> bool {
+    let sdf_object = match sdf_model.as_object() {
+        Some(sdf_object) => sdf_object,
+        None => return false,
+    };
+
+    let mut affordances = Vec::new();
+    collect_affordances(sdf_object, "", Level::Model, None, &mut affordances);
+
+    sdf_object.contains_key(WOT_QUALITY)
+        || affordances.iter().any(|affordance| {
+            object_at(sdf_model, &affordance.pointer)
+                .is_some_and(|definition| definition.contains_key(WOT_QUALITY))
+        })
+}
+
+/// Restores the Thing Model an SDF model annotated by [`annotate_sdf_model`]
+/// has been converted from.
+///
+/// Affordances are named after the keys of their SDF definitions.
+pub(crate) fn restore_thing_model(sdf_model: &Value) -> Result<Value> {
+    let sdf_object = match sdf_model.as_object() {
+        Some(sdf_object) => sdf_object,
+        None => return Ok(Value::Object(Map::new())),
+    };
+
+    let mut thing_model = convert_sdf_model_level(sdf_object)?;
+    apply_extension(&mut thing_model, read_wot_block(sdf_object));
+
+    let mut affordances = Vec::new();
+    collect_affordances(sdf_object, "", Level::Model, None, &mut affordances);
+
+    for affordance in affordances {
+        let definition = object_at(sdf_model, &affordance.pointer)
+            .cloned()
+            .unwrap_or_default();
+        let mut tm_affordance = convert_affordance_to_thing_model(
+            affordance.keyword,
+            affordance.member,
+            &affordance.key,
+            &Value::Object(definition.clone()),
+        )?;
+        apply_extension(&mut tm_affordance, read_wot_block(&definition));
+
+        if let Some(affordances) = thing_model
+            .entry(affordance.member)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+        {
+            affordances.insert(affordance.key, Value::Object(tm_affordance));
+        }
+    }
+
+    Ok(Value::Object(thing_model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn is_equivalent_test() {
+        assert!(is_equivalent(
+            &json!({"minimum": 0, "enum": [1, 2.5]}),
+            &json!({"minimum": 0.0, "enum": [1.0, 2.5]})
+        ));
+        assert!(!is_equivalent(&json!({"minimum": 0}), &json!({})));
+        assert!(!is_equivalent(&json!([1, 2]), &json!([2, 1])));
+    }
+
+    #[test]
+    fn sdf_round_trip_test() {
+        let sdf_model = json!({
+            "info": {
+                "title": "Example",
+                "version": "2021-06-01",
+                "copyright": "Copyright 2021 Example Corp.",
+                "license": "BSD-3-Clause"
+            },
+            "sdfObject": {
+                "Switch": {
+                    "label": "Switch",
+                    "sdfRequired": ["#/sdfObject/Switch/sdfProperty/value"],
+                    "sdfProperty": {
+                        "value": {
+                            "type": "boolean",
+                            "sdfType": "byte-string"
+                        }
+                    }
+                }
+            }
+        });
+        let mut thing_model = serde_json::to_value(ThingModel::from(
+            from_json_value::<SDFModel>(sdf_model.clone()).unwrap(),
+        ))
+        .unwrap();
+
+        annotate_thing_model(&sdf_model, &mut thing_model).unwrap();
+
+        let property = &thing_model["properties"]["SwitchValue"];
+        assert_eq!(property["sdf:sdfType"], json!("byte-string"));
+        assert_eq!(
+            property["sdf:pointer"],
+            json!("#/sdfObject/Switch/sdfProperty/value")
+        );
+        assert_eq!(thing_model["sdf:info"], sdf_model["info"]);
+        assert!(has_sdf_annotations(&thing_model));
+
+        let restored = restore_sdf_model(&thing_model).unwrap();
+        assert!(is_equivalent(&restored, &sdf_model));
+    }
+
+    #[test]
+    fn thing_model_round_trip_test() {
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "@type": "tm:ThingModel",
+            "title": "Lamp",
+            "version": {"model": "1.0.0", "instance": "1.0.0"},
+            "properties": {
+                "status": {
+                    "type": "string",
+                    "readOnly": true,
+                    "forms": [{"href": "status"}]
+                }
+            }
+        });
+        let mut sdf_model = serde_json::to_value(SDFModel::from(
+            from_json_value::<ThingModel>(thing_model.clone()).unwrap(),
+        ))
+        .unwrap();
+
+        annotate_sdf_model(&thing_model, &mut sdf_model).unwrap();
+
+        let property = &sdf_model["sdfObject"]["Lamp"]["sdfProperty"]["status"];
+        assert_eq!(property["wot"], json!({"forms": [{"href": "status"}]}));
+        assert_eq!(sdf_model["wot"]["@type"], json!("tm:ThingModel"));
+        assert!(has_wot_annotations(&sdf_model));
+
+        let restored = restore_thing_model(&sdf_model).unwrap();
+        assert!(is_equivalent(&restored, &thing_model));
+    }
+
+    #[test]
+    fn insert_at_pointer_test() {
+        let mut sdf_model = Map::new();
+        insert_at_pointer(&mut sdf_model, "/sdfObject/a~1b/sdfProperty/c", Map::new()).unwrap();
+
+        assert_eq!(
+            Value::Object(sdf_model),
+            json!({"sdfObject": {"a/b": {"sdfProperty": {"c": {}}}}})
+        );
+    }
+}
@@ -1,6 +1,12 @@
-use sdf_wot_converter::{converter, Result};
+use sdf_wot_converter::diagnostics::{Conversion, Diagnostic};
+use sdf_wot_converter::encoding::{self, Encoding};
+use sdf_wot_converter::sdf::model_set::{SDFModelSet, SDF_FILE_EXTENSIONS};
+use sdf_wot_converter::wot::td_from_sdf::{BindingOptions, Protocol};
+use sdf_wot_converter::wot::units::UnitVocabulary;
+use sdf_wot_converter::{converter, ConversionError, Result};
 
 use clap::{app_from_crate, App, Arg, ArgGroup};
+use std::path::Path;
 use std::{env, fs};
 use url::Url;
 
@@ -9,11 +15,27 @@ const SDF_OUTPUT_NAME: &str = "SDF output file";
 const TM_INPUT_NAME: &str = "TM input file";
 const TM_OUTPUT_NAME: &str = "TM output file";
 const TD_INPUT_NAME: &str = "TD input file";
+const TD_OUTPUT_NAME: &str = "TD output file";
+const SDF_MAPPING_OUTPUT_NAME: &str = "SDF mapping output file";
+const SDF_MAPPING_INPUT_NAME: &str = "SDF mapping input file";
+const BASE_URI_NAME: &str = "base URI";
+const PROTOCOL_NAME: &str = "protocol";
+const SECURITY_SCHEME_NAME: &str = "security scheme";
+const SDF_CONTEXT_NAME: &str = "SDF context";
+const COMPOSED_FLAG_NAME: &str = "composed";
+const REPORT_NAME: &str = "report";
+const LOSSLESS_FLAG_NAME: &str = "lossless";
+const INPUT_FORMAT_NAME: &str = "input format";
+const OUTPUT_FORMAT_NAME: &str = "output format";
+const UNITS_NAME: &str = "unit vocabulary";
 
-type ConversionFunction<'a> = &'a dyn Fn(String) -> Result<String>;
-type PrintFunction<'a> = &'a dyn Fn(String) -> Result<()>;
+/// The result of a command, which may also fail due to invalid arguments.
+type CommandResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-type MatchSubcommandFunction<'a> = &'a dyn Fn(&&clap::ArgMatches) -> Result<()>;
+type ConversionFunction<'a> = &'a dyn Fn(String) -> Result<Conversion<String>>;
+type FormatFunction<'a> = &'a dyn Fn(String) -> Result<String>;
+
+type MatchSubcommandFunction<'a> = &'a dyn Fn(&&clap::ArgMatches) -> CommandResult<()>;
 
 #[derive(Debug, PartialEq)]
 enum InputPathType {
@@ -40,74 +62,509 @@ fn determine_path_type(path: &str) -> InputPathType {
     }
 }
 
-fn write_to_file(path: &str, content: String) -> Result<()> {
+fn write_to_file(path: &str, content: String) -> CommandResult<()> {
     fs::write(path, content).map_err(|e| e.into())
 }
 
-fn write_to_another_file(input_path: &str, output_path: &str) -> Result<()> {
-    let content = get_json(input_path)?;
-    write_to_file(output_path, content)
+fn write_to_another_file(
+    input_path: &str,
+    output_path: &str,
+    encodings: Encodings,
+) -> CommandResult<Vec<Diagnostic>> {
+    let content = encodings.read(input_path)?;
+    encodings.write(output_path, content)?;
+    Ok(Vec::new())
 }
 
-fn get_json_from_file(path: &str) -> Result<String> {
-    fs::read_to_string(&path).map_err(|e| e.into())
+fn get_bytes_from_file(path: &str) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| e.into())
 }
 
-fn get_json_from_url(url: &str) -> Result<String> {
-    Ok(reqwest::blocking::get(url)?.text()?)
+fn get_bytes_from_url(url: &str) -> Result<Vec<u8>> {
+    reqwest::blocking::get(url)
+        .and_then(|response| response.bytes())
+        .map(|bytes| bytes.to_vec())
+        .map_err(|error| ConversionError::Io(format!("Cannot fetch \"{}\": {}", url, error)))
 }
 
-fn get_json(path: &str) -> Result<String> {
+fn get_bytes(path: &str) -> Result<Vec<u8>> {
     let path_type = determine_path_type(path);
     match path_type {
-        InputPathType::File => get_json_from_file(path),
-        InputPathType::ValidUrl => get_json_from_url(path),
-        InputPathType::InvalidUrl => Err("Invalid URL or file path!".into()),
+        InputPathType::File => get_bytes_from_file(path),
+        InputPathType::ValidUrl => get_bytes_from_url(path),
+        InputPathType::InvalidUrl => Err(ConversionError::Io(format!(
+            "Invalid URL or file path \"{}\"!",
+            path
+        ))),
+    }
+}
+
+/// Retrieves a document as a JSON `String`, detecting its encoding
+/// automatically.
+fn get_json(path: &str) -> Result<String> {
+    Encodings::default().read(path)
+}
+
+/// Splits the path of an SDF file into its stem and its extension.
+fn split_sdf_extension(path: &str) -> Option<(&str, &str)> {
+    SDF_FILE_EXTENSIONS
+        .iter()
+        .find_map(|extension| path.strip_suffix(extension).map(|x| (x, *extension)))
+}
+
+/// The encodings of the documents read and written by a command.
+///
+/// Unless given explicitly, the encoding of a document is determined by its
+/// file extension. Input documents without a known extension are detected
+/// from their content, output documents default to JSON.
+#[derive(Debug, Clone, Copy, Default)]
+struct Encodings {
+    input: Option<Encoding>,
+    output: Option<Encoding>,
+}
+
+impl Encodings {
+    fn from_arguments(command: &clap::ArgMatches) -> Result<Self> {
+        // Not every command accepts both options, so unknown arguments are
+        // treated as absent.
+        let parse = |name: &str| {
+            command
+                .try_get_one::<String>(name)
+                .ok()
+                .flatten()
+                .map(|x| x.parse())
+                .transpose()
+        };
+
+        Ok(Encodings {
+            input: parse(INPUT_FORMAT_NAME)?,
+            output: parse(OUTPUT_FORMAT_NAME)?,
+        })
+    }
+
+    fn input_encoding(&self, path: &str) -> Option<Encoding> {
+        self.input.or_else(|| Encoding::from_path(path))
+    }
+
+    fn output_encoding(&self, path: &str) -> Encoding {
+        self.output
+            .or_else(|| Encoding::from_path(path))
+            .unwrap_or(Encoding::Json)
+    }
+
+    /// Reads an input document as a JSON `String`.
+    fn read(&self, path: &str) -> Result<String> {
+        let bytes = get_bytes(path)?;
+        encoding::decode(
+            &bytes,
+            self.input_encoding(path)
+                .unwrap_or_else(|| Encoding::detect(&bytes)),
+        )
+    }
+
+    /// Writes a JSON `String` to an output document.
+    fn write(&self, path: &str, json_string: String) -> CommandResult<()> {
+        let bytes = encoding::encode(&json_string, self.output_encoding(path))?;
+        fs::write(path, bytes).map_err(|e| e.into())
     }
 }
 
-fn print_model_from_file(path: &str, print_function: PrintFunction) -> Result<()> {
-    let json_string = get_json(path)?;
-    print_function(json_string)
+fn print_model_from_file(
+    path: &str,
+    encodings: Encodings,
+    format_function: FormatFunction,
+) -> CommandResult<()> {
+    let bytes = get_bytes(path)?;
+    converter::print_encoded(
+        &bytes,
+        encodings.input_encoding(path),
+        encodings.output.unwrap_or(Encoding::Json),
+        format_function,
+    )
+    .map_err(|e| e.into())
 }
 
 fn convert(
     input_path: &str,
     output_path: &str,
+    encodings: Encodings,
     conversion_function: ConversionFunction,
-) -> Result<()> {
-    let input_string = get_json(input_path)?;
-    let output_string = conversion_function(input_string)?;
-    write_to_file(output_path, output_string)
+) -> CommandResult<Vec<Diagnostic>> {
+    let input = get_bytes(input_path)?;
+    let conversion = converter::convert_encoded(
+        &input,
+        encodings.input_encoding(input_path),
+        encodings.output_encoding(output_path),
+        conversion_function,
+    )?;
+    fs::write(output_path, conversion.output)?;
+    Ok(conversion.diagnostics)
+}
+
+/// Determines the vocabulary of the units of Thing Models and Thing
+/// Descriptions converted from SDF.
+fn get_unit_vocabulary(convert_command: &clap::ArgMatches) -> Result<UnitVocabulary> {
+    convert_command
+        .value_of(UNITS_NAME)
+        .unwrap_or("symbol")
+        .parse::<UnitVocabulary>()
+}
+
+/// Translates the units of a Thing Model converted from SDF, which already use
+/// SenML unit symbols.
+fn translate_units(json_string: String, units: UnitVocabulary) -> Result<String> {
+    match units {
+        UnitVocabulary::Symbol => Ok(json_string),
+        _ => converter::translate_wot_units(json_string, units),
+    }
+}
+
+fn with_units(
+    conversion: Result<Conversion<String>>,
+    units: UnitVocabulary,
+) -> Result<Conversion<String>> {
+    let conversion = conversion?;
+    let output = translate_units(conversion.output, units)?;
+    Ok(Conversion::new(output, conversion.diagnostics))
+}
+
+/// Converts all SDF models within a directory into Thing Models, which are
+/// written to the output directory, mirroring the input directory structure.
+fn convert_sdf_directory(
+    input_path: &str,
+    output_path: &str,
+    context_paths: &[&str],
+    units: UnitVocabulary,
+    encodings: Encodings,
+) -> CommandResult<Vec<Diagnostic>> {
+    let mut model_set = SDFModelSet::from_paths(&[input_path])?;
+    for context_path in context_paths {
+        model_set.load_path(context_path)?;
+    }
+
+    let conversion = converter::convert_sdf_model_set_to_wot_tm(&model_set)?;
+
+    for (name, thing_model) in conversion.output {
+        let relative_path = match Path::new(&name).strip_prefix(input_path) {
+            Ok(relative_path) => relative_path.to_string_lossy().to_string(),
+            Err(_) => continue,
+        };
+        // The Thing Models keep the encoding of their SDF models by default.
+        let output_file_name = match split_sdf_extension(&relative_path) {
+            Some((stem, extension)) => format!(
+                "{}.tm.{}",
+                stem,
+                encodings
+                    .output
+                    .map_or_else(|| extension.trim_start_matches(".sdf."), |x| x.extension())
+            ),
+            None => relative_path,
+        };
+        let output_file_path = Path::new(output_path).join(output_file_name);
+
+        if let Some(parent_directory) = output_file_path.parent() {
+            fs::create_dir_all(parent_directory)?;
+        }
+
+        encodings.write(
+            &output_file_path.to_string_lossy(),
+            translate_units(thing_model, units)?,
+        )?;
+    }
+
+    Ok(conversion.diagnostics)
+}
+
+/// Converts an SDF model into a set of composed Thing Models, which are written to
+/// the output directory.
+fn convert_sdf_to_composed_tms(
+    input_path: &str,
+    output_path: &str,
+    units: UnitVocabulary,
+    encodings: Encodings,
+) -> CommandResult<Vec<Diagnostic>> {
+    let input_file_name = Path::new(input_path)
+        .file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let root_name =
+        split_sdf_extension(&input_file_name).map_or(input_file_name.as_str(), |(stem, _)| stem);
+
+    let input_string = encodings.read(input_path)?;
+    fs::create_dir_all(output_path)?;
+
+    let conversion = converter::convert_sdf_to_composed_wot_tms(input_string, root_name)?;
+
+    for (name, thing_model) in conversion.output {
+        let output_file_path = Path::new(output_path).join(name);
+        encodings.write(
+            &output_file_path.to_string_lossy(),
+            translate_units(thing_model, units)?,
+        )?;
+    }
+
+    Ok(conversion.diagnostics)
+}
+
+/// Converts an SDF model (or a directory of SDF models) into Thing Models using
+/// the options given on the command line.
+fn convert_sdf_to_tm(
+    input_path: &str,
+    output_path: &str,
+    convert_command: &clap::ArgMatches,
+) -> CommandResult<Vec<Diagnostic>> {
+    let encodings = Encodings::from_arguments(convert_command)?;
+    let context_paths: Vec<&str> = convert_command
+        .values_of(SDF_CONTEXT_NAME)
+        .map(|x| x.collect())
+        .unwrap_or_default();
+    let mapping_path = convert_command.value_of(SDF_MAPPING_INPUT_NAME);
+    let lossless = convert_command.is_present(LOSSLESS_FLAG_NAME);
+    let units = get_unit_vocabulary(convert_command)?;
+
+    if lossless && units != UnitVocabulary::Symbol {
+        return Err("Units cannot be translated in lossless conversions!".into());
+    }
+
+    if convert_command.is_present(COMPOSED_FLAG_NAME) {
+        if Path::new(input_path).is_dir() {
+            return Err("Composed Thing Models can only be created from single SDF files!".into());
+        }
+        convert_sdf_to_composed_tms(input_path, output_path, units, encodings)
+    } else if Path::new(input_path).is_dir() {
+        if mapping_path.is_some() {
+            return Err("SDF mapping files can only be applied to single SDF files!".into());
+        }
+        if lossless {
+            return Err("Lossless conversions are only supported for single SDF files!".into());
+        }
+        convert_sdf_directory(input_path, output_path, &context_paths, units, encodings)
+    } else if lossless {
+        convert(
+            input_path,
+            output_path,
+            encodings,
+            &converter::convert_sdf_to_wot_tm_lossless,
+        )
+    } else if let Some(mapping_path) = mapping_path {
+        let mapping_json_string = get_json(mapping_path)?;
+        convert(input_path, output_path, encodings, &|json_string| {
+            let conversion = converter::convert_sdf_to_wot_tm_with_mapping(
+                json_string,
+                mapping_json_string.clone(),
+            );
+            with_units(conversion, units)
+        })
+    } else if context_paths.is_empty() {
+        convert(input_path, output_path, encodings, &|json_string| {
+            with_units(converter::convert_sdf_to_wot_tm(json_string), units)
+        })
+    } else {
+        let model_set = SDFModelSet::from_paths(&context_paths)?;
+        convert(input_path, output_path, encodings, &|json_string| {
+            let conversion =
+                converter::convert_sdf_to_wot_tm_with_model_set(json_string, &model_set);
+            with_units(conversion, units)
+        })
+    }
+}
+
+/// Converts an SDF model into a Thing Description using the binding information
+/// given on the command line.
+fn convert_sdf_to_td(
+    input_path: &str,
+    output_path: &str,
+    convert_command: &clap::ArgMatches,
+) -> CommandResult<Vec<Diagnostic>> {
+    let encodings = Encodings::from_arguments(convert_command)?;
+    let options = BindingOptions {
+        base: convert_command
+            .value_of(BASE_URI_NAME)
+            .ok_or("A base URI is required for creating a Thing Description!")?
+            .to_string(),
+        protocol: convert_command
+            .value_of(PROTOCOL_NAME)
+            .unwrap_or("http")
+            .parse::<Protocol>()?,
+        security_scheme: convert_command
+            .value_of(SECURITY_SCHEME_NAME)
+            .unwrap_or("nosec")
+            .to_string(),
+        units: get_unit_vocabulary(convert_command)?,
+    };
+
+    match convert_command.value_of(SDF_MAPPING_INPUT_NAME) {
+        Some(mapping_path) => {
+            let mapping_json_string = get_json(mapping_path)?;
+            convert(input_path, output_path, encodings, &|json_string| {
+                converter::convert_sdf_to_wot_td_with_mapping(
+                    json_string,
+                    mapping_json_string.clone(),
+                    &options,
+                )
+            })
+        }
+        None => convert(input_path, output_path, encodings, &|json_string| {
+            converter::convert_sdf_to_wot_td(json_string, &options)
+        }),
+    }
+}
+
+/// Derives the path of an SDF mapping file from the path of the SDF model it
+/// belongs to.
+fn derive_mapping_path(sdf_path: &str) -> String {
+    match split_sdf_extension(sdf_path) {
+        Some((stem, extension)) => format!(
+            "{}.sdf-mapping.{}",
+            stem,
+            extension.trim_start_matches(".sdf.")
+        ),
+        None => format!(
+            "{}.sdf-mapping.json",
+            sdf_path.strip_suffix(".json").unwrap_or(sdf_path)
+        ),
+    }
+}
+
+/// Converts a Thing Description into an SDF model and an SDF mapping file
+/// containing its forms and security information.
+fn convert_td_to_sdf(
+    input_path: &str,
+    output_path: &str,
+    mapping_path: Option<&str>,
+    encodings: Encodings,
+) -> CommandResult<Vec<Diagnostic>> {
+    let input_string = encodings.read(input_path)?;
+    let conversion = converter::convert_wot_td_to_sdf(input_string)?;
+    let (sdf_model, sdf_mapping) = conversion.output;
+    let mapping_path = mapping_path
+        .map(|x| x.to_string())
+        .unwrap_or_else(|| derive_mapping_path(output_path));
+
+    encodings.write(output_path, sdf_model)?;
+    encodings.write(&mapping_path, sdf_mapping)?;
+    Ok(conversion.diagnostics)
+}
+
+/// Resolves a (relative) reference like a link `href` against the path or URL
+/// of the document containing it.
+fn resolve_relative_path(base_path: &str, href: &str) -> Result<String> {
+    match determine_path_type(base_path) {
+        InputPathType::ValidUrl => Url::parse(base_path)
+            .and_then(|base_url| base_url.join(href))
+            .map(|url| url.to_string())
+            .map_err(|error| {
+                ConversionError::Reference(format!(
+                    "Cannot resolve \"{}\" against \"{}\": {}",
+                    href, base_path, error
+                ))
+            }),
+        _ => {
+            let base_directory = Path::new(base_path)
+                .parent()
+                .unwrap_or_else(|| Path::new(""));
+            Ok(base_directory.join(href).to_string_lossy().to_string())
+        }
+    }
+}
+
+/// Converts a Thing Model into an SDF model, loading the Thing Models it
+/// extends or references relative to the location of the input file.
+fn convert_tm_to_sdf(
+    input_path: &str,
+    output_path: &str,
+    lossless: bool,
+    encodings: Encodings,
+) -> CommandResult<Vec<Diagnostic>> {
+    let loader = |href: &str| get_json(&resolve_relative_path(input_path, href)?);
+    convert(input_path, output_path, encodings, &|json_string| {
+        if lossless {
+            converter::convert_wot_tm_to_sdf_lossless_with_loader(json_string, &loader)
+        } else {
+            converter::convert_wot_tm_to_sdf_with_loader(json_string, &loader)
+        }
+    })
+}
+
+/// Converts a composed Thing Model into an SDF model, loading the submodels it
+/// links to relative to the location of the input file.
+fn convert_composed_tm_to_sdf(
+    input_path: &str,
+    output_path: &str,
+    encodings: Encodings,
+) -> CommandResult<Vec<Diagnostic>> {
+    let loader = |href: &str| get_json(&resolve_relative_path(input_path, href)?);
+    convert(input_path, output_path, encodings, &|json_string| {
+        converter::convert_composed_wot_tm_to_sdf(json_string, &loader)
+    })
 }
 
-fn match_print_arguments(print_command: &&clap::ArgMatches) -> Result<()> {
+fn match_print_arguments(print_command: &&clap::ArgMatches) -> CommandResult<()> {
+    let encodings = Encodings::from_arguments(print_command)?;
     if let Some(input_path) = print_command.value_of(SDF_INPUT_NAME) {
-        print_model_from_file(input_path, &converter::print_sdf_definition)
+        print_model_from_file(input_path, encodings, &converter::format_sdf_definition)
     } else if let Some(input_path) = print_command.value_of(TD_INPUT_NAME) {
-        print_model_from_file(input_path, &converter::print_wot_td_definition)
+        print_model_from_file(input_path, encodings, &converter::format_wot_td_definition)
     } else if let Some(input_path) = print_command.value_of(TM_INPUT_NAME) {
-        print_model_from_file(input_path, &converter::print_wot_tm_definition)
+        print_model_from_file(input_path, encodings, &converter::format_wot_tm_definition)
     } else {
         Err("No legal argument for print subcommand found!".into())
     }
 }
 
-fn match_convert_arguments(convert_command: &&clap::ArgMatches) -> Result<()> {
+/// Writes the diagnostics of a conversion as JSON to the given file or, if no
+/// path is given, prints them in the terminal.
+fn write_report(report_path: Option<&str>, diagnostics: &[Diagnostic]) -> CommandResult<()> {
+    let report = serde_json::to_string_pretty(diagnostics)?;
+    match report_path {
+        Some(report_path) => write_to_file(report_path, report),
+        None => {
+            println!("{}", report);
+            Ok(())
+        }
+    }
+}
+
+fn match_convert_arguments(convert_command: &&clap::ArgMatches) -> CommandResult<()> {
+    let diagnostics = convert_from_arguments(convert_command)?;
+
+    if convert_command.is_present(REPORT_NAME) {
+        write_report(convert_command.value_of(REPORT_NAME), &diagnostics)
+    } else {
+        Ok(())
+    }
+}
+
+fn convert_from_arguments(convert_command: &clap::ArgMatches) -> CommandResult<Vec<Diagnostic>> {
+    let encodings = Encodings::from_arguments(convert_command)?;
     let output_error_message = "No legal output path argument given!";
     if let Some(input_path) = convert_command.value_of(SDF_INPUT_NAME) {
         if let Some(output_path) = convert_command.value_of(TM_OUTPUT_NAME) {
-            convert(input_path, output_path, &converter::convert_sdf_to_wot_tm)
+            convert_sdf_to_tm(input_path, output_path, convert_command)
+        } else if let Some(output_path) = convert_command.value_of(TD_OUTPUT_NAME) {
+            convert_sdf_to_td(input_path, output_path, convert_command)
         } else if let Some(output_path) = convert_command.value_of(SDF_OUTPUT_NAME) {
-            write_to_another_file(input_path, output_path)
+            write_to_another_file(input_path, output_path, encodings)
         } else {
             Err(output_error_message.into())
         }
     } else if let Some(input_path) = convert_command.value_of(TM_INPUT_NAME) {
         if let Some(output_path) = convert_command.value_of(SDF_OUTPUT_NAME) {
-            convert(input_path, output_path, &converter::convert_wot_tm_to_sdf)
+            if convert_command.is_present(COMPOSED_FLAG_NAME) {
+                convert_composed_tm_to_sdf(input_path, output_path, encodings)
+            } else {
+                let lossless = convert_command.is_present(LOSSLESS_FLAG_NAME);
+                convert_tm_to_sdf(input_path, output_path, lossless, encodings)
+            }
         } else if let Some(output_path) = convert_command.value_of(TM_OUTPUT_NAME) {
-            write_to_another_file(input_path, output_path)
+            write_to_another_file(input_path, output_path, encodings)
+        } else {
+            Err(output_error_message.into())
+        }
+    } else if let Some(input_path) = convert_command.value_of(TD_INPUT_NAME) {
+        if let Some(output_path) = convert_command.value_of(SDF_OUTPUT_NAME) {
+            let mapping_path = convert_command.value_of(SDF_MAPPING_OUTPUT_NAME);
+            convert_td_to_sdf(input_path, output_path, mapping_path, encodings)
         } else {
             Err(output_error_message.into())
         }
@@ -116,20 +573,89 @@ fn match_convert_arguments(convert_command: &&clap::ArgMatches) -> Result<()> {
     }
 }
 
+fn match_compare_arguments(compare_command: &&clap::ArgMatches) -> CommandResult<()> {
+    let sdf_paths: Vec<&str> = compare_command
+        .values_of(SDF_INPUT_NAME)
+        .map(|x| x.collect())
+        .unwrap_or_default();
+    let tm_paths: Vec<&str> = compare_command
+        .values_of(TM_INPUT_NAME)
+        .map(|x| x.collect())
+        .unwrap_or_default();
+
+    let differences = match (sdf_paths.as_slice(), tm_paths.as_slice()) {
+        ([first, second], []) => converter::compare_sdf_models(get_json(first)?, get_json(second)?),
+        ([], [first, second]) => converter::compare_wot_tms(get_json(first)?, get_json(second)?),
+        ([sdf_path], [tm_path]) => {
+            converter::compare_sdf_with_wot_tm(get_json(sdf_path)?, get_json(tm_path)?)
+        }
+        _ => return Err("Exactly two documents have to be given for a comparison!".into()),
+    }?;
+
+    if differences.is_empty() {
+        println!("The documents are equivalent.");
+        Ok(())
+    } else {
+        println!("{}", serde_json::to_string_pretty(&differences)?);
+        Err(format!(
+            "Found {} differences between the documents!",
+            differences.len()
+        )
+        .into())
+    }
+}
+
+fn match_validate_arguments(validate_command: &&clap::ArgMatches) -> CommandResult<()> {
+    let violations = if let Some(input_path) = validate_command.value_of(SDF_INPUT_NAME) {
+        converter::validate_sdf_model(get_json(input_path)?)?
+    } else if let Some(input_path) = validate_command.value_of(TD_INPUT_NAME) {
+        converter::validate_wot_td(get_json(input_path)?)?
+    } else if let Some(input_path) = validate_command.value_of(TM_INPUT_NAME) {
+        let loader = |href: &str| get_json(&resolve_relative_path(input_path, href)?);
+        converter::validate_wot_tm_with_loader(get_json(input_path)?, &loader)?
+    } else {
+        return Err("No legal argument for validate subcommand found!".into());
+    };
+
+    println!("{}", serde_json::to_string_pretty(&violations)?);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Found {} violations in the model!", violations.len()).into())
+    }
+}
+
 fn match_arguments(
     app: clap::ArgMatches,
     match_print_command_function: MatchSubcommandFunction,
     match_convert_command_function: MatchSubcommandFunction,
-) -> Result<()> {
+    match_compare_command_function: MatchSubcommandFunction,
+    match_validate_command_function: MatchSubcommandFunction,
+) -> CommandResult<()> {
     if let Some(ref matches) = app.subcommand_matches("print") {
         match_print_command_function(matches)
     } else if let Some(ref matches) = app.subcommand_matches("convert") {
         match_convert_command_function(matches)
+    } else if let Some(ref matches) = app.subcommand_matches("compare") {
+        match_compare_command_function(matches)
+    } else if let Some(ref matches) = app.subcommand_matches("validate") {
+        match_validate_command_function(matches)
     } else {
         Err("No known subcommand found!".into())
     }
 }
 
+fn create_input_format_arg() -> Arg<'static> {
+    Arg::new(INPUT_FORMAT_NAME)
+        .long("input-format")
+        .help(
+            "The encoding of the input file. Detected from its extension or \
+            content by default.",
+        )
+        .takes_value(true)
+        .possible_values(["json", "cbor", "yaml"])
+}
+
 fn create_app() -> clap::App<'static> {
     app_from_crate!()
         .subcommand(
@@ -153,6 +679,17 @@ fn create_app() -> clap::App<'static> {
                         .help("Reads in a WoT Thing Description file.")
                         .takes_value(true),
                 )
+                .arg(create_input_format_arg())
+                .arg(
+                    Arg::new(OUTPUT_FORMAT_NAME)
+                        .long("output-format")
+                        .help(
+                            "The encoding in which the model is printed. CBOR is written to \
+                            the standard output as raw bytes. Defaults to JSON.",
+                        )
+                        .takes_value(true)
+                        .possible_values(["json", "cbor", "yaml"]),
+                )
                 .group(
                     ArgGroup::new("input")
                         .args(&[SDF_INPUT_NAME, TM_INPUT_NAME, TD_INPUT_NAME])
@@ -174,6 +711,57 @@ fn create_app() -> clap::App<'static> {
                         .help("Reads in a WoT Thing Model file.")
                         .takes_value(true),
                 )
+                .arg(
+                    Arg::new(TD_INPUT_NAME)
+                        .long("from-td")
+                        .help("Reads in a WoT Thing Description file.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new(SDF_CONTEXT_NAME)
+                        .long("sdf-context")
+                        .help("Reads in SDF files or directories for resolving namespaced sdfRefs.")
+                        .takes_value(true)
+                        .multiple_occurrences(true)
+                        .requires(SDF_INPUT_NAME),
+                )
+                .arg(
+                    Arg::new(SDF_MAPPING_INPUT_NAME)
+                        .long("sdf-mapping")
+                        .help(
+                            "Reads in an SDF mapping file providing protocol binding information \
+                            for the SDF model.",
+                        )
+                        .takes_value(true)
+                        .requires(SDF_INPUT_NAME)
+                        .conflicts_with(SDF_CONTEXT_NAME),
+                )
+                .arg(
+                    Arg::new(COMPOSED_FLAG_NAME)
+                        .long("composed")
+                        .help(
+                            "Converts every sdfObject and sdfThing into a Thing Model of its own \
+                            and writes them to the directory given by --to-tm, or follows the \
+                            tm:submodel links of a Thing Model when converting to SDF.",
+                        )
+                        .conflicts_with(SDF_CONTEXT_NAME)
+                        .conflicts_with(SDF_MAPPING_INPUT_NAME),
+                )
+                .arg(
+                    Arg::new(LOSSLESS_FLAG_NAME)
+                        .long("lossless")
+                        .help(
+                            "Preserves all information that cannot be represented in the output \
+                            format in sdf:-prefixed terms of a Thing Model or wot quality blocks \
+                            of an SDF model, so that converting the result back restores the \
+                            input.",
+                        )
+                        .conflicts_with(SDF_CONTEXT_NAME)
+                        .conflicts_with(SDF_MAPPING_INPUT_NAME)
+                        .conflicts_with(COMPOSED_FLAG_NAME)
+                        .conflicts_with(TD_INPUT_NAME)
+                        .conflicts_with(TD_OUTPUT_NAME),
+                )
                 .arg(
                     Arg::new(TM_OUTPUT_NAME)
                         .long("to-tm")
@@ -186,23 +774,160 @@ fn create_app() -> clap::App<'static> {
                         .help("Converts to a WoT Thing Model and writes it to a file.")
                         .takes_value(true),
                 )
+                .arg(
+                    Arg::new(SDF_MAPPING_OUTPUT_NAME)
+                        .long("to-sdf-mapping")
+                        .help(
+                            "Writes the forms and security information of a Thing Description to \
+                            an SDF mapping file. Defaults to the --to-sdf path with the extension \
+                            .sdf-mapping.json.",
+                        )
+                        .takes_value(true)
+                        .requires(TD_INPUT_NAME)
+                        .requires(SDF_OUTPUT_NAME),
+                )
+                .arg(
+                    Arg::new(TD_OUTPUT_NAME)
+                        .long("to-td")
+                        .help("Converts an SDF model to a WoT Thing Description and writes it to a file.")
+                        .takes_value(true)
+                        .requires(SDF_INPUT_NAME)
+                        .requires(BASE_URI_NAME),
+                )
+                .arg(
+                    Arg::new(BASE_URI_NAME)
+                        .long("base")
+                        .help("The base URI of the generated Thing Description.")
+                        .takes_value(true)
+                        .requires(TD_OUTPUT_NAME),
+                )
+                .arg(
+                    Arg::new(PROTOCOL_NAME)
+                        .long("protocol")
+                        .help("The protocol used in the forms of the generated Thing Description.")
+                        .takes_value(true)
+                        .possible_values(["http", "coap"])
+                        .default_value("http")
+                        .requires(TD_OUTPUT_NAME),
+                )
+                .arg(
+                    Arg::new(SECURITY_SCHEME_NAME)
+                        .long("security")
+                        .help("The security scheme of the generated Thing Description.")
+                        .takes_value(true)
+                        .possible_values(["nosec", "basic", "digest", "bearer", "psk", "apikey"])
+                        .default_value("nosec")
+                        .requires(TD_OUTPUT_NAME),
+                )
+                .arg(
+                    Arg::new(REPORT_NAME)
+                        .long("report")
+                        .help(
+                            "Reports every member of the input that has been dropped, \
+                            approximated, or defaulted during the conversion. The report is \
+                            written as JSON to the given file or printed if no file is given.",
+                        )
+                        .takes_value(true)
+                        .min_values(0),
+                )
+                .arg(
+                    Arg::new(UNITS_NAME)
+                        .long("units")
+                        .help(
+                            "The vocabulary of the units of Thing Models and Thing Descriptions \
+                            converted from SDF. Units are given as SenML unit symbols by default \
+                            or as QUDT or OM IRIs.",
+                        )
+                        .takes_value(true)
+                        .possible_values(["symbol", "qudt", "om"])
+                        .default_value("symbol"),
+                )
+                .arg(create_input_format_arg())
+                .arg(
+                    Arg::new(OUTPUT_FORMAT_NAME)
+                        .long("output-format")
+                        .help(
+                            "The encoding of the output files. Defaults to CBOR for files with \
+                            the extension .cbor, to YAML for files with the extension .yaml \
+                            or .yml, and to JSON otherwise.",
+                        )
+                        .takes_value(true)
+                        .possible_values(["json", "cbor", "yaml"]),
+                )
                 .group(
                     ArgGroup::new("from")
-                        .args(&[SDF_INPUT_NAME, TM_INPUT_NAME])
+                        .args(&[SDF_INPUT_NAME, TM_INPUT_NAME, TD_INPUT_NAME])
                         .required(true),
                 )
                 .group(
                     ArgGroup::new("to")
-                        .args(&[TM_OUTPUT_NAME, SDF_OUTPUT_NAME])
+                        .args(&[TM_OUTPUT_NAME, SDF_OUTPUT_NAME, TD_OUTPUT_NAME])
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            App::new("compare")
+                .about(
+                    "Reads in two SDF or WoT files and reports the differences between the \
+                    models they describe.",
+                )
+                .arg(
+                    Arg::new(SDF_INPUT_NAME)
+                        .long("sdf")
+                        .help("Reads in an SDF file. Can be given twice.")
+                        .takes_value(true)
+                        .multiple_occurrences(true),
+                )
+                .arg(
+                    Arg::new(TM_INPUT_NAME)
+                        .long("tm")
+                        .help("Reads in a WoT Thing Model file. Can be given twice.")
+                        .takes_value(true)
+                        .multiple_occurrences(true),
+                ),
+        )
+        .subcommand(
+            App::new("validate")
+                .about(
+                    "Reads in an SDF or WoT file and reports all violations of the rules of the \
+                    respective specification as JSON.",
+                )
+                .arg(
+                    Arg::new(SDF_INPUT_NAME)
+                        .long("sdf")
+                        .help("Reads in an SDF file.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new(TM_INPUT_NAME)
+                        .long("tm")
+                        .help("Reads in a WoT Thing Model file.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new(TD_INPUT_NAME)
+                        .long("td")
+                        .help("Reads in a WoT Thing Description file.")
+                        .takes_value(true),
+                )
+                .group(
+                    ArgGroup::new("input")
+                        .args(&[SDF_INPUT_NAME, TM_INPUT_NAME, TD_INPUT_NAME])
                         .required(true),
                 ),
         )
 }
 
-fn main() -> Result<()> {
+fn main() -> CommandResult<()> {
     let app = create_app().get_matches();
 
-    match_arguments(app, &match_print_arguments, &match_convert_arguments)
+    match_arguments(
+        app,
+        &match_print_arguments,
+        &match_convert_arguments,
+        &match_compare_arguments,
+        &match_validate_arguments,
+    )
 }
 
 #[cfg(test)]
@@ -213,42 +938,53 @@ mod tests {
         let _ = fs::create_dir_all("test_output");
     }
 
-    fn successful_print_function(_input: String) -> Result<()> {
-        Ok(())
+    fn successful_format_function(_input: String) -> Result<String> {
+        Ok(String::new())
     }
 
-    fn failing_print_function(_input: String) -> Result<()> {
-        Err("This is an error".into())
+    fn failing_format_function(_input: String) -> Result<String> {
+        Err(ConversionError::Invalid("This is an error".to_string()))
     }
 
-    fn successful_converter_function(_input: String) -> Result<String> {
-        Ok(String::new())
+    fn successful_converter_function(_input: String) -> Result<Conversion<String>> {
+        Ok(Conversion::new(String::new(), Vec::new()))
     }
 
-    fn failing_converter_function(_input: String) -> Result<String> {
-        Err("This is an error".into())
+    fn failing_converter_function(_input: String) -> Result<Conversion<String>> {
+        Err(ConversionError::Invalid("This is an error".to_string()))
     }
 
     #[test]
     fn write_to_another_file_test() {
         create_test_dir();
-        assert!(
-            write_to_another_file("examples/sdf/example.sdf.json", "test_output/barfoo.json")
-                .is_ok()
-        );
+        assert!(write_to_another_file(
+            "examples/sdf/example.sdf.json",
+            "test_output/barfoo.json",
+            Encodings::default()
+        )
+        .is_ok());
     }
 
     #[test]
     fn print_model_from_path_test() {
-        assert!(
-            print_model_from_file("examples/sdf/example.sdf.json", &successful_print_function)
-                .is_ok()
-        );
-        assert!(
-            print_model_from_file("examples/sdf/example.sdf.json", &failing_print_function)
-                .is_err()
-        );
-        assert!(print_model_from_file("foobar.json", &successful_print_function).is_err());
+        assert!(print_model_from_file(
+            "examples/sdf/example.sdf.json",
+            Encodings::default(),
+            &successful_format_function
+        )
+        .is_ok());
+        assert!(print_model_from_file(
+            "examples/sdf/example.sdf.json",
+            Encodings::default(),
+            &failing_format_function
+        )
+        .is_err());
+        assert!(print_model_from_file(
+            "foobar.json",
+            Encodings::default(),
+            &successful_format_function
+        )
+        .is_err());
     }
 
     #[test]
@@ -257,55 +993,180 @@ mod tests {
         let working_input_path = "examples/sdf/example.sdf.json";
         let failing_input_path = "foobar.json";
         let output_path = "test_output/foobar.tm.json";
+        let encodings = Encodings::default();
         assert!(convert(
             working_input_path,
             output_path,
+            encodings,
             &successful_converter_function
         )
         .is_ok());
-        assert!(convert(working_input_path, output_path, &failing_converter_function).is_err());
+        assert!(convert(
+            working_input_path,
+            output_path,
+            encodings,
+            &failing_converter_function
+        )
+        .is_err());
         assert!(convert(
             failing_input_path,
             output_path,
+            encodings,
             &successful_converter_function
         )
         .is_err());
     }
 
     #[test]
-    fn determine_path_type_test() {
-        assert_eq!(
-            InputPathType::ValidUrl,
-            determine_path_type("https://example.org")
-        );
-        assert_eq!(
-            InputPathType::InvalidUrl,
-            determine_path_type("coap://example.org")
-        );
-        assert_eq!(InputPathType::File, determine_path_type("file://foobar"));
-        assert_eq!(InputPathType::File, determine_path_type("foobar"));
-        assert_eq!(InputPathType::File, determine_path_type("./foobar"));
-        assert_eq!(InputPathType::File, determine_path_type("~/foobar"));
-        if cfg!(windows) {
-            assert_eq!(InputPathType::File, determine_path_type("C:\\foobar"));
-        }
-    }
-
-    fn successful_match_command_function(_matches: &&clap::ArgMatches) -> Result<()> {
-        Ok(())
-    }
-
-    fn failing_match_command_function(_matches: &&clap::ArgMatches) -> Result<()> {
-        Err("This is an error".into())
-    }
-
-    #[test]
-    fn match_print_arguments_test() {
-        // TODO: This test should be revisited
+    fn match_convert_arguments_cbor_test() {
+        create_test_dir();
         let app = create_app().get_matches_from(vec![
             "",
-            "print",
-            "--sdf",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--to-tm",
+            "test_output/example.tm.cbor",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        let cbor = fs::read("test_output/example.tm.cbor").unwrap();
+        assert_eq!(Encoding::detect(&cbor), Encoding::Cbor);
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-tm",
+            "test_output/example.tm.cbor",
+            "--input-format",
+            "cbor",
+            "--to-sdf",
+            "test_output/example-cbor.sdf.json",
+            "--output-format",
+            "json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(get_json("test_output/example-cbor.sdf.json").is_ok());
+
+        let app =
+            create_app().get_matches_from(vec!["", "print", "--tm", "test_output/example.tm.cbor"]);
+        let matches = app.subcommand_matches("print").unwrap();
+        assert!(match_print_arguments(&matches).is_ok());
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "print",
+            "--tm",
+            "examples/wot/example.tm.json",
+            "--output-format",
+            "cbor",
+        ]);
+        let matches = app.subcommand_matches("print").unwrap();
+        assert!(match_print_arguments(&matches).is_ok());
+    }
+
+    #[test]
+    fn match_convert_arguments_yaml_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.yaml",
+            "--to-tm",
+            "test_output/example.tm.yaml",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        let yaml = fs::read_to_string("test_output/example.tm.yaml").unwrap();
+        assert!(yaml.starts_with("'@context':"));
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-tm",
+            "test_output/example.tm.yaml",
+            "--to-sdf",
+            "test_output/example-yaml.sdf.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        let sdf_model: serde_json::Value =
+            serde_json::from_str(&get_json("test_output/example-yaml.sdf.json").unwrap()).unwrap();
+        let expected_sdf_model: serde_json::Value = serde_json::from_str(
+            &converter::convert_wot_tm_to_sdf(
+                converter::convert_sdf_to_wot_tm(
+                    get_json("examples/sdf/example.sdf.json").unwrap(),
+                )
+                .unwrap()
+                .output,
+            )
+            .unwrap()
+            .output,
+        )
+        .unwrap();
+        assert_eq!(sdf_model, expected_sdf_model);
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "print",
+            "--sdf",
+            "examples/sdf/example.sdf.yaml",
+            "--input-format",
+            "yaml",
+        ]);
+        let matches = app.subcommand_matches("print").unwrap();
+        assert!(match_print_arguments(&matches).is_ok());
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "print",
+            "--sdf",
+            "examples/sdf/example.sdf.json",
+            "--output-format",
+            "yaml",
+        ]);
+        let matches = app.subcommand_matches("print").unwrap();
+        assert!(match_print_arguments(&matches).is_ok());
+    }
+
+    #[test]
+    fn determine_path_type_test() {
+        assert_eq!(
+            InputPathType::ValidUrl,
+            determine_path_type("https://example.org")
+        );
+        assert_eq!(
+            InputPathType::InvalidUrl,
+            determine_path_type("coap://example.org")
+        );
+        assert_eq!(InputPathType::File, determine_path_type("file://foobar"));
+        assert_eq!(InputPathType::File, determine_path_type("foobar"));
+        assert_eq!(InputPathType::File, determine_path_type("./foobar"));
+        assert_eq!(InputPathType::File, determine_path_type("~/foobar"));
+        if cfg!(windows) {
+            assert_eq!(InputPathType::File, determine_path_type("C:\\foobar"));
+        }
+    }
+
+    fn successful_match_command_function(_matches: &&clap::ArgMatches) -> CommandResult<()> {
+        Ok(())
+    }
+
+    fn failing_match_command_function(_matches: &&clap::ArgMatches) -> CommandResult<()> {
+        Err("This is an error".into())
+    }
+
+    #[test]
+    fn match_print_arguments_test() {
+        // TODO: This test should be revisited
+        let app = create_app().get_matches_from(vec![
+            "",
+            "print",
+            "--sdf",
             "examples/sdf/example.sdf.json",
         ]);
         let matches = app.subcommand_matches("print").unwrap();
@@ -381,15 +1242,381 @@ mod tests {
         assert!(match_convert_arguments(&matches).is_ok());
     }
 
+    #[test]
+    fn match_convert_arguments_sdf_context_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--sdf-context",
+            "examples/sdf",
+            "--to-tm",
+            "test_output/sdf-context.tm.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        fs::create_dir_all("test_output/sdf-models/level").unwrap();
+        for (source, target) in [
+            ("examples/sdf/example.sdf.json", "example.sdf.json"),
+            ("examples/sdf/example.sdf.yaml", "example.sdf.yaml"),
+            (
+                "examples/sdf/sdfobject-level-refs.sdf.json",
+                "level/sdfobject-level.sdf.json",
+            ),
+        ] {
+            fs::copy(source, Path::new("test_output/sdf-models").join(target)).unwrap();
+        }
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "test_output/sdf-models",
+            "--to-tm",
+            "test_output/sdf-directory",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(Path::new("test_output/sdf-directory/example.tm.json").exists());
+        assert!(Path::new("test_output/sdf-directory/level/sdfobject-level.tm.json").exists());
+        assert!(Path::new("test_output/sdf-directory/example.tm.yaml").exists());
+    }
+
+    #[test]
+    fn match_convert_arguments_composed_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--composed",
+            "--to-tm",
+            "test_output/composed",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(Path::new("test_output/composed/example.tm.json").exists());
+        assert!(Path::new("test_output/composed/example_Switch.tm.json").exists());
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf",
+            "--composed",
+            "--to-tm",
+            "test_output/composed",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_err());
+    }
+
+    #[test]
+    fn match_convert_arguments_composed_tm_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-tm",
+            "examples/wot/composed/lamp.tm.json",
+            "--composed",
+            "--to-sdf",
+            "test_output/composed.sdf.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+    }
+
+    #[test]
+    fn match_convert_arguments_extends_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-tm",
+            "examples/wot/extends/dimmer.tm.json",
+            "--to-sdf",
+            "test_output/dimmer.sdf.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(Path::new("test_output/dimmer.sdf.json").exists());
+    }
+
+    #[test]
+    fn match_convert_arguments_td_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--to-td",
+            "test_output/example.td.json",
+            "--base",
+            "coap://example.org/",
+            "--protocol",
+            "coap",
+            "--security",
+            "psk",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(Path::new("test_output/example.td.json").exists());
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--to-td",
+            "test_output/example.td.json",
+            "--base",
+            "http://example.org/",
+            "--protocol",
+            "coap",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_err());
+    }
+
+    #[test]
+    fn match_convert_arguments_from_td_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-td",
+            "examples/wot/example.td.json",
+            "--to-sdf",
+            "test_output/from_td.sdf.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(Path::new("test_output/from_td.sdf.json").exists());
+        assert!(Path::new("test_output/from_td.sdf-mapping.json").exists());
+    }
+
+    #[test]
+    fn match_convert_arguments_lossless_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--to-tm",
+            "test_output/lossless.tm.json",
+            "--lossless",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-tm",
+            "test_output/lossless.tm.json",
+            "--to-sdf",
+            "test_output/lossless.sdf.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        let sdf_model: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string("test_output/lossless.sdf.json").unwrap())
+                .unwrap();
+        assert_eq!(sdf_model["defaultNamespace"], "cap");
+
+        let result = create_app().try_get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--to-tm",
+            "test_output/",
+            "--composed",
+            "--lossless",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn match_convert_arguments_units_test() {
+        create_test_dir();
+        fs::write(
+            "test_output/units.sdf.json",
+            r#"{
+                "info": {"title": "Sensor", "version": "1", "copyright": "", "license": ""},
+                "sdfProperty": {"temperature": {"type": "number", "unit": "Cel"}}
+            }"#,
+        )
+        .unwrap();
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "test_output/units.sdf.json",
+            "--to-td",
+            "test_output/units.td.json",
+            "--base",
+            "https://example.org",
+            "--units",
+            "qudt",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        let thing_description: serde_json::Value =
+            serde_json::from_str(&get_json("test_output/units.td.json").unwrap()).unwrap();
+        assert_eq!(
+            thing_description["properties"]["temperature"]["unit"],
+            "qudt:DEG_C"
+        );
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-td",
+            "test_output/units.td.json",
+            "--to-sdf",
+            "test_output/units-td.sdf.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(get_json("test_output/units-td.sdf.json")
+            .unwrap()
+            .contains("\"unit\": \"Cel\""));
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "test_output/units.sdf.json",
+            "--to-tm",
+            "test_output/units.tm.json",
+            "--units",
+            "om",
+            "--lossless",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_err());
+    }
+
+    #[test]
+    fn match_convert_arguments_report_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--to-tm",
+            "test_output/report.tm.json",
+            "--report",
+            "test_output/report.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string("test_output/report.json").unwrap()).unwrap();
+        assert!(!report.as_array().unwrap().is_empty());
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-tm",
+            "examples/wot/example.tm.json",
+            "--to-sdf",
+            "test_output/report.sdf.json",
+            "--report",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+    }
+
+    #[test]
+    fn match_convert_arguments_sdf_mapping_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--sdf-mapping",
+            "examples/sdf/example.sdf-mapping.json",
+            "--to-td",
+            "test_output/mapped.td.json",
+            "--base",
+            "coap://example.org/",
+            "--protocol",
+            "coap",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(Path::new("test_output/mapped.td.json").exists());
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            "examples/sdf/example.sdf.json",
+            "--sdf-mapping",
+            "examples/sdf/example.sdf-mapping.json",
+            "--to-tm",
+            "test_output/mapped.tm.json",
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+        assert!(Path::new("test_output/mapped.tm.json").exists());
+    }
+
+    #[test]
+    fn derive_mapping_path_test() {
+        assert_eq!(
+            derive_mapping_path("foo/bar.sdf.json"),
+            "foo/bar.sdf-mapping.json"
+        );
+        assert_eq!(
+            derive_mapping_path("foo/bar.sdf.yaml"),
+            "foo/bar.sdf-mapping.yaml"
+        );
+        assert_eq!(derive_mapping_path("bar"), "bar.sdf-mapping.json");
+    }
+
+    #[test]
+    fn resolve_relative_path_test() {
+        assert_eq!(
+            resolve_relative_path("examples/wot/composed/lamp.tm.json", "./switch.tm.json")
+                .unwrap(),
+            "examples/wot/composed/./switch.tm.json"
+        );
+        assert_eq!(
+            resolve_relative_path("https://example.org/foo/lamp.tm.json", "./switch.tm.json")
+                .unwrap(),
+            "https://example.org/foo/switch.tm.json"
+        );
+    }
+
     #[test]
     fn match_arguments_test() {
         let app = app_from_crate!()
             .subcommand(App::new("print"))
             .subcommand(App::new("convert"))
+            .subcommand(App::new("compare"))
+            .subcommand(App::new("validate"))
             .get_matches_from(vec!["", "print"]);
         assert!(match_arguments(
             app,
             &successful_match_command_function,
+            &failing_match_command_function,
+            &failing_match_command_function,
             &failing_match_command_function
         )
         .is_ok());
@@ -397,10 +1624,44 @@ mod tests {
         let app = app_from_crate!()
             .subcommand(App::new("print"))
             .subcommand(App::new("convert"))
+            .subcommand(App::new("compare"))
+            .subcommand(App::new("validate"))
             .get_matches_from(vec!["", "convert"]);
         assert!(match_arguments(
             app,
             &failing_match_command_function,
+            &successful_match_command_function,
+            &failing_match_command_function,
+            &failing_match_command_function
+        )
+        .is_ok());
+
+        let app = app_from_crate!()
+            .subcommand(App::new("print"))
+            .subcommand(App::new("convert"))
+            .subcommand(App::new("compare"))
+            .subcommand(App::new("validate"))
+            .get_matches_from(vec!["", "compare"]);
+        assert!(match_arguments(
+            app,
+            &failing_match_command_function,
+            &failing_match_command_function,
+            &successful_match_command_function,
+            &failing_match_command_function
+        )
+        .is_ok());
+
+        let app = app_from_crate!()
+            .subcommand(App::new("print"))
+            .subcommand(App::new("convert"))
+            .subcommand(App::new("compare"))
+            .subcommand(App::new("validate"))
+            .get_matches_from(vec!["", "validate"]);
+        assert!(match_arguments(
+            app,
+            &failing_match_command_function,
+            &failing_match_command_function,
+            &failing_match_command_function,
             &successful_match_command_function
         )
         .is_ok());
@@ -409,12 +1670,16 @@ mod tests {
             .subcommand(App::new("foobar"))
             .subcommand(App::new("print"))
             .subcommand(App::new("convert"))
+            .subcommand(App::new("compare"))
+            .subcommand(App::new("validate"))
             .get_matches_from(vec!["", "foobar"]);
         assert_eq!(
             "No known subcommand found!".to_string(),
             match_arguments(
                 app,
                 &failing_match_command_function,
+                &failing_match_command_function,
+                &failing_match_command_function,
                 &failing_match_command_function
             )
             .unwrap_err()
@@ -422,6 +1687,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn match_validate_arguments_test() {
+        create_test_dir();
+        let app = create_app().get_matches_from(vec![
+            "",
+            "validate",
+            "--sdf",
+            "examples/sdf/example.sdf.json",
+        ]);
+        let matches = app.subcommand_matches("validate").unwrap();
+        assert!(match_validate_arguments(&matches).is_ok());
+
+        for arguments in [
+            vec!["", "validate", "--td", "examples/wot/example.td.json"],
+            vec![
+                "",
+                "validate",
+                "--tm",
+                "examples/wot/extends/dimmer.tm.json",
+            ],
+        ] {
+            let app = create_app().get_matches_from(arguments);
+            let matches = app.subcommand_matches("validate").unwrap();
+            assert!(match_validate_arguments(&matches).is_ok());
+        }
+
+        let invalid_model_path = "test_output/invalid.sdf.json";
+        let invalid_model =
+            r#"{"sdfData": {"foo": {"type": "integer", "minimum": 2, "maximum": 1}}}"#;
+        write_to_file(invalid_model_path, invalid_model.to_string()).unwrap();
+        let app = create_app().get_matches_from(vec!["", "validate", "--sdf", invalid_model_path]);
+        let matches = app.subcommand_matches("validate").unwrap();
+        assert_eq!(
+            match_validate_arguments(&matches).unwrap_err().to_string(),
+            "Found 1 violations in the model!"
+        );
+    }
+
+    #[test]
+    fn match_compare_arguments_test() {
+        create_test_dir();
+        let sdf_path = "examples/sdf/example.sdf.json";
+        let tm_path = "test_output/compare.tm.json";
+        let app = create_app().get_matches_from(vec![
+            "",
+            "convert",
+            "--from-sdf",
+            sdf_path,
+            "--to-tm",
+            tm_path,
+        ]);
+        let matches = app.subcommand_matches("convert").unwrap();
+        assert!(match_convert_arguments(&matches).is_ok());
+
+        for arguments in [
+            vec!["", "compare", "--sdf", sdf_path, "--sdf", sdf_path],
+            vec!["", "compare", "--tm", tm_path, "--tm", tm_path],
+            vec!["", "compare", "--sdf", sdf_path, "--tm", tm_path],
+        ] {
+            let app = create_app().get_matches_from(arguments);
+            let matches = app.subcommand_matches("compare").unwrap();
+            assert!(match_compare_arguments(&matches).is_ok());
+        }
+
+        let app = create_app().get_matches_from(vec![
+            "",
+            "compare",
+            "--tm",
+            tm_path,
+            "--tm",
+            "examples/wot/example.tm.json",
+        ]);
+        let matches = app.subcommand_matches("compare").unwrap();
+        assert!(match_compare_arguments(&matches).is_err());
+
+        let app = create_app().get_matches_from(vec!["", "compare", "--sdf", sdf_path]);
+        let matches = app.subcommand_matches("compare").unwrap();
+        assert_eq!(
+            match_compare_arguments(&matches).unwrap_err().to_string(),
+            "Exactly two documents have to be given for a comparison!"
+        );
+    }
+
     #[test]
     fn create_app_print_test() {
         let app = create_app();
@@ -0,0 +1,118 @@
+//! Resolution of the `contentFormat` quality of SDF data qualities.
+//!
+//! SDF allows a `contentFormat` to be given either as a media type (optionally
+//! with parameters) or as a numeric CoAP Content-Format. Numeric values are
+//! translated into media types using the entries of the
+//! [CoAP Content-Formats registry](https://www.iana.org/assignments/core-parameters/core-parameters.xhtml#content-formats)
+//! listed in [`CONTENT_FORMATS`], as Thing Descriptions only use media types.
+
+/// The CoAP Content-Formats known to the converter and their media types.
+///
+/// Content-Formats that only differ from another entry in their content
+/// coding are not included.
+pub const CONTENT_FORMATS: &[(u16, &str)] = &[
+    (0, "text/plain;charset=utf-8"),
+    (16, "application/cose; cose-type=\"cose-encrypt0\""),
+    (17, "application/cose; cose-type=\"cose-mac0\""),
+    (18, "application/cose; cose-type=\"cose-sign1\""),
+    (40, "application/link-format"),
+    (41, "application/xml"),
+    (42, "application/octet-stream"),
+    (47, "application/exi"),
+    (50, "application/json"),
+    (51, "application/json-patch+json"),
+    (52, "application/merge-patch+json"),
+    (60, "application/cbor"),
+    (61, "application/cwt"),
+    (62, "application/multipart-core"),
+    (63, "application/cbor-seq"),
+    (96, "application/cose; cose-type=\"cose-encrypt\""),
+    (97, "application/cose; cose-type=\"cose-mac\""),
+    (98, "application/cose; cose-type=\"cose-sign\""),
+    (101, "application/cose-key"),
+    (102, "application/cose-key-set"),
+    (110, "application/senml+json"),
+    (111, "application/sensml+json"),
+    (112, "application/senml+cbor"),
+    (113, "application/sensml+cbor"),
+    (114, "application/senml-exi"),
+    (115, "application/sensml-exi"),
+    (256, "application/coap-group+json"),
+    (271, "application/dots+cbor"),
+    (272, "application/missing-blocks+cbor-seq"),
+    (
+        280,
+        "application/pkcs7-mime; smime-type=server-generated-key",
+    ),
+    (281, "application/pkcs7-mime; smime-type=certs-only"),
+    (284, "application/pkcs8"),
+    (285, "application/csrattrs"),
+    (286, "application/pkcs10"),
+    (287, "application/pkix-cert"),
+    (310, "application/senml+xml"),
+    (311, "application/sensml+xml"),
+    (320, "application/senml-etch+json"),
+    (322, "application/senml-etch+cbor"),
+    (432, "application/td+json"),
+    (10000, "application/vnd.ocf+cbor"),
+    (10001, "application/oscore"),
+    (11542, "application/vnd.oma.lwm2m+tlv"),
+    (11543, "application/vnd.oma.lwm2m+json"),
+    (11544, "application/vnd.oma.lwm2m+cbor"),
+    (20000, "text/css"),
+    (30000, "image/svg+xml"),
+];
+
+/// Resolves the `contentFormat` of an SDF data quality into a media type.
+///
+/// Media types are returned unchanged, while numeric CoAP Content-Formats are
+/// looked up in [`CONTENT_FORMATS`]. Returns `None` for Content-Formats that
+/// are not part of the table.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::sdf::content_format::resolve_media_type;
+///
+/// assert_eq!(resolve_media_type("60"), Some("application/cbor"));
+/// assert_eq!(resolve_media_type("text/csv"), Some("text/csv"));
+/// assert_eq!(resolve_media_type("12345"), None);
+/// ```
+pub fn resolve_media_type(content_format: &str) -> Option<&str> {
+    let content_format = content_format.trim();
+
+    match content_format.parse::<u16>() {
+        Ok(id) => CONTENT_FORMATS
+            .iter()
+            .find(|(registered_id, _)| *registered_id == id)
+            .map(|(_, media_type)| *media_type),
+        Err(_) if content_format.chars().all(|x| x.is_ascii_digit()) => None,
+        Err(_) => Some(content_format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_media_type_test() {
+        assert_eq!(resolve_media_type("0"), Some("text/plain;charset=utf-8"));
+        assert_eq!(resolve_media_type(" 50 "), Some("application/json"));
+        assert_eq!(
+            resolve_media_type("application/senml+cbor"),
+            Some("application/senml+cbor")
+        );
+        assert_eq!(resolve_media_type("11050"), None);
+        assert_eq!(resolve_media_type("70000"), None);
+    }
+
+    #[test]
+    fn unique_content_formats_test() {
+        for (index, (id, media_type)) in CONTENT_FORMATS.iter().enumerate() {
+            assert!(CONTENT_FORMATS[index + 1..].iter().all(
+                |(other_id, other_media_type)| other_id != id && other_media_type != media_type
+            ));
+        }
+    }
+}
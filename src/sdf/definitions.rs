@@ -1,25 +1,26 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use std::collections::HashMap;
+use std::convert::TryFrom;
 
 #[skip_serializing_none]
 #[derive(Default, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SDFModel {
     pub info: Option<InfoBlock>,
-    pub namespace: Option<HashMap<String, String>>,
+    pub namespace: Option<IndexMap<String, String>>,
     pub default_namespace: Option<String>,
-    pub sdf_thing: Option<HashMap<String, ThingQualities>>,
-    pub sdf_product: Option<HashMap<String, ProductQualities>>,
-    pub sdf_object: Option<HashMap<String, ObjectQualities>>,
-    pub sdf_property: Option<HashMap<String, PropertyQualities>>,
-    pub sdf_action: Option<HashMap<String, ActionQualities>>,
-    pub sdf_event: Option<HashMap<String, EventQualities>>,
-    pub sdf_data: Option<HashMap<String, DataQualities>>,
+    pub sdf_thing: Option<IndexMap<String, ThingQualities>>,
+    pub sdf_product: Option<IndexMap<String, ProductQualities>>,
+    pub sdf_object: Option<IndexMap<String, ObjectQualities>>,
+    pub sdf_property: Option<IndexMap<String, PropertyQualities>>,
+    pub sdf_action: Option<IndexMap<String, ActionQualities>>,
+    pub sdf_event: Option<IndexMap<String, EventQualities>>,
+    pub sdf_data: Option<IndexMap<String, DataQualities>>,
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Default, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CommonQualities {
     pub description: Option<String>,
@@ -27,7 +28,6 @@ pub struct CommonQualities {
     pub comment: Option<String>,
     pub sdf_ref: Option<String>,
     pub sdf_required: Option<Vec<String>>,
-    // TODO: Add validation for sdfRef and sdfRequired
 }
 
 #[skip_serializing_none]
@@ -36,8 +36,8 @@ pub struct CommonQualities {
 pub struct ThingQualities {
     #[serde(flatten)]
     pub common_qualities: CommonQualities,
-    pub sdf_object: Option<HashMap<String, ObjectQualities>>,
-    pub sdf_thing: Option<HashMap<String, ThingQualities>>,
+    pub sdf_object: Option<IndexMap<String, ObjectQualities>>,
+    pub sdf_thing: Option<IndexMap<String, ThingQualities>>,
 }
 
 use ThingQualities as ProductQualities;
@@ -48,10 +48,10 @@ use ThingQualities as ProductQualities;
 pub struct ObjectQualities {
     #[serde(flatten)]
     pub common_qualities: CommonQualities,
-    pub sdf_property: Option<HashMap<String, PropertyQualities>>,
-    pub sdf_action: Option<HashMap<String, ActionQualities>>,
-    pub sdf_event: Option<HashMap<String, EventQualities>>,
-    pub sdf_data: Option<HashMap<String, DataQualities>>,
+    pub sdf_property: Option<IndexMap<String, PropertyQualities>>,
+    pub sdf_action: Option<IndexMap<String, ActionQualities>>,
+    pub sdf_event: Option<IndexMap<String, EventQualities>>,
+    pub sdf_data: Option<IndexMap<String, DataQualities>>,
 }
 
 #[skip_serializing_none]
@@ -67,16 +67,35 @@ pub struct NumberTypeQualities<T> {
     pub multiple_of: Option<T>,
 }
 
-#[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+/// The `format` of a string, covering the formats defined by JSON Schema.
+///
+/// SDF itself only defines `date-time`, `date`, `time`, `uri`,
+/// `uri-reference`, and `uuid`. Formats unknown to the converter are kept as
+/// they are.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum FormatQualities {
     DateTime,
     Date,
     Time,
+    Duration,
+    Email,
+    IdnEmail,
+    Hostname,
+    IdnHostname,
+    Ipv4,
+    Ipv6,
     Uri,
     UriReference,
+    Iri,
+    IriReference,
     Uuid,
+    UriTemplate,
+    JsonPointer,
+    RelativeJsonPointer,
+    Regex,
+    #[serde(untagged)]
+    Other(String),
 }
 
 #[skip_serializing_none]
@@ -105,19 +124,31 @@ pub struct StringTypeQualities {
 #[serde(rename_all = "camelCase")]
 pub struct ObjectTypeQualities {
     #[serde(flatten)]
-    pub common_qualities: TypedQualities<HashMap<String, serde_json::Value>>,
+    pub common_qualities: TypedQualities<IndexMap<String, serde_json::Value>>,
     pub required: Option<Vec<String>>,
-    pub properties: Option<HashMap<String, DataQualities>>,
+    pub properties: Option<IndexMap<String, DataQualities>>,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArrayTypeQualities {
+    #[serde(flatten)]
+    pub common_qualities: TypedQualities<Vec<serde_json::Value>>,
     pub min_items: Option<u32>,
     pub max_items: Option<u32>,
     pub unique_items: Option<bool>,
-    pub items: Option<Vec<DataQualities>>, // TODO: Should this be an array/map?
+    pub items: Option<ArrayItems>,
+}
+
+/// The `items` of an array, which SDF defines as a single set of data
+/// qualities. Arrays of data qualities (as used by earlier versions of the
+/// converter) are accepted as well.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ArrayItems {
+    Single(Box<DataQualities>),
+    Multiple(Vec<DataQualities>),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -136,15 +167,40 @@ pub enum RegularTypes {
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SdfChoice {
-    sdf_choice: HashMap<String, DataQualities>,
+    pub r#type: Option<String>,
+    pub sdf_choice: IndexMap<String, DataQualities>,
+}
+
+/// The `const`, `default`, and `enum` qualities of data without a `type`.
+///
+/// Data qualities only contain untyped qualities if at least one of them is
+/// present.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(try_from = "TypedQualities<serde_json::Value>")]
+pub struct UntypedQualities(pub TypedQualities<serde_json::Value>);
+
+impl TryFrom<TypedQualities<serde_json::Value>> for UntypedQualities {
+    type Error = &'static str;
+
+    fn try_from(qualities: TypedQualities<serde_json::Value>) -> Result<Self, Self::Error> {
+        if qualities.r#const.is_none() && qualities.default.is_none() && qualities.r#enum.is_none()
+        {
+            return Err("no const, default, or enum quality");
+        }
+
+        Ok(UntypedQualities(qualities))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
 pub enum Types {
-    Type(RegularTypes),
+    // sdfChoice has to be tried first as it might be accompanied by a type
     SdfChoice(SdfChoice),
+    Type(RegularTypes),
+    // Only data without a (known) type is untyped
+    Untyped(UntypedQualities),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -182,7 +238,7 @@ pub struct ActionQualities {
     pub common_qualities: CommonQualities,
     pub sdf_input_data: Option<DataQualities>,
     pub sdf_output_data: Option<DataQualities>,
-    pub sdf_data: Option<HashMap<String, DataQualities>>,
+    pub sdf_data: Option<IndexMap<String, DataQualities>>,
 }
 
 #[skip_serializing_none]
@@ -192,13 +248,31 @@ pub struct EventQualities {
     #[serde(flatten)]
     pub common_qualities: CommonQualities,
     pub sdf_output_data: Option<DataQualities>,
-    pub sdf_data: Option<HashMap<String, DataQualities>>,
+    pub sdf_data: Option<IndexMap<String, DataQualities>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct InfoBlock {
     pub title: String,
     pub version: String,
     pub copyright: String,
     pub license: String,
 }
+
+/// The qualities a mapping file adds to a single definition of an SDF model.
+pub type MappingEntry = IndexMap<String, serde_json::Value>;
+
+/// An SDF mapping file, which supplements the definitions of an SDF model with
+/// additional qualities like protocol binding information.
+///
+/// The entries of `map` are keyed by JSON pointers (optionally preceded by a
+/// namespace prefix) into the model they supplement.
+#[skip_serializing_none]
+#[derive(Default, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SDFMapping {
+    pub info: Option<InfoBlock>,
+    pub namespace: Option<IndexMap<String, String>>,
+    pub default_namespace: Option<String>,
+    pub map: IndexMap<String, MappingEntry>,
+}
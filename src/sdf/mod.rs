@@ -1,2 +1,9 @@
+pub mod content_format;
 pub mod definitions;
+pub mod model_set;
+pub mod sdf_from_td;
 pub mod sdf_from_tm;
+pub mod sdf_ref;
+pub mod validation;
+
+pub use validation::{validate, validate_json, Violation};
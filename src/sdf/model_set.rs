@@ -0,0 +1,257 @@
+//! Sets of SDF models that reference each other via namespaces.
+
+use super::sdf_ref::Resolver;
+use crate::encoding::{decode, Encoding};
+use crate::error::from_json_str;
+use crate::Result;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// The extensions of SDF files in the supported encodings.
+pub const SDF_FILE_EXTENSIONS: [&str; 4] = [".sdf.json", ".sdf.yaml", ".sdf.yml", ".sdf.cbor"];
+
+/// A set of SDF models whose namespaced `sdfRef`s can be resolved against
+/// each other.
+///
+/// Models are indexed by the URI of their default namespace, so a reference
+/// like `"zcl:#/sdfData/foo"` is resolved by looking up the URI of the `zcl`
+/// prefix in the `namespace` block of the referencing model and searching
+/// all models with this URI as their default namespace.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::sdf::model_set::SDFModelSet;
+/// use serde_json::json;
+///
+/// let mut model_set = SDFModelSet::new();
+/// model_set.add_model(
+///     "foo".to_string(),
+///     json!({
+///         "namespace": {"foo": "https://example.org/foo"},
+///         "defaultNamespace": "foo",
+///         "sdfData": {"bar": {"type": "string"}}
+///     }),
+/// );
+///
+/// let sdf_model = json!({
+///     "namespace": {"foo": "https://example.org/foo"},
+///     "sdfProperty": {"baz": {"sdfRef": "foo:#/sdfData/bar"}}
+/// });
+///
+/// let resolved_model = model_set.resolve(&sdf_model).unwrap();
+///
+/// assert_eq!(resolved_model["sdfProperty"]["baz"], json!({"type": "string"}));
+/// ```
+#[derive(Default, Debug)]
+pub struct SDFModelSet {
+    models: Vec<(String, Value)>,
+}
+
+impl SDFModelSet {
+    /// Creates an empty model set.
+    pub fn new() -> Self {
+        SDFModelSet::default()
+    }
+
+    /// Creates a model set from a list of SDF files and/or directories.
+    ///
+    /// See [`SDFModelSet::load_path`] for how paths are processed.
+    pub fn from_paths<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let mut model_set = SDFModelSet::new();
+
+        for path in paths {
+            model_set.load_path(path)?;
+        }
+
+        Ok(model_set)
+    }
+
+    /// Adds an SDF model with the given name to the set.
+    pub fn add_model(&mut self, name: String, sdf_model: Value) {
+        self.models.push((name, sdf_model));
+    }
+
+    /// Loads an SDF file or, if `path` points to a directory, all files ending
+    /// with `.sdf.json`, `.sdf.yaml`, `.sdf.yml`, or `.sdf.cbor` within the
+    /// directory and its subdirectories.
+    ///
+    /// The models are named after the path they have been loaded from.
+    pub fn load_path<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|x| x.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            entries.sort();
+
+            for entry in entries {
+                let is_sdf_file = entry
+                    .file_name()
+                    .and_then(|x| x.to_str())
+                    .is_some_and(|x| SDF_FILE_EXTENSIONS.iter().any(|y| x.ends_with(y)));
+
+                if entry.is_dir() || is_sdf_file {
+                    self.load_path(entry)?;
+                }
+            }
+
+            Ok(())
+        } else {
+            let bytes = fs::read(path)?;
+            let encoding = Encoding::from_path(&path.to_string_lossy())
+                .unwrap_or_else(|| Encoding::detect(&bytes));
+            let json_string = decode(&bytes, encoding)?;
+            let sdf_model = from_json_str(&json_string)?;
+            self.add_model(path.to_string_lossy().to_string(), sdf_model);
+            Ok(())
+        }
+    }
+
+    /// Returns the names of all models in the set.
+    pub fn model_names(&self) -> Vec<&str> {
+        self.models.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Resolves all `sdfRef`s of an SDF model, using the models of the set
+    /// for resolving namespaced references.
+    pub fn resolve(&self, sdf_model: &Value) -> Result<Value> {
+        let mut documents = vec![sdf_model];
+        documents.extend(self.models.iter().map(|(_, model)| model));
+
+        Resolver::new(documents).resolve_document(0)
+    }
+
+    /// Resolves all `sdfRef`s of all models in the set.
+    ///
+    /// Returns the resolved models together with their names.
+    pub fn resolve_all(&self) -> Result<Vec<(String, Value)>> {
+        let documents = self.models.iter().map(|(_, model)| model).collect();
+        let resolver = Resolver::new(documents);
+
+        self.models
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| Ok((name.clone(), resolver.resolve_document(index)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn create_model_set() -> SDFModelSet {
+        let mut model_set = SDFModelSet::new();
+        model_set.add_model(
+            "foo".to_string(),
+            json!({
+                "namespace": {
+                    "foo": "https://example.org/foo",
+                    "bar": "https://example.org/bar"
+                },
+                "defaultNamespace": "foo",
+                "sdfData": {
+                    "foo": {
+                        "sdfRef": "bar:#/sdfData/bar",
+                        "minimum": 0
+                    }
+                }
+            }),
+        );
+        model_set.add_model(
+            "bar".to_string(),
+            json!({
+                "namespace": {
+                    "bar": "https://example.org/bar"
+                },
+                "defaultNamespace": "bar",
+                "sdfData": {
+                    "bar": {
+                        "type": "integer"
+                    },
+                    "baz": {
+                        "sdfRef": "#/sdfData/bar"
+                    }
+                }
+            }),
+        );
+        model_set
+    }
+
+    #[test]
+    fn resolve_cross_document_sdf_ref_test() {
+        let model_set = create_model_set();
+        let sdf_model = json!({
+            "namespace": {
+                "foo": "https://example.org/foo"
+            },
+            "sdfProperty": {
+                "foobar": {
+                    "sdfRef": "foo:#/sdfData/foo",
+                    "maximum": 5
+                }
+            }
+        });
+
+        let resolved_model = model_set.resolve(&sdf_model).unwrap();
+
+        assert_eq!(
+            resolved_model["sdfProperty"]["foobar"],
+            json!({"type": "integer", "minimum": 0, "maximum": 5})
+        );
+    }
+
+    #[test]
+    fn resolve_all_test() {
+        let model_set = create_model_set();
+        let resolved_models = model_set.resolve_all().unwrap();
+
+        assert_eq!(model_set.model_names(), vec!["foo", "bar"]);
+        assert_eq!(
+            resolved_models[0].1["sdfData"]["foo"],
+            json!({"type": "integer", "minimum": 0})
+        );
+        assert_eq!(
+            resolved_models[1].1["sdfData"]["baz"],
+            json!({"type": "integer"})
+        );
+    }
+
+    #[test]
+    fn resolve_missing_cross_document_sdf_ref_test() {
+        let model_set = create_model_set();
+        let sdf_model = json!({
+            "namespace": {
+                "bar": "https://example.org/bar"
+            },
+            "sdfProperty": {
+                "foobar": {
+                    "sdfRef": "bar:#/sdfData/foo"
+                }
+            }
+        });
+
+        assert!(model_set.resolve(&sdf_model).is_err());
+    }
+
+    #[test]
+    fn load_path_test() {
+        let model_set = SDFModelSet::from_paths(&["examples/sdf"]).unwrap();
+
+        assert_eq!(
+            model_set.model_names(),
+            vec![
+                "examples/sdf/example.sdf.json",
+                "examples/sdf/example.sdf.yaml",
+                "examples/sdf/sdfobject-level-refs.sdf.json",
+                "examples/sdf/sdfobject-level.sdf.json"
+            ]
+        );
+        assert!(model_set.resolve_all().is_ok());
+        assert!(SDFModelSet::from_paths(&["foobar.sdf.json"]).is_err());
+    }
+}
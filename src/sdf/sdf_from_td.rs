@@ -0,0 +1,226 @@
+//! Conversion of Thing Descriptions into SDF models and mapping files.
+//!
+//! The affordances of a Thing Description are converted in the same way as
+//! those of a Thing Model. The protocol binding information of the Thing
+//! Description, i.e., its forms and security information, is exported into an
+//! SDF mapping file, whose `map` entries are keyed by JSON pointers into the
+//! resulting SDF model.
+
+use super::definitions as sdf;
+use super::sdf_from_tm::{convert_thing_model, WotSource};
+use crate::diagnostics::{report_ignored_members, sort_diagnostics, Conversion};
+use crate::error::from_json_value;
+use crate::json_pointer::escape_pointer_token;
+use crate::wot::definitions as wot;
+use crate::Result;
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+const AFFORDANCE_TYPES: [(&str, &str); 3] = [
+    ("properties", "sdfProperty"),
+    ("actions", "sdfAction"),
+    ("events", "sdfEvent"),
+];
+
+const THING_LEVEL_FIELDS: [&str; 5] = ["id", "base", "security", "securityDefinitions", "forms"];
+
+/// Returns the JSON pointer to the definition containing the affordances
+/// converted from the Thing Description.
+fn get_affordance_container(sdf_model: &sdf::SDFModel, title: &str) -> String {
+    let has_object = sdf_model
+        .sdf_object
+        .as_ref()
+        .is_some_and(|x| x.contains_key(title));
+
+    match has_object {
+        true => format!("#/sdfObject/{}", escape_pointer_token(title)),
+        false => "#".to_string(),
+    }
+}
+
+/// Creates a mapping entry from the protocol binding fields of a Thing
+/// Description or an interaction affordance.
+///
+/// The fields are prefixed with `td:` to distinguish them from SDF qualities.
+fn create_mapping_entry(object: &Map<String, Value>, fields: &[&str]) -> Option<sdf::MappingEntry> {
+    let entry: sdf::MappingEntry = fields
+        .iter()
+        .filter_map(|field| {
+            object
+                .get(*field)
+                .map(|value| (format!("td:{}", field), value.clone()))
+        })
+        .collect();
+
+    match entry.is_empty() {
+        true => None,
+        false => Some(entry),
+    }
+}
+
+fn create_mapping(
+    thing_description: &Value,
+    sdf_model: &sdf::SDFModel,
+    title: &str,
+) -> sdf::SDFMapping {
+    let container = get_affordance_container(sdf_model, title);
+    let mut map = IndexMap::new();
+
+    if let Value::Object(thing_description) = thing_description {
+        if let Some(entry) = create_mapping_entry(thing_description, &THING_LEVEL_FIELDS) {
+            map.insert(container.clone(), entry);
+        }
+
+        for (td_affordance_type, sdf_affordance_type) in AFFORDANCE_TYPES {
+            let affordances = thing_description
+                .get(td_affordance_type)
+                .and_then(Value::as_object);
+
+            for (name, affordance) in affordances.into_iter().flatten() {
+                let entry = affordance
+                    .as_object()
+                    .and_then(|x| create_mapping_entry(x, &["forms"]));
+
+                if let Some(entry) = entry {
+                    let pointer = format!(
+                        "{}/{}/{}",
+                        container,
+                        sdf_affordance_type,
+                        escape_pointer_token(name)
+                    );
+                    map.insert(pointer, entry);
+                }
+            }
+        }
+    }
+
+    sdf::SDFMapping {
+        info: sdf_model.info.clone(),
+        namespace: sdf_model.namespace.clone(),
+        default_namespace: sdf_model.default_namespace.clone(),
+        map,
+    }
+}
+
+/// Converts the JSON representation of a Thing Description into an SDF model
+/// and an SDF mapping file.
+///
+/// The affordances are mapped using the same rules as for Thing Models. The
+/// forms of every affordance are placed in the `map` entry of the
+/// corresponding SDF definition (e.g., `#/sdfObject/Lamp/sdfProperty/status`)
+/// as `td:forms`, while Thing-level information like `td:base`, `td:security`
+/// and `td:securityDefinitions` is placed in the entry of the `sdfObject`
+/// containing the affordances.
+///
+/// The diagnostics report the members of the Thing Description that cannot be
+/// represented in either of them.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::sdf::sdf_from_td::convert_thing_description;
+/// use serde_json::json;
+///
+/// let thing_description = json!({
+///     "@context": "https://www.w3.org/2019/wot/td/v1",
+///     "title": "Lamp",
+///     "security": "nosec_sc",
+///     "securityDefinitions": {"nosec_sc": {"scheme": "nosec"}},
+///     "properties": {
+///         "status": {
+///             "type": "boolean",
+///             "forms": [{"href": "https://example.org/status"}]
+///         }
+///     }
+/// });
+///
+/// let (sdf_model, mapping) = convert_thing_description(&thing_description)
+///     .unwrap()
+///     .output;
+///
+/// assert!(sdf_model.sdf_object.unwrap().contains_key("Lamp"));
+/// assert_eq!(
+///     mapping.map["#/sdfObject/Lamp/sdfProperty/status"]["td:forms"],
+///     json!([{"href": "https://example.org/status"}])
+/// );
+/// ```
+pub fn convert_thing_description(
+    thing_description: &Value,
+) -> Result<Conversion<(sdf::SDFModel, sdf::SDFMapping)>> {
+    let typed_thing_description =
+        from_json_value::<wot::ThingDescription>(thing_description.clone())?;
+    let thing_model = from_json_value::<wot::ThingModel>(thing_description.clone())?;
+
+    let mut diagnostics = Vec::new();
+    let typed_thing_model = serde_json::to_value(&thing_model)?;
+    report_ignored_members(&mut diagnostics, thing_description, &typed_thing_model, "");
+
+    let conversion = convert_thing_model(thing_model, WotSource::ThingDescription);
+    diagnostics.extend(conversion.diagnostics);
+    sort_diagnostics(&mut diagnostics, thing_description, None);
+
+    let sdf_model = conversion.output;
+    let mapping = create_mapping(
+        thing_description,
+        &sdf_model,
+        &typed_thing_description.title,
+    );
+
+    Ok(Conversion::new((sdf_model, mapping), diagnostics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn create_mapping_entry_test() {
+        let object = json!({
+            "title": "foo",
+            "forms": [{"href": "coap://example.org"}]
+        });
+
+        let entry = create_mapping_entry(object.as_object().unwrap(), &["forms"]).unwrap();
+        assert_eq!(entry["td:forms"], json!([{"href": "coap://example.org"}]));
+        assert_eq!(entry.len(), 1);
+        assert_eq!(
+            create_mapping_entry(object.as_object().unwrap(), &["base"]),
+            None
+        );
+    }
+
+    #[test]
+    fn convert_thing_description_without_affordances_test() {
+        let thing_description = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "title": "Lamp",
+            "base": "https://example.org",
+            "security": "nosec_sc",
+            "securityDefinitions": {"nosec_sc": {"scheme": "nosec"}}
+        });
+
+        let (_, mapping) = convert_thing_description(&thing_description)
+            .unwrap()
+            .output;
+
+        assert_eq!(
+            serde_json::to_value(&mapping.map["#"]).unwrap(),
+            json!({
+                "td:base": "https://example.org",
+                "td:security": "nosec_sc",
+                "td:securityDefinitions": {"nosec_sc": {"scheme": "nosec"}}
+            })
+        );
+    }
+
+    #[test]
+    fn convert_invalid_thing_description_test() {
+        let thing_description = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "title": "Lamp"
+        });
+
+        assert!(convert_thing_description(&thing_description).is_err());
+    }
+}
@@ -1,6 +1,42 @@
 use super::definitions as sdf;
+use crate::diagnostics::{
+    report, report_dropped, set_document, Conversion, Diagnostic, DiagnosticKind,
+};
+use crate::json_pointer::{definition_pointer, member_pointer};
 use crate::wot::definitions as wot;
-use std::collections::HashMap;
+use crate::wot::td_from_sdf::DEFAULT_CONTENT_TYPE;
+use crate::wot::tm_from_sdf::{
+    BYTE_STRING_ENCODING, CHOICE_TYPE_PREFIX, SDF_VOCABULARY, UNIX_TIME_TYPE, UNIX_TIME_UNIT,
+};
+use crate::wot::tm_resolver::resolve_href;
+use crate::wot::units::{is_unit_namespace, normalize_units};
+use crate::{ConversionError, Result};
+use indexmap::IndexMap;
+use std::convert::TryFrom;
+
+const TARGET: &str = "an SDF model";
+
+const TD_CONTEXT_URIS: [&str; 2] = [
+    "https://www.w3.org/2019/wot/td/v1",
+    "https://www.w3.org/2022/wot/td/v1.1",
+];
+
+/// The string formats defined by SDF. Other formats are kept, but might not be
+/// understood by consumers of the SDF model.
+const SDF_FORMATS: [&str; 6] = ["date-time", "date", "time", "uri", "uri-reference", "uuid"];
+
+/// The kind of WoT document converted into an SDF model.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum WotSource {
+    /// A Thing Model, whose forms and security information are dropped.
+    ThingModel,
+    /// A composed Thing Model, whose `tm:submodel` links are converted into
+    /// sdfObjects and sdfThings.
+    ComposedThingModel,
+    /// A Thing Description, whose forms and security information are exported
+    /// into an SDF mapping file.
+    ThingDescription,
+}
 
 /// Creates an info block from a Thing Model. I am a bit unsure how to map a
 /// TM that has not been an SDF model before therefore this function only
@@ -25,14 +61,18 @@ fn create_info_block(thing_model: &wot::ThingModel) -> Option<sdf::InfoBlock> {
 }
 
 /// Converts the @context of a Thing Model to SDF namespaces. Context entries that
-/// only consist of a single URI are ignored for now as it is unclear how to map them.
-fn convert_namespaces(context: &wot::Context) -> Option<HashMap<String, String>> {
-    let mut namespaces: HashMap<String, String> = HashMap::new();
+/// only consist of a single URI are ignored for now as it is unclear how to map them,
+/// as are the prefixes of the SDF vocabulary and of unit vocabularies, whose
+/// units are converted into SenML unit symbols.
+fn convert_namespaces(context: &wot::Context) -> Option<IndexMap<String, String>> {
+    let mut namespaces: IndexMap<String, String> = IndexMap::new();
     if let wot::Context::Array(array_context) = context {
         for context_entry in array_context {
             if let wot::ContextEntry::Map(map_entry) = context_entry {
                 for (key, value) in map_entry {
-                    namespaces.insert(key.clone(), value.clone());
+                    if value != SDF_VOCABULARY.1 && !is_unit_namespace(value) {
+                        namespaces.insert(key.clone(), value.clone());
+                    }
                 }
             }
         }
@@ -46,46 +86,533 @@ fn convert_namespaces(context: &wot::Context) -> Option<HashMap<String, String>>
 }
 
 /// Converts a WoT Thing Model into an SDF model.
+///
+/// If the Thing Model has a title, its affordances are placed inside of an
+/// sdfObject named after the title. Otherwise, they are placed at the top level
+/// of the SDF model.
 impl From<wot::ThingModel> for sdf::SDFModel {
     fn from(thing_model: wot::ThingModel) -> Self {
-        let info = create_info_block(&thing_model);
-        let namespace = convert_namespaces(&thing_model.base_thing.context);
-        let default_namespace = None;
-        let sdf_thing = None;
-        let sdf_product = None;
-        let sdf_object = None;
-        let sdf_property = create_qualities::<wot::TMPropertyAffordance, sdf::PropertyQualities>(
-            thing_model.properties,
+        convert_thing_model(thing_model, WotSource::ThingModel).output
+    }
+}
+
+/// Converts a WoT Thing Model (or the Thing Model part of a Thing Description)
+/// into an SDF model like the [`From`] implementation, reporting the members
+/// of the `source` document that are dropped, approximated, or defaulted.
+pub(crate) fn convert_thing_model(
+    mut thing_model: wot::ThingModel,
+    source: WotSource,
+) -> Conversion<sdf::SDFModel> {
+    let mut diagnostics = Vec::new();
+    normalize_units(&mut thing_model);
+    report_thing_members(&mut diagnostics, &thing_model, source);
+
+    let info = create_info_block(&thing_model);
+    let namespace = convert_namespaces(&thing_model.base_thing.context);
+    let default_namespace = None;
+    let sdf_thing = None;
+    let sdf_product = None;
+    let sdf_data = None;
+
+    let sdf_object;
+    let sdf_property;
+    let sdf_action;
+    let sdf_event;
+
+    match thing_model.title.clone() {
+        Some(title) if has_affordances(&thing_model) => {
+            let object_qualities =
+                create_object_qualities(thing_model, None, &mut diagnostics, source);
+            sdf_object = Some(vec![(title, object_qualities)].into_iter().collect());
+            sdf_property = None;
+            sdf_action = None;
+            sdf_event = None;
+        }
+        _ => {
+            report_unused_title(&mut diagnostics, &thing_model, source);
+            sdf_object = None;
+            sdf_property = create_qualities(
+                thing_model.properties,
+                "properties",
+                &mut diagnostics,
+                source,
+                convert_property,
+            );
+            sdf_action = create_qualities(
+                thing_model.actions,
+                "actions",
+                &mut diagnostics,
+                source,
+                convert_action,
+            );
+            sdf_event = create_qualities(
+                thing_model.events,
+                "events",
+                &mut diagnostics,
+                source,
+                convert_event,
+            );
+        }
+    }
+
+    let sdf_model = sdf::SDFModel {
+        info,
+        namespace,
+        default_namespace,
+        sdf_thing,
+        sdf_product,
+        sdf_object,
+        sdf_property,
+        sdf_action,
+        sdf_event,
+        sdf_data,
+    };
+
+    Conversion::new(sdf_model, diagnostics)
+}
+
+fn has_affordances(thing_model: &wot::ThingModel) -> bool {
+    thing_model.properties.is_some()
+        || thing_model.actions.is_some()
+        || thing_model.events.is_some()
+}
+
+/// Reports the members out of `members` that are present as dropped.
+fn report_members(diagnostics: &mut Vec<Diagnostic>, pointer: &str, members: &[(&str, bool)]) {
+    for (name, is_present) in members {
+        if *is_present {
+            report_dropped(diagnostics, pointer, name, TARGET);
+        }
+    }
+}
+
+/// Reports the Thing-level members of a Thing Model that cannot be
+/// represented in an SDF model.
+///
+/// The protocol binding information of Thing Descriptions is exported into
+/// the mapping file instead.
+fn report_thing_members(
+    diagnostics: &mut Vec<Diagnostic>,
+    thing_model: &wot::ThingModel,
+    source: WotSource,
+) {
+    let base_thing = &thing_model.base_thing;
+    report_members(
+        diagnostics,
+        "",
+        &[
+            ("@type", base_thing.r#type.is_some()),
+            ("titles", base_thing.titles.is_some()),
+            ("descriptions", base_thing.descriptions.is_some()),
+            ("version", base_thing.version.is_some()),
+            ("created", base_thing.created.is_some()),
+            ("modified", base_thing.modified.is_some()),
+            ("support", base_thing.support.is_some()),
+            ("profile", base_thing.profile.is_some()),
+            ("schemaDefinitions", base_thing.schema_definitions.is_some()),
+        ],
+    );
+
+    if source != WotSource::ThingDescription {
+        report_members(
+            diagnostics,
+            "",
+            &[
+                ("id", base_thing.id.is_some()),
+                ("base", base_thing.base.is_some()),
+                ("security", thing_model.security.is_some()),
+                (
+                    "securityDefinitions",
+                    thing_model.security_definitions.is_some(),
+                ),
+                ("forms", thing_model.forms.is_some()),
+            ],
         );
-        let sdf_action =
-            create_qualities::<wot::TMActionAffordance, sdf::ActionQualities>(thing_model.actions);
-        let sdf_event =
-            create_qualities::<wot::TMEventAffordance, sdf::EventQualities>(thing_model.events);
-        let sdf_data = None;
-
-        sdf::SDFModel {
-            info,
-            namespace,
-            default_namespace,
-            sdf_thing,
-            sdf_product,
-            sdf_object,
-            sdf_property,
-            sdf_action,
-            sdf_event,
-            sdf_data,
+    }
+
+    report_context(diagnostics, &base_thing.context);
+    report_links(diagnostics, &thing_model.links, source);
+}
+
+/// Reports the URIs of the `@context` that have no prefix and can therefore
+/// not be converted into SDF namespaces.
+fn report_context(diagnostics: &mut Vec<Diagnostic>, context: &wot::Context) {
+    let uris: Vec<(String, &String)> = match context {
+        wot::Context::String(uri) => vec![("/@context".to_string(), uri)],
+        wot::Context::Array(context_entries) => context_entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, context_entry)| match context_entry {
+                wot::ContextEntry::String(uri) => Some((format!("/@context/{}", index), uri)),
+                wot::ContextEntry::Map(_) => None,
+            })
+            .collect(),
+    };
+
+    for (pointer, uri) in uris {
+        if !TD_CONTEXT_URIS.contains(&uri.as_str()) {
+            report(
+                diagnostics,
+                &pointer,
+                DiagnosticKind::Dropped,
+                format!(
+                    "The context URI \"{}\" has no prefix and cannot be represented as an SDF namespace",
+                    uri
+                ),
+            );
+        }
+    }
+}
+
+/// Reports all links of a Thing Model except for the `tm:submodel` links of
+/// composed Thing Models, which are converted into sdfObjects and sdfThings.
+fn report_links(
+    diagnostics: &mut Vec<Diagnostic>,
+    links: &Option<Vec<wot::TMLink>>,
+    source: WotSource,
+) {
+    for (index, link) in links.iter().flatten().enumerate() {
+        let is_submodel_link = link.link_fields.rel.as_deref() == Some(SUBMODEL_RELATION);
+        if is_submodel_link && source == WotSource::ComposedThingModel {
+            continue;
+        }
+
+        report(
+            diagnostics,
+            &format!("/links/{}", index),
+            DiagnosticKind::Dropped,
+            "Links cannot be represented in an SDF model".to_string(),
+        );
+    }
+}
+
+/// Reports the title and description of a Thing Model whose affordances are
+/// placed at the top level of the SDF model, which has no place for them.
+///
+/// A composed Thing Model without affordances only serves as the container of
+/// its submodels, which is why its title and description are not reported.
+fn report_unused_title(
+    diagnostics: &mut Vec<Diagnostic>,
+    thing_model: &wot::ThingModel,
+    source: WotSource,
+) {
+    if !has_affordances(thing_model) && source == WotSource::ComposedThingModel {
+        return;
+    }
+
+    let reason = match has_affordances(thing_model) {
+        true => "a model without a title",
+        false => "a model without affordances",
+    };
+    let unused_members = match has_affordances(thing_model) {
+        true => vec![("description", thing_model.base_thing.description.is_some())],
+        false => vec![
+            ("title", thing_model.title.is_some()),
+            ("description", thing_model.base_thing.description.is_some()),
+        ],
+    };
+
+    for (name, is_present) in unused_members {
+        if is_present {
+            report(
+                diagnostics,
+                &member_pointer("", name),
+                DiagnosticKind::Dropped,
+                format!(
+                    "The {} of {} cannot be represented in an SDF model",
+                    name, reason
+                ),
+            );
+        }
+    }
+}
+
+/// Creates an sdfObject containing the affordances of a Thing Model.
+fn create_object_qualities(
+    thing_model: wot::ThingModel,
+    label: Option<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+    source: WotSource,
+) -> sdf::ObjectQualities {
+    let common_qualities = sdf::CommonQualities {
+        label,
+        description: thing_model.base_thing.description.clone(),
+        ..Default::default()
+    };
+
+    sdf::ObjectQualities {
+        common_qualities,
+        sdf_property: create_qualities(
+            thing_model.properties,
+            "properties",
+            diagnostics,
+            source,
+            convert_property,
+        ),
+        sdf_action: create_qualities(
+            thing_model.actions,
+            "actions",
+            diagnostics,
+            source,
+            convert_action,
+        ),
+        sdf_event: create_qualities(
+            thing_model.events,
+            "events",
+            diagnostics,
+            source,
+            convert_event,
+        ),
+        sdf_data: None,
+    }
+}
+
+/// Loads the Thing Model a link `href` points to, together with the
+/// diagnostics of its resolution.
+///
+/// Relative `href`s of submodels are resolved against the `href` of the Thing
+/// Model linking to them, so that all `href`s passed to the loader are relative
+/// to the composed Thing Model the conversion has started from.
+pub type ThingModelLoader<'a> = &'a dyn Fn(&str) -> Result<Conversion<wot::ThingModel>>;
+
+const SUBMODEL_RELATION: &str = "tm:submodel";
+
+/// sdfObjects and sdfThings converted from submodels.
+type Submodels = (
+    Option<IndexMap<String, sdf::ObjectQualities>>,
+    Option<IndexMap<String, sdf::ThingQualities>>,
+);
+
+enum Submodel {
+    Object(sdf::ObjectQualities),
+    Thing(sdf::ThingQualities),
+}
+
+/// Returns the `href`s of all `tm:submodel` links of a Thing Model together
+/// with the respective instance names.
+///
+/// If a link has no `instanceName`, the file name of the `href` is used instead.
+fn get_submodel_links(thing_model: &wot::ThingModel) -> Result<Vec<(String, String)>> {
+    thing_model
+        .links
+        .iter()
+        .flatten()
+        .filter(|x| x.link_fields.rel.as_deref() == Some(SUBMODEL_RELATION))
+        .map(|link| {
+            let href = link.href.clone().ok_or_else(|| {
+                ConversionError::Reference(
+                    "Encountered a tm:submodel link without an href!".to_string(),
+                )
+            })?;
+            let instance_name = link.link_fields.instance_name.clone().unwrap_or_else(|| {
+                let file_name = href.rsplit('/').next().unwrap_or(&href);
+                file_name
+                    .strip_suffix(".tm.json")
+                    .unwrap_or(file_name)
+                    .to_string()
+            });
+            Ok((href, instance_name))
+        })
+        .collect()
+}
+
+/// Inserts an sdfObject or sdfThing converted from a composed Thing Model,
+/// returning an error if another one with the same `name` has already been
+/// inserted instead of silently replacing it.
+fn insert_definition<T>(
+    definitions: &mut IndexMap<String, T>,
+    name: String,
+    definition: T,
+    quality_name: &str,
+) -> Result<()> {
+    if definitions.contains_key(&name) {
+        return Err(ConversionError::Invalid(format!(
+            "The composed Thing Model would be converted into more than one {} named \"{}\"! \
+             Use distinct titles and instance names for its submodels.",
+            quality_name, name
+        )));
+    }
+
+    definitions.insert(name, definition);
+    Ok(())
+}
+
+/// Converts the submodels a Thing Model located at `document_href` links to into
+/// sdfObjects and sdfThings.
+fn convert_submodels(
+    submodel_links: Vec<(String, String)>,
+    document_href: &str,
+    loader: ThingModelLoader,
+    visited_hrefs: &mut Vec<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Submodels> {
+    let mut sdf_objects: IndexMap<String, sdf::ObjectQualities> = IndexMap::new();
+    let mut sdf_things: IndexMap<String, sdf::ThingQualities> = IndexMap::new();
+
+    for (href, instance_name) in submodel_links {
+        let href = resolve_href(document_href, &href);
+        match convert_submodel(&href, &instance_name, loader, visited_hrefs, diagnostics)? {
+            Submodel::Object(object_qualities) => {
+                insert_definition(
+                    &mut sdf_objects,
+                    instance_name,
+                    object_qualities,
+                    "sdfObject",
+                )?;
+            }
+            Submodel::Thing(thing_qualities) => {
+                insert_definition(&mut sdf_things, instance_name, thing_qualities, "sdfThing")?;
+            }
         }
     }
+
+    Ok((
+        Some(sdf_objects).filter(|x| !x.is_empty()),
+        Some(sdf_things).filter(|x| !x.is_empty()),
+    ))
 }
 
-fn create_qualities<T, U: From<T>>(
-    wot_definitions: Option<HashMap<String, T>>,
-) -> Option<HashMap<String, U>> {
-    let mut sdf_definitions: HashMap<String, U> = HashMap::new();
+/// Converts a submodel into an sdfObject if it does not link to any submodels
+/// itself and into an sdfThing otherwise.
+///
+/// The affordances of a submodel that is converted into an sdfThing are placed
+/// inside of an sdfObject named after its title (or instance name). The
+/// diagnostics of the submodel refer to its `href`.
+fn convert_submodel(
+    href: &str,
+    instance_name: &str,
+    loader: ThingModelLoader,
+    visited_hrefs: &mut Vec<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Submodel> {
+    if visited_hrefs.iter().any(|x| x == href) {
+        return Err(ConversionError::Reference(format!(
+            "Cyclic tm:submodel link to \"{}\" detected!",
+            href
+        )));
+    }
+
+    let Conversion {
+        output: mut thing_model,
+        diagnostics: mut submodel_diagnostics,
+    } = loader(href)?;
+    normalize_units(&mut thing_model);
+    report_thing_members(
+        &mut submodel_diagnostics,
+        &thing_model,
+        WotSource::ComposedThingModel,
+    );
+
+    let submodel_links = get_submodel_links(&thing_model)?;
+    let label = thing_model.title.clone().filter(|x| x != instance_name);
+    let object_name = thing_model
+        .title
+        .clone()
+        .unwrap_or_else(|| instance_name.to_string());
+    let description = thing_model.base_thing.description.clone();
+    let source = WotSource::ComposedThingModel;
+
+    if submodel_links.is_empty() {
+        let object_qualities =
+            create_object_qualities(thing_model, label, &mut submodel_diagnostics, source);
+        set_document(&mut submodel_diagnostics, href);
+        diagnostics.append(&mut submodel_diagnostics);
+        return Ok(Submodel::Object(object_qualities));
+    }
+
+    let object_qualities = match has_affordances(&thing_model) {
+        true => Some(create_object_qualities(
+            thing_model,
+            None,
+            &mut submodel_diagnostics,
+            source,
+        )),
+        false => None,
+    };
+    set_document(&mut submodel_diagnostics, href);
+    diagnostics.append(&mut submodel_diagnostics);
+
+    visited_hrefs.push(href.to_string());
+    let (sdf_object, sdf_thing) =
+        convert_submodels(submodel_links, href, loader, visited_hrefs, diagnostics)?;
+    visited_hrefs.pop();
+
+    let common_qualities = sdf::CommonQualities {
+        label,
+        description,
+        ..Default::default()
+    };
+
+    let mut thing_qualities = sdf::ThingQualities {
+        common_qualities,
+        sdf_object,
+        sdf_thing,
+    };
+
+    if let Some(object_qualities) = object_qualities {
+        insert_definition(
+            thing_qualities.sdf_object.get_or_insert_with(IndexMap::new),
+            object_name,
+            object_qualities,
+            "sdfObject",
+        )?;
+    }
+
+    Ok(Submodel::Thing(thing_qualities))
+}
+
+/// Converts a composed WoT Thing Model into an SDF model.
+///
+/// The submodels referenced via `tm:submodel` links are retrieved using the
+/// `loader` and converted into sdfObjects (if they do not reference any
+/// submodels themselves) or sdfThings (otherwise), named after the
+/// `instanceName` of the respective link. An error is returned if two of the
+/// resulting sdfObjects or sdfThings would have the same name.
+pub fn convert_composed_thing_model(
+    thing_model: wot::ThingModel,
+    loader: ThingModelLoader,
+) -> Result<Conversion<sdf::SDFModel>> {
+    let submodel_links = get_submodel_links(&thing_model)?;
+    let Conversion {
+        output: mut sdf_model,
+        mut diagnostics,
+    } = convert_thing_model(thing_model, WotSource::ComposedThingModel);
+
+    let (sdf_objects, sdf_things) = convert_submodels(
+        submodel_links,
+        "",
+        loader,
+        &mut Vec::new(),
+        &mut diagnostics,
+    )?;
+
+    if let Some(sdf_objects) = sdf_objects {
+        let definitions = sdf_model.sdf_object.get_or_insert_with(IndexMap::new);
+        for (name, object_qualities) in sdf_objects {
+            insert_definition(definitions, name, object_qualities, "sdfObject")?;
+        }
+    }
+    sdf_model.sdf_thing = sdf_things;
+
+    Ok(Conversion::new(sdf_model, diagnostics))
+}
+
+/// Converts the affordances of the given `affordance_type` (e.g.,
+/// `properties`) into SDF definitions using `convert`.
+fn create_qualities<T, U>(
+    wot_definitions: Option<IndexMap<String, T>>,
+    affordance_type: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+    source: WotSource,
+    convert: fn(T, &mut Vec<Diagnostic>, &str, WotSource) -> U,
+) -> Option<IndexMap<String, U>> {
+    let mut sdf_definitions: IndexMap<String, U> = IndexMap::new();
 
     if let Some(wot_affordances) = wot_definitions {
         for (key, wot_affordance) in wot_affordances {
-            sdf_definitions.insert(key.clone(), U::from(wot_affordance));
+            let pointer = definition_pointer("", affordance_type, &key);
+            let sdf_definition = convert(wot_affordance, diagnostics, &pointer, source);
+            sdf_definitions.insert(key, sdf_definition);
         }
     }
 
@@ -96,9 +623,39 @@ fn create_qualities<T, U: From<T>>(
     }
 }
 
+/// Reports the members of an interaction affordance that cannot be
+/// represented in an SDF model.
+///
+/// The semantic types, titles, and descriptions of property affordances are
+/// reported as part of their data schema.
+fn report_interaction_members(
+    interaction_affordance: &wot::TMInteractionAffordance,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    is_property: bool,
+) {
+    let fields = &interaction_affordance.interaction_affordance_fields;
+
+    if !is_property {
+        report_members(
+            diagnostics,
+            pointer,
+            &[
+                ("@type", fields.r#type.is_some()),
+                ("titles", fields.titles.is_some()),
+                ("descriptions", fields.descriptions.is_some()),
+            ],
+        );
+    }
+    report_members(
+        diagnostics,
+        pointer,
+        &[("uriVariables", fields.uri_variables.is_some())],
+    );
+}
+
 impl From<wot::TMInteractionAffordance> for sdf::CommonQualities {
     fn from(interaction_affordance: wot::TMInteractionAffordance) -> Self {
-        // TODO: Map missing fields
         let interaction_affordance_fields = interaction_affordance.interaction_affordance_fields;
         let description = interaction_affordance_fields.description.clone();
         let label = interaction_affordance_fields.title;
@@ -115,7 +672,6 @@ impl From<wot::TMInteractionAffordance> for sdf::CommonQualities {
 
 impl From<&wot::DataSchema> for sdf::CommonQualities {
     fn from(data_schema: &wot::DataSchema) -> Self {
-        // TODO: Map missing fields
         let description = data_schema.description.clone();
         let label = data_schema.title.clone();
 
@@ -129,44 +685,387 @@ impl From<&wot::DataSchema> for sdf::CommonQualities {
     }
 }
 
-fn map_data_type(data_schema: &wot::DataSchema) -> Option<sdf::Types> {
+fn map_json_object(value: &serde_json::Value) -> Option<IndexMap<String, serde_json::Value>> {
+    value
+        .as_object()
+        .map(|x| x.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+}
+
+/// Returns the names of the members of the serialized `data_type` of a data
+/// schema.
+fn data_type_members(data_schema: &wot::DataSchema) -> Vec<String> {
+    match serde_json::to_value(&data_schema.data_type) {
+        Ok(serde_json::Value::Object(members)) => members.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Reports the members of the `data_type` of a data schema as dropped, except
+/// for the `kept` ones.
+fn report_data_type_members(
+    data_schema: &wot::DataSchema,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    kept: &[&str],
+) {
+    for member in data_type_members(data_schema) {
+        if !kept.contains(&member.as_str()) {
+            report_dropped(diagnostics, pointer, &member, TARGET);
+        }
+    }
+}
+
+/// Maps a `const` or `default` value of a typed data schema, reporting it if
+/// it does not match the type.
+fn map_typed_value<T>(
+    value: &Option<serde_json::Value>,
+    key: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    convert: fn(&serde_json::Value) -> Option<T>,
+) -> Option<T> {
+    let converted = convert(value.as_ref()?);
+
+    if converted.is_none() {
+        report(
+            diagnostics,
+            &member_pointer(pointer, key),
+            DiagnosticKind::Dropped,
+            format!("The {} value does not match the type of the data", key),
+        );
+    }
+
+    converted
+}
+
+/// Maps the `const`, `default`, and `enum` of a typed data schema using
+/// `convert`, reporting the values that do not match the type.
+fn map_typed_qualities<T>(
+    data_schema: &wot::DataSchema,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    convert: fn(&serde_json::Value) -> Option<T>,
+) -> sdf::TypedQualities<T> {
+    let r#const = map_typed_value(&data_schema.r#const, "const", diagnostics, pointer, convert);
+    let default = map_typed_value(
+        &data_schema.default,
+        "default",
+        diagnostics,
+        pointer,
+        convert,
+    );
+    let r#enum = data_schema.r#enum.as_ref().and_then(|values| {
+        let converted: Option<Vec<T>> = values.iter().map(convert).collect();
+        if converted.is_none() {
+            report(
+                diagnostics,
+                &member_pointer(pointer, "enum"),
+                DiagnosticKind::Dropped,
+                "The enum contains values that do not match the type of the data".to_string(),
+            );
+        }
+        converted
+    });
+
+    sdf::TypedQualities {
+        r#enum,
+        r#const,
+        default,
+    }
+}
+
+/// Creates an sdfChoice alternative consisting of a single constant value.
+fn create_constant_choice(value: &serde_json::Value) -> sdf::DataQualities {
+    let data_type = match value {
+        serde_json::Value::String(_) => Some(wot::JSONSchemaTypes::String(Default::default())),
+        serde_json::Value::Bool(_) => Some(wot::JSONSchemaTypes::Boolean),
+        serde_json::Value::Number(number) if number.is_f64() => {
+            Some(wot::JSONSchemaTypes::Number(Default::default()))
+        }
+        serde_json::Value::Number(_) => Some(wot::JSONSchemaTypes::Integer(Default::default())),
+        _ => None,
+    };
+
+    let data_schema = wot::DataSchema {
+        data_type,
+        r#const: Some(value.clone()),
+        ..Default::default()
+    };
+
+    // The data schema only contains members that can be converted.
+    convert_data_schema(&data_schema, &mut Vec::new(), "")
+}
+
+/// Inserts an sdfChoice alternative, appending a numeric suffix to its `name`
+/// if another alternative already uses it instead of replacing that one.
+fn insert_choice(
+    sdf_choice: &mut IndexMap<String, sdf::DataQualities>,
+    name: String,
+    data_qualities: sdf::DataQualities,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) {
+    let mut unique_name = name.clone();
+    let mut suffix = 2;
+    while sdf_choice.contains_key(&unique_name) {
+        unique_name = format!("{}_{}", name, suffix);
+        suffix += 1;
+    }
+
+    if unique_name != name {
+        report(
+            diagnostics,
+            pointer,
+            DiagnosticKind::Approximated,
+            format!(
+                "The name \"{}\" is already used by another sdfChoice alternative, the alternative has been named \"{}\"",
+                name, unique_name
+            ),
+        );
+    }
+
+    sdf_choice.insert(unique_name, data_qualities);
+}
+
+/// Maps the `enum` of a data schema to an sdfChoice, using the (string) values
+/// as the names of the alternatives.
+fn map_enum_to_sdf_choice(
+    values: &[serde_json::Value],
+    r#type: Option<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> sdf::SdfChoice {
+    let mut sdf_choice = IndexMap::new();
+
+    for (index, value) in values.iter().enumerate() {
+        let name = match value {
+            serde_json::Value::String(string) => string.clone(),
+            value => value.to_string(),
+        };
+        let value_pointer = format!("{}/{}", pointer, index);
+        insert_choice(
+            &mut sdf_choice,
+            name,
+            create_constant_choice(value),
+            diagnostics,
+            &value_pointer,
+        );
+    }
+
+    sdf::SdfChoice { r#type, sdf_choice }
+}
+
+/// Removes the semantic annotation carrying the name of an sdfChoice
+/// alternative from a data schema.
+///
+/// # Return value
+///
+/// The name of the alternative, if the data schema has been annotated.
+fn take_choice_name(data_schema: &mut wot::DataSchema) -> Option<String> {
+    let mut types = match data_schema.r#type.take() {
+        Some(wot::TypeOrTypeArray::Type(x)) => vec![x],
+        Some(wot::TypeOrTypeArray::Array(x)) => x,
+        None => Vec::new(),
+    };
+
+    let position = types.iter().position(|x| x.starts_with(CHOICE_TYPE_PREFIX));
+    let name = position.map(|x| types.remove(x)[CHOICE_TYPE_PREFIX.len()..].to_string());
+
+    data_schema.r#type = match types.len() {
+        0 => None,
+        1 => types.pop().map(wot::TypeOrTypeArray::Type),
+        _ => Some(wot::TypeOrTypeArray::Array(types)),
+    };
+
+    name
+}
+
+/// Maps the `oneOf` alternatives of a data schema (together with their
+/// indices) to an sdfChoice.
+///
+/// The alternatives are named after the annotation added when converting an
+/// sdfChoice into a `oneOf` or, if it is missing, after the titles of the
+/// subschemas. In the former case, a differing title becomes the label of the
+/// alternative. Names used by more than one alternative are made unique using
+/// [`insert_choice`].
+fn map_one_of_to_sdf_choice(
+    alternatives: &[(usize, &wot::DataSchema)],
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> sdf::SdfChoice {
+    let mut sdf_choice = IndexMap::new();
+
+    for (position, (index, data_schema)) in alternatives.iter().enumerate() {
+        let alternative_pointer = format!("{}/{}", pointer, index);
+        let mut data_schema = (*data_schema).clone();
+        let choice_name = take_choice_name(&mut data_schema);
+        let mut data_qualities =
+            convert_data_schema(&data_schema, diagnostics, &alternative_pointer);
+
+        let name = match choice_name {
+            Some(name) => {
+                if data_schema.title.as_ref() == Some(&name) {
+                    data_qualities.common_qualities.label = None;
+                }
+                name
+            }
+            None => {
+                data_qualities.common_qualities.label = None;
+                match data_schema.title {
+                    Some(title) => {
+                        report(
+                            diagnostics,
+                            &member_pointer(&alternative_pointer, "title"),
+                            DiagnosticKind::Approximated,
+                            "The title has been used as the name of the sdfChoice alternative"
+                                .to_string(),
+                        );
+                        title
+                    }
+                    None => {
+                        let name = format!("choice{}", position + 1);
+                        report(
+                            diagnostics,
+                            &alternative_pointer,
+                            DiagnosticKind::Defaulted,
+                            format!(
+                                "The subschema has no title, the sdfChoice alternative has been named \"{}\"",
+                                name
+                            ),
+                        );
+                        name
+                    }
+                }
+            }
+        };
+
+        insert_choice(
+            &mut sdf_choice,
+            name,
+            data_qualities,
+            diagnostics,
+            &alternative_pointer,
+        );
+    }
+
+    sdf::SdfChoice {
+        r#type: None,
+        sdf_choice,
+    }
+}
+
+/// Maps the `enum` of an untyped or string data schema to an sdfChoice,
+/// reporting the members of the data schema that are not part of it.
+fn map_sdf_choice(
+    values: &[serde_json::Value],
+    data_schema: &wot::DataSchema,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> sdf::SdfChoice {
+    report(
+        diagnostics,
+        &member_pointer(pointer, "enum"),
+        DiagnosticKind::Approximated,
+        "The enum has been converted into an sdfChoice named after its values".to_string(),
+    );
+    report_members(
+        diagnostics,
+        pointer,
+        &[
+            ("const", data_schema.r#const.is_some()),
+            ("default", data_schema.default.is_some()),
+            ("format", data_schema.format.is_some()),
+        ],
+    );
+    // The content encoding and media type are mapped to the sdfType and
+    // contentFormat of the choice.
+    report_data_type_members(
+        data_schema,
+        diagnostics,
+        pointer,
+        &["type", "contentEncoding", "contentMediaType"],
+    );
+
+    let r#type = data_schema.data_type.as_ref().map(|_| "string".to_string());
+    let enum_pointer = member_pointer(pointer, "enum");
+    map_enum_to_sdf_choice(values, r#type, diagnostics, &enum_pointer)
+}
+
+/// Maps the `format` of a string schema, keeping formats without a
+/// corresponding [`sdf::FormatQualities`] variant as they are.
+fn map_format(
+    format: &Option<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> Option<sdf::FormatQualities> {
+    let format = format.as_ref()?;
+
+    if !SDF_FORMATS.contains(&format.as_str()) {
+        report(
+            diagnostics,
+            &member_pointer(pointer, "format"),
+            DiagnosticKind::Approximated,
+            format!("The format \"{}\" is not defined by SDF", format),
+        );
+    }
+
+    match serde_json::from_value(serde_json::Value::from(format.as_str())) {
+        Ok(format) => Some(format),
+        Err(_) => Some(sdf::FormatQualities::Other(format.clone())),
+    }
+}
+
+fn map_data_type(
+    data_schema: &wot::DataSchema,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> Option<sdf::Types> {
+    match (&data_schema.r#enum, &data_schema.data_type) {
+        (Some(values), None) | (Some(values), Some(wot::JSONSchemaTypes::String(_))) => {
+            let sdf_choice = map_sdf_choice(values, data_schema, diagnostics, pointer);
+            return Some(sdf::Types::SdfChoice(sdf_choice));
+        }
+        (_, Some(wot::JSONSchemaTypes::String(_))) => (),
+        _ => report_members(
+            diagnostics,
+            pointer,
+            &[("format", data_schema.format.is_some())],
+        ),
+    }
+
     if let Some(json_schema_type) = &data_schema.data_type {
         match json_schema_type {
-            wot::JSONSchemaTypes::Null => None,
+            wot::JSONSchemaTypes::Null => {
+                report_members(
+                    diagnostics,
+                    pointer,
+                    &[
+                        ("type", true),
+                        ("const", data_schema.r#const.is_some()),
+                        ("default", data_schema.default.is_some()),
+                        ("enum", data_schema.r#enum.is_some()),
+                    ],
+                );
+                None
+            }
             wot::JSONSchemaTypes::Boolean => {
-                let typed_qualities = sdf::TypedQualities::<bool> {
-                    r#enum: data_schema
-                        .r#enum
-                        .as_ref()
-                        .and_then(|x| x.iter().map(serde_json::value::Value::as_bool).collect()),
-                    r#const: data_schema
-                        .r#const
-                        .as_ref()
-                        .and_then(serde_json::value::Value::as_bool),
-                    default: data_schema
-                        .default
-                        .as_ref()
-                        .and_then(serde_json::value::Value::as_bool),
-                };
+                let typed_qualities = map_typed_qualities(
+                    data_schema,
+                    diagnostics,
+                    pointer,
+                    serde_json::Value::as_bool,
+                );
 
                 let boolean_type = sdf::RegularTypes::Boolean(typed_qualities);
                 Some(sdf::Types::Type(boolean_type))
             }
             wot::JSONSchemaTypes::Integer(integer_schema) => {
-                let common_qualities = sdf::TypedQualities::<i64> {
-                    r#enum: data_schema
-                        .r#enum
-                        .as_ref()
-                        .and_then(|x| x.iter().map(serde_json::value::Value::as_i64).collect()),
-                    r#const: data_schema
-                        .r#const
-                        .as_ref()
-                        .and_then(serde_json::value::Value::as_i64),
-                    default: data_schema
-                        .default
-                        .as_ref()
-                        .and_then(serde_json::value::Value::as_i64),
-                };
+                let common_qualities = map_typed_qualities(
+                    data_schema,
+                    diagnostics,
+                    pointer,
+                    serde_json::Value::as_i64,
+                );
 
                 let typed_qualities = sdf::NumberTypeQualities::<i64> {
                     minimum: integer_schema.minimum,
@@ -181,20 +1080,12 @@ fn map_data_type(data_schema: &wot::DataSchema) -> Option<sdf::Types> {
                 Some(sdf::Types::Type(integer_type))
             }
             wot::JSONSchemaTypes::Number(number_schema) => {
-                let common_qualities = sdf::TypedQualities::<f64> {
-                    r#enum: data_schema
-                        .r#enum
-                        .as_ref()
-                        .and_then(|x| x.iter().map(serde_json::value::Value::as_f64).collect()),
-                    r#const: data_schema
-                        .r#const
-                        .as_ref()
-                        .and_then(serde_json::value::Value::as_f64),
-                    default: data_schema
-                        .default
-                        .as_ref()
-                        .and_then(serde_json::value::Value::as_f64),
-                };
+                let common_qualities = map_typed_qualities(
+                    data_schema,
+                    diagnostics,
+                    pointer,
+                    serde_json::Value::as_f64,
+                );
 
                 let typed_qualities = sdf::NumberTypeQualities::<f64> {
                     minimum: number_schema.minimum,
@@ -209,86 +1100,80 @@ fn map_data_type(data_schema: &wot::DataSchema) -> Option<sdf::Types> {
                 Some(sdf::Types::Type(number_type))
             }
             wot::JSONSchemaTypes::String(string_schema) => {
-                let common_qualities = sdf::TypedQualities::<String> {
-                    r#enum: data_schema
-                        .r#enum
-                        .as_ref()
-                        .and_then(|x| x.iter().map(|y| y.as_str().map(String::from)).collect()),
-                    r#const: data_schema
-                        .r#const
-                        .as_ref()
-                        .and_then(|x| serde_json::value::Value::as_str(x).map(String::from)),
-                    default: data_schema
-                        .default
-                        .as_ref()
-                        .and_then(|x| serde_json::value::Value::as_str(x).map(String::from)),
-                };
+                let common_qualities =
+                    map_typed_qualities(data_schema, diagnostics, pointer, |x| {
+                        x.as_str().map(String::from)
+                    });
 
                 let typed_qualities = sdf::StringTypeQualities {
                     common_qualities,
                     min_length: string_schema.min_length,
                     max_length: string_schema.max_length,
                     pattern: string_schema.pattern.clone(),
-                    format: None, // TODO: How to map to FormatQualities?,
+                    format: map_format(&data_schema.format, diagnostics, pointer),
                 };
 
                 let string_type = sdf::RegularTypes::String(typed_qualities);
                 Some(sdf::Types::Type(string_type))
             }
             wot::JSONSchemaTypes::Array(array_schema) => {
-                let items;
-                let mut array_items: Vec<sdf::DataQualities> = Vec::new();
-
-                if let Some(wot_items) = &array_schema.items {
-                    match &**wot_items {
-                        wot::TypeOrTypeArray::Type::<wot::DataSchema>(array_type) => {
-                            array_items.push(sdf::DataQualities::from(array_type));
-                        }
-                        wot::TypeOrTypeArray::Array::<wot::DataSchema>(array) => {
-                            for item in array {
-                                array_items.push(sdf::DataQualities::from(item));
-                            }
-                        }
-                    }
-                };
+                let items_pointer = member_pointer(pointer, "items");
+                let items = array_schema
+                    .items
+                    .as_deref()
+                    .and_then(|wot_items| match wot_items {
+                        wot::TypeOrTypeArray::Type(array_type) => Some(sdf::ArrayItems::Single(
+                            Box::new(convert_data_schema(array_type, diagnostics, &items_pointer)),
+                        )),
+                        wot::TypeOrTypeArray::Array(array) if array.is_empty() => None,
+                        wot::TypeOrTypeArray::Array(array) => Some(sdf::ArrayItems::Multiple(
+                            array
+                                .iter()
+                                .enumerate()
+                                .map(|(index, item)| {
+                                    let item_pointer = format!("{}/{}", items_pointer, index);
+                                    convert_data_schema(item, diagnostics, &item_pointer)
+                                })
+                                .collect(),
+                        )),
+                    });
 
-                if array_items.is_empty() {
-                    items = None;
-                } else {
-                    items = Some(array_items);
-                }
+                let common_qualities =
+                    map_typed_qualities(data_schema, diagnostics, pointer, |x| {
+                        x.as_array().cloned()
+                    });
 
                 let typed_qualities = sdf::ArrayTypeQualities {
+                    common_qualities,
                     min_items: array_schema.min_items,
                     max_items: array_schema.max_items,
                     unique_items: None, // TODO: Is there an equivalent in WoT?
-                    items,              // TODO: Should this be an array or a map?
+                    items,
                 };
 
                 let array_type = sdf::RegularTypes::Array(typed_qualities);
                 Some(sdf::Types::Type(array_type))
             }
             wot::JSONSchemaTypes::Object(object_schema) => {
-                let common_qualities = sdf::TypedQualities::<HashMap<String, serde_json::Value>> {
-                    // TODO: How do you map this?
-                    r#const: None,
-                    default: None,
-                    r#enum: None,
-                };
-                let properties;
-                let mut properties_map: HashMap<String, sdf::DataQualities> = HashMap::new();
+                let common_qualities =
+                    map_typed_qualities(data_schema, diagnostics, pointer, map_json_object);
+                let mut properties_map: IndexMap<String, sdf::DataQualities> = IndexMap::new();
 
                 if let Some(wot_properties) = &object_schema.properties {
                     for (key, data_schema) in wot_properties {
-                        properties_map.insert(key.clone(), sdf::DataQualities::from(data_schema));
+                        let property_pointer = definition_pointer(pointer, "properties", key);
+                        properties_map.insert(
+                            key.clone(),
+                            convert_data_schema(data_schema, diagnostics, &property_pointer),
+                        );
                     }
                 }
 
-                if !properties_map.is_empty() {
-                    properties = Some(properties_map);
+                let properties = if !properties_map.is_empty() {
+                    Some(properties_map)
                 } else {
-                    properties = None;
-                }
+                    None
+                };
 
                 let typed_qualities = sdf::ObjectTypeQualities {
                     common_qualities,
@@ -301,87 +1186,446 @@ fn map_data_type(data_schema: &wot::DataSchema) -> Option<sdf::Types> {
             }
         }
     } else {
-        None
+        map_untyped_qualities(data_schema)
     }
 }
 
-impl From<&wot::DataSchema> for sdf::DataQualities {
-    fn from(data_schema: &wot::DataSchema) -> Self {
-        let unit = data_schema.unit.clone();
-        let writable = data_schema.read_only.map(|x| !x);
-        let readable = data_schema.write_only.map(|x| !x);
-        let jsonschema = map_data_type(data_schema);
-
-        // TODO: Unmapped fields: @type, titles, descriptions, one_of, format
-        // TODO: Check how type of enum, const, and default should be handled
-
-        sdf::DataQualities {
-            common_qualities: sdf::CommonQualities::from(data_schema),
-            jsonschema,
-            unit,
-            observable: None,
-            readable,
-            writable,
-            nullable: None,
-            sdf_type: None,
-            content_format: None,
+/// Keeps the `const` and `default` values of data schemas without a `type`.
+fn map_untyped_qualities(data_schema: &wot::DataSchema) -> Option<sdf::Types> {
+    let typed_qualities = sdf::TypedQualities {
+        r#const: data_schema.r#const.clone(),
+        default: data_schema.default.clone(),
+        r#enum: data_schema.r#enum.clone(),
+    };
+
+    sdf::UntypedQualities::try_from(typed_qualities)
+        .ok()
+        .map(sdf::Types::Untyped)
+}
+
+fn has_semantic_type(data_schema: &wot::DataSchema, semantic_type: &str) -> bool {
+    match &data_schema.r#type {
+        Some(wot::TypeOrTypeArray::Type(x)) => x == semantic_type,
+        Some(wot::TypeOrTypeArray::Array(x)) => x.iter().any(|y| y == semantic_type),
+        None => false,
+    }
+}
+
+/// Recognizes the data schemas that correspond to an SDF `sdfType`, i.e.,
+/// base64-encoded strings (`byte-string`) and numbers annotated with the
+/// semantic type `sdf:UnixTime` (`unix-time`).
+fn map_sdf_type(data_schema: &wot::DataSchema) -> Option<sdf::SDFType> {
+    match &data_schema.data_type {
+        Some(wot::JSONSchemaTypes::String(string_schema))
+            if string_schema.content_encoding.as_deref() == Some(BYTE_STRING_ENCODING) =>
+        {
+            Some(sdf::SDFType::ByteString)
         }
+        Some(wot::JSONSchemaTypes::Number(_)) | Some(wot::JSONSchemaTypes::Integer(_))
+            if has_semantic_type(data_schema, UNIX_TIME_TYPE) =>
+        {
+            Some(sdf::SDFType::UnixTime)
+        }
+        _ => None,
     }
 }
 
-impl From<wot::TMActionAffordance> for sdf::ActionQualities {
-    fn from(action_affordance: wot::TMActionAffordance) -> Self {
-        let common_qualities = sdf::CommonQualities::from(action_affordance.interaction_affordance);
-        let action_affordance_fields = action_affordance.action_affordance_fields;
-        let sdf_input_data = action_affordance_fields
-            .input
-            .as_ref()
-            .map(sdf::DataQualities::from);
-        let sdf_output_data = action_affordance_fields
-            .output
-            .as_ref()
-            .map(sdf::DataQualities::from);
-
-        sdf::ActionQualities {
-            common_qualities,
-            sdf_input_data,
-            sdf_output_data,
-            sdf_data: None,
+/// Reports the semantic types and content encodings of a data schema that
+/// are not represented by its `sdf_type`.
+fn report_annotations(
+    data_schema: &wot::DataSchema,
+    sdf_type: &Option<sdf::SDFType>,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) {
+    let is_unix_time = matches!(sdf_type, Some(sdf::SDFType::UnixTime))
+        && matches!(&data_schema.r#type, Some(wot::TypeOrTypeArray::Type(x)) if x == UNIX_TIME_TYPE);
+    let has_unknown_encoding = matches!(
+        &data_schema.data_type,
+        Some(wot::JSONSchemaTypes::String(string_schema))
+            if string_schema.content_encoding.is_some()
+                && !matches!(sdf_type, Some(sdf::SDFType::ByteString))
+    );
+
+    report_members(
+        diagnostics,
+        pointer,
+        &[
+            ("@type", data_schema.r#type.is_some() && !is_unix_time),
+            ("titles", data_schema.titles.is_some()),
+            ("descriptions", data_schema.descriptions.is_some()),
+            ("contentEncoding", has_unknown_encoding),
+        ],
+    );
+}
+
+/// Determines whether a data schema only allows the value `null`.
+fn is_null_schema(data_schema: &wot::DataSchema) -> bool {
+    matches!(data_schema.data_type, Some(wot::JSONSchemaTypes::Null))
+        && data_schema.one_of.is_none()
+        && data_schema.r#enum.is_none()
+        && data_schema.r#const.is_none()
+}
+
+/// Merges the only alternative of a nullable data schema with the enclosing
+/// schema, whose title, description, unit, and access flags take precedence.
+fn merge_alternative(
+    data_schema: &wot::DataSchema,
+    alternative: &wot::DataSchema,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> wot::DataSchema {
+    let overridden_members = [
+        (
+            "title",
+            data_schema.title.is_some() && alternative.title.is_some(),
+        ),
+        (
+            "description",
+            data_schema.description.is_some() && alternative.description.is_some(),
+        ),
+        (
+            "unit",
+            data_schema.unit.is_some() && alternative.unit.is_some(),
+        ),
+        (
+            "readOnly",
+            data_schema.read_only.is_some() && alternative.read_only.is_some(),
+        ),
+        (
+            "writeOnly",
+            data_schema.write_only.is_some() && alternative.write_only.is_some(),
+        ),
+    ];
+
+    for (name, is_overridden) in overridden_members {
+        if is_overridden {
+            report(
+                diagnostics,
+                &member_pointer(pointer, name),
+                DiagnosticKind::Dropped,
+                format!("\"{}\" is overridden by the enclosing data schema", name),
+            );
         }
     }
+
+    let alternative = alternative.clone();
+    wot::DataSchema {
+        title: data_schema.title.clone().or(alternative.title),
+        description: data_schema.description.clone().or(alternative.description),
+        unit: data_schema.unit.clone().or(alternative.unit),
+        read_only: data_schema.read_only.or(alternative.read_only),
+        write_only: data_schema.write_only.or(alternative.write_only),
+        ..alternative
+    }
 }
 
-impl From<wot::TMPropertyAffordance> for sdf::PropertyQualities {
-    fn from(property_affordance: wot::TMPropertyAffordance) -> Self {
-        let property_affordance_fields = property_affordance.property_affordance_fields;
-        let mut property_qualities =
-            sdf::DataQualities::from(&property_affordance_fields.data_schema);
-        property_qualities.observable = property_affordance_fields.observable;
-        property_qualities
+/// Converts a data schema with a `oneOf` into an sdfChoice.
+///
+/// `null` alternatives are converted into `nullable` if there are other
+/// alternatives. A single remaining alternative is merged with the enclosing
+/// schema instead of being converted into an sdfChoice.
+fn convert_one_of(
+    data_schema: &wot::DataSchema,
+    one_of: &[wot::DataSchema],
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> sdf::DataQualities {
+    report_members(
+        diagnostics,
+        pointer,
+        &[
+            ("@type", data_schema.r#type.is_some()),
+            ("titles", data_schema.titles.is_some()),
+            ("descriptions", data_schema.descriptions.is_some()),
+            ("const", data_schema.r#const.is_some()),
+            ("default", data_schema.default.is_some()),
+            ("enum", data_schema.r#enum.is_some()),
+            ("format", data_schema.format.is_some()),
+        ],
+    );
+    report_data_type_members(data_schema, diagnostics, pointer, &[]);
+
+    let one_of_pointer = member_pointer(pointer, "oneOf");
+    let (null_schemas, mut alternatives): (Vec<_>, Vec<_>) = one_of
+        .iter()
+        .enumerate()
+        .partition(|(_, x)| is_null_schema(x));
+    let nullable = !null_schemas.is_empty() && !alternatives.is_empty();
+
+    if nullable {
+        for (index, null_schema) in &null_schemas {
+            let null_pointer = format!("{}/{}", one_of_pointer, index);
+            let members = match serde_json::to_value(null_schema) {
+                Ok(serde_json::Value::Object(members)) => members,
+                _ => continue,
+            };
+            for name in members.keys().filter(|x| *x != "type") {
+                report_dropped(diagnostics, &null_pointer, name, TARGET);
+            }
+        }
+    } else if alternatives.is_empty() {
+        alternatives = null_schemas;
+    }
+
+    if let (true, [(index, alternative)]) = (nullable, alternatives.as_slice()) {
+        let alternative_pointer = format!("{}/{}", one_of_pointer, index);
+        let merged_schema =
+            merge_alternative(data_schema, alternative, diagnostics, &alternative_pointer);
+        let mut data_qualities =
+            convert_data_schema(&merged_schema, diagnostics, &alternative_pointer);
+        data_qualities.nullable = Some(true);
+        return data_qualities;
+    }
+
+    let sdf_choice = map_one_of_to_sdf_choice(&alternatives, diagnostics, &one_of_pointer);
+
+    sdf::DataQualities {
+        common_qualities: sdf::CommonQualities::from(data_schema),
+        jsonschema: Some(sdf::Types::SdfChoice(sdf_choice)),
+        unit: data_schema.unit.clone(),
+        observable: None,
+        readable: data_schema.write_only.map(|x| !x),
+        writable: data_schema.read_only.map(|x| !x),
+        nullable: Some(true).filter(|_| nullable),
+        sdf_type: None,
+        content_format: None,
     }
 }
 
-impl From<wot::TMEventAffordance> for sdf::EventQualities {
-    fn from(event_affordance: wot::TMEventAffordance) -> Self {
-        // TODO: What to do with subscription and cancellation?
-        let common_qualities = sdf::CommonQualities::from(event_affordance.interaction_affordance);
-        let event_affordance_fields = event_affordance.event_affordance_fields;
-        let sdf_output_data = event_affordance_fields
-            .data
-            .as_ref()
-            .map(sdf::DataQualities::from);
+fn map_content_media_type(data_schema: &wot::DataSchema) -> Option<String> {
+    match &data_schema.data_type {
+        Some(wot::JSONSchemaTypes::String(string_schema)) => {
+            string_schema.content_media_type.clone()
+        }
+        _ => None,
+    }
+}
+
+/// Recovers the `contentFormat` of the payload of an affordance from the
+/// `contentType` of its forms if all of them agree on a type other than the
+/// default.
+fn map_forms_content_type(forms: &Option<Vec<wot::TMForm>>) -> Option<String> {
+    let mut content_types = forms
+        .iter()
+        .flatten()
+        .map(|form| form.form_fields.content_type.as_deref());
+    let content_type = content_types.next()??;
+
+    if content_type == DEFAULT_CONTENT_TYPE || !content_types.all(|x| x == Some(content_type)) {
+        return None;
+    }
+
+    Some(content_type.to_string())
+}
 
-        sdf::EventQualities {
-            common_qualities,
-            sdf_output_data,
-            sdf_data: None, // TODO: How should this be mapped?
+/// Sets the `contentFormat` of the sdfInputData of an action or the
+/// sdfOutputData of an event to the content type of the forms of the
+/// affordance, unless the data schema already provides one.
+fn map_payload_content_format(
+    data_qualities: &mut Option<sdf::DataQualities>,
+    forms: &Option<Vec<wot::TMForm>>,
+) {
+    if let Some(data_qualities) = data_qualities {
+        if data_qualities.content_format.is_none() {
+            data_qualities.content_format = map_forms_content_type(forms);
         }
     }
 }
 
+/// Determines whether the forms of an affordance consist of nothing but a
+/// `contentType`, which is fully represented by a recovered `contentFormat`.
+fn has_content_type_forms(forms: &Option<Vec<wot::TMForm>>) -> bool {
+    map_forms_content_type(forms).is_some()
+        && forms.iter().flatten().all(|form| {
+            matches!(
+                serde_json::to_value(form),
+                Ok(serde_json::Value::Object(members)) if members.len() == 1
+            )
+        })
+}
+
+/// Reports the forms of an affordance of a Thing Model, which cannot be
+/// represented in an SDF model unless they only carry the content type of a
+/// `payload` of the affordance.
+fn report_forms(
+    forms: &Option<Vec<wot::TMForm>>,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    source: WotSource,
+    payload: bool,
+) {
+    if forms.is_some()
+        && source != WotSource::ThingDescription
+        && !(payload && has_content_type_forms(forms))
+    {
+        report_dropped(diagnostics, pointer, "forms", TARGET);
+    }
+}
+
+/// Converts a data schema into SDF data qualities, reporting the members that
+/// cannot be represented in an SDF model.
+fn convert_data_schema(
+    data_schema: &wot::DataSchema,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> sdf::DataQualities {
+    if let Some(one_of) = &data_schema.one_of {
+        return convert_one_of(data_schema, one_of, diagnostics, pointer);
+    }
+
+    let sdf_type = map_sdf_type(data_schema);
+    report_annotations(data_schema, &sdf_type, diagnostics, pointer);
+
+    // The unit of a unix-time is implied by its sdfType.
+    let unit = data_schema
+        .unit
+        .clone()
+        .filter(|x| !matches!(sdf_type, Some(sdf::SDFType::UnixTime)) || x != UNIX_TIME_UNIT);
+    let writable = data_schema.read_only.map(|x| !x);
+    let readable = data_schema.write_only.map(|x| !x);
+    let jsonschema = map_data_type(data_schema, diagnostics, pointer);
+
+    sdf::DataQualities {
+        common_qualities: sdf::CommonQualities::from(data_schema),
+        jsonschema,
+        unit,
+        observable: None,
+        readable,
+        writable,
+        nullable: None,
+        sdf_type,
+        content_format: map_content_media_type(data_schema),
+    }
+}
+
+/// Converts an action affordance into an sdfAction.
+fn convert_action(
+    action_affordance: wot::TMActionAffordance,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    source: WotSource,
+) -> sdf::ActionQualities {
+    let interaction_affordance = action_affordance.interaction_affordance;
+    report_interaction_members(&interaction_affordance, diagnostics, pointer, false);
+
+    let action_affordance_fields = action_affordance.action_affordance_fields;
+    report_members(
+        diagnostics,
+        pointer,
+        &[
+            ("safe", action_affordance_fields.safe.is_some()),
+            ("idempotent", action_affordance_fields.idempotent.is_some()),
+        ],
+    );
+
+    let mut sdf_input_data = action_affordance_fields
+        .input
+        .as_ref()
+        .map(|x| convert_data_schema(x, diagnostics, &member_pointer(pointer, "input")));
+    map_payload_content_format(&mut sdf_input_data, &interaction_affordance.forms);
+    report_forms(
+        &interaction_affordance.forms,
+        diagnostics,
+        pointer,
+        source,
+        sdf_input_data.is_some(),
+    );
+    let sdf_output_data = action_affordance_fields
+        .output
+        .as_ref()
+        .map(|x| convert_data_schema(x, diagnostics, &member_pointer(pointer, "output")));
+
+    sdf::ActionQualities {
+        common_qualities: sdf::CommonQualities::from(interaction_affordance),
+        sdf_input_data,
+        sdf_output_data,
+        sdf_data: None,
+    }
+}
+
+/// Converts a property affordance into an sdfProperty.
+fn convert_property(
+    property_affordance: wot::TMPropertyAffordance,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    source: WotSource,
+) -> sdf::PropertyQualities {
+    let interaction_affordance = property_affordance.interaction_affordance;
+    report_interaction_members(&interaction_affordance, diagnostics, pointer, true);
+
+    let property_affordance_fields = property_affordance.property_affordance_fields;
+    let mut property_qualities = convert_data_schema(
+        &property_affordance_fields.data_schema,
+        diagnostics,
+        pointer,
+    );
+    property_qualities.observable = property_affordance_fields.observable;
+    if property_qualities.content_format.is_none() {
+        property_qualities.content_format = map_forms_content_type(&interaction_affordance.forms);
+    }
+    report_forms(
+        &interaction_affordance.forms,
+        diagnostics,
+        pointer,
+        source,
+        true,
+    );
+
+    property_qualities
+}
+
+/// Converts an event affordance into an sdfEvent.
+fn convert_event(
+    event_affordance: wot::TMEventAffordance,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    source: WotSource,
+) -> sdf::EventQualities {
+    let interaction_affordance = event_affordance.interaction_affordance;
+    report_interaction_members(&interaction_affordance, diagnostics, pointer, false);
+
+    let event_affordance_fields = event_affordance.event_affordance_fields;
+    report_members(
+        diagnostics,
+        pointer,
+        &[
+            (
+                "subscription",
+                event_affordance_fields.subscription.is_some(),
+            ),
+            (
+                "cancellation",
+                event_affordance_fields.cancellation.is_some(),
+            ),
+        ],
+    );
+
+    let mut sdf_output_data = event_affordance_fields
+        .data
+        .as_ref()
+        .map(|x| convert_data_schema(x, diagnostics, &member_pointer(pointer, "data")));
+    map_payload_content_format(&mut sdf_output_data, &interaction_affordance.forms);
+    report_forms(
+        &interaction_affordance.forms,
+        diagnostics,
+        pointer,
+        source,
+        sdf_output_data.is_some(),
+    );
+
+    sdf::EventQualities {
+        common_qualities: sdf::CommonQualities::from(interaction_affordance),
+        sdf_output_data,
+        sdf_data: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostics::{report_ignored_members, sort_diagnostics};
+    use serde_json::json;
 
     #[test]
     fn convert_namespaces_string_test() {
@@ -395,11 +1639,11 @@ mod tests {
 
     #[test]
     fn convert_namespaces_map_test() {
-        let context_map: HashMap<String, String> = vec![("foo".to_string(), "bar".to_string())]
+        let context_map: IndexMap<String, String> = vec![("foo".to_string(), "bar".to_string())]
             .into_iter()
             .collect();
         let map_array_context = wot::Context::Array(vec![wot::ContextEntry::Map(context_map)]);
-        let expected_namespace: Option<HashMap<String, String>> = Some(
+        let expected_namespace: Option<IndexMap<String, String>> = Some(
             vec![("foo".to_string(), "bar".to_string())]
                 .into_iter()
                 .collect(),
@@ -409,4 +1653,339 @@ mod tests {
         assert!(converted_namespace.is_some());
         assert_eq!(converted_namespace, expected_namespace);
     }
+
+    /// Converts the JSON representation of a Thing Model like the converter,
+    /// returning the pointers and kinds of the diagnostics in document order.
+    fn get_messages(
+        thing_model: serde_json::Value,
+        source: WotSource,
+    ) -> Vec<(String, DiagnosticKind)> {
+        let typed_model: wot::ThingModel = serde_json::from_value(thing_model.clone()).unwrap();
+        let typed_json = serde_json::to_value(&typed_model).unwrap();
+        let mut diagnostics = Vec::new();
+        report_ignored_members(&mut diagnostics, &thing_model, &typed_json, "");
+
+        diagnostics.extend(convert_thing_model(typed_model, source).diagnostics);
+        sort_diagnostics(&mut diagnostics, &thing_model, None);
+
+        diagnostics
+            .into_iter()
+            .map(|x| (x.pointer, x.kind))
+            .collect()
+    }
+
+    fn to_messages(messages: &[(&str, DiagnosticKind)]) -> Vec<(String, DiagnosticKind)> {
+        messages.iter().map(|(x, y)| (x.to_string(), *y)).collect()
+    }
+
+    #[test]
+    fn thing_model_diagnostics_test() {
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1", "https://example.org", {"foo": "https://example.org/foo"}],
+            "@type": "tm:ThingModel",
+            "title": "Lamp",
+            "titles": {"de": "Lampe"},
+            "version": {"instance": "1.0.0"},
+            "base": "https://example.org",
+            "links": [{"rel": "license", "href": "https://example.org/license"}],
+            "properties": {
+                "status": {
+                    "type": "string",
+                    "format": "email",
+                    "forms": [{"href": "status"}]
+                }
+            },
+            "actions": {
+                "toggle": {
+                    "safe": false,
+                    "input": {
+                        "type": "string",
+                        "contentEncoding": "gzip"
+                    }
+                }
+            },
+            "events": {
+                "overheated": {
+                    "subscription": {"type": "string"},
+                    "data": {"type": "null"}
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model.clone(), WotSource::ThingModel),
+            to_messages(&[
+                ("/@context/1", DiagnosticKind::Dropped),
+                ("/@type", DiagnosticKind::Dropped),
+                ("/titles", DiagnosticKind::Dropped),
+                ("/version", DiagnosticKind::Dropped),
+                ("/base", DiagnosticKind::Dropped),
+                ("/links/0", DiagnosticKind::Dropped),
+                ("/properties/status/format", DiagnosticKind::Approximated),
+                ("/properties/status/forms", DiagnosticKind::Dropped),
+                ("/actions/toggle/safe", DiagnosticKind::Dropped),
+                (
+                    "/actions/toggle/input/contentEncoding",
+                    DiagnosticKind::Dropped
+                ),
+                ("/events/overheated/subscription", DiagnosticKind::Dropped),
+                ("/events/overheated/data/type", DiagnosticKind::Dropped),
+            ])
+        );
+        assert_eq!(
+            get_messages(thing_model, WotSource::ThingDescription).len(),
+            10
+        );
+    }
+
+    #[test]
+    fn format_diagnostics_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "properties": {
+                "foo": {"type": "string", "format": "uuid"},
+                "bar": {"type": "string", "format": "ipv6"},
+                "baz": {"type": "integer", "format": "date-time"}
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model, WotSource::ThingModel),
+            to_messages(&[
+                ("/properties/bar/format", DiagnosticKind::Approximated),
+                ("/properties/baz/format", DiagnosticKind::Dropped),
+            ])
+        );
+    }
+
+    #[test]
+    fn content_type_forms_diagnostics_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "actions": {
+                "foo": {
+                    "input": {"type": "object"},
+                    "forms": [{"contentType": "application/cbor"}]
+                },
+                "bar": {
+                    "forms": [{"contentType": "application/cbor"}]
+                }
+            },
+            "events": {
+                "baz": {
+                    "data": {"type": "array"},
+                    "forms": [{"contentType": "application/senml+cbor"}]
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model.clone(), WotSource::ThingModel),
+            to_messages(&[("/actions/bar/forms", DiagnosticKind::Dropped)])
+        );
+
+        let thing_model: wot::ThingModel = serde_json::from_value(thing_model).unwrap();
+        let sdf_model = sdf::SDFModel::from(thing_model);
+        let actions = sdf_model.sdf_action.unwrap();
+        assert_eq!(
+            actions["foo"]
+                .sdf_input_data
+                .as_ref()
+                .unwrap()
+                .content_format,
+            Some("application/cbor".to_string())
+        );
+        let events = sdf_model.sdf_event.unwrap();
+        assert_eq!(
+            events["baz"]
+                .sdf_output_data
+                .as_ref()
+                .unwrap()
+                .content_format,
+            Some("application/senml+cbor".to_string())
+        );
+    }
+
+    #[test]
+    fn sdf_type_diagnostics_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "properties": {
+                "foo": {"type": "string", "contentEncoding": "base64"},
+                "bar": {"type": "number", "@type": "sdf:UnixTime", "unit": "s"},
+                "baz": {"type": "string", "contentEncoding": "gzip"},
+                "qux": {"type": "integer", "@type": ["sdf:UnixTime", "saref:Time"]}
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model, WotSource::ThingModel),
+            to_messages(&[
+                ("/properties/baz/contentEncoding", DiagnosticKind::Dropped),
+                ("/properties/qux/@type", DiagnosticKind::Dropped),
+            ])
+        );
+    }
+
+    #[test]
+    fn nullable_diagnostics_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "properties": {
+                "foo": {
+                    "readOnly": true,
+                    "oneOf": [
+                        {"type": "number", "minimum": 0, "contentEncoding": "gzip"},
+                        {"type": "null", "title": "Unknown"}
+                    ]
+                },
+                "bar": {
+                    "oneOf": [
+                        {"title": "baz", "type": "integer"},
+                        {"type": "null"},
+                        {"type": "boolean"}
+                    ]
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model, WotSource::ThingModel),
+            to_messages(&[
+                (
+                    "/properties/foo/oneOf/0/contentEncoding",
+                    DiagnosticKind::Dropped
+                ),
+                ("/properties/foo/oneOf/1/title", DiagnosticKind::Dropped),
+                (
+                    "/properties/bar/oneOf/0/title",
+                    DiagnosticKind::Approximated
+                ),
+                ("/properties/bar/oneOf/2", DiagnosticKind::Defaulted),
+            ])
+        );
+    }
+
+    #[test]
+    fn choice_diagnostics_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "properties": {
+                "foo": {
+                    "type": "string",
+                    "enum": ["a", "b"],
+                    "minLength": 1
+                },
+                "bar": {
+                    "oneOf": [
+                        {"title": "baz", "type": "integer"},
+                        {"type": "boolean", "@type": "qux"}
+                    ]
+                },
+                "baz": {
+                    "oneOf": [
+                        {"title": "on", "@type": "sdf:choice/on", "const": 1},
+                        {"title": "Off", "@type": ["sdf:choice/off", "qux"], "const": 0}
+                    ]
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model, WotSource::ThingModel),
+            to_messages(&[
+                ("/properties/foo/enum", DiagnosticKind::Approximated),
+                ("/properties/foo/minLength", DiagnosticKind::Dropped),
+                (
+                    "/properties/bar/oneOf/0/title",
+                    DiagnosticKind::Approximated
+                ),
+                ("/properties/bar/oneOf/1", DiagnosticKind::Defaulted),
+                ("/properties/bar/oneOf/1/@type", DiagnosticKind::Dropped),
+                ("/properties/baz/oneOf/1/@type", DiagnosticKind::Dropped),
+            ])
+        );
+    }
+
+    #[test]
+    fn untyped_diagnostics_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "properties": {
+                "foo": {"default": 3, "const": 3, "minimum": 0}
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model, WotSource::ThingModel),
+            to_messages(&[("/properties/foo/minimum", DiagnosticKind::Dropped)])
+        );
+    }
+
+    #[test]
+    fn mismatching_value_diagnostics_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "properties": {
+                "foo": {"type": "integer", "const": "a", "default": 1},
+                "bar": {"type": "boolean", "enum": [true, 0]}
+            }
+        });
+
+        assert_eq!(
+            get_messages(thing_model, WotSource::ThingModel),
+            to_messages(&[
+                ("/properties/foo/const", DiagnosticKind::Dropped),
+                ("/properties/bar/enum", DiagnosticKind::Dropped),
+            ])
+        );
+    }
+
+    #[test]
+    fn duplicate_choice_names_test() {
+        let thing_model = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "properties": {
+                "foo": {
+                    "oneOf": [
+                        {"title": "bar", "type": "integer"},
+                        {"title": "bar", "type": "boolean"},
+                        {"title": "choice4", "type": "string"},
+                        {"type": "number"}
+                    ]
+                },
+                "baz": {"enum": ["1", 1]}
+            }
+        });
+
+        let typed_model: wot::ThingModel = serde_json::from_value(thing_model.clone()).unwrap();
+        let conversion = convert_thing_model(typed_model, WotSource::ThingModel);
+        let sdf_model = serde_json::to_value(conversion.output).unwrap();
+        let names = |name: &str| -> Vec<String> {
+            sdf_model["sdfProperty"][name]["sdfChoice"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect()
+        };
+
+        assert_eq!(names("foo"), vec!["bar", "bar_2", "choice4", "choice4_2"]);
+        assert_eq!(names("baz"), vec!["1", "1_2"]);
+
+        let renamed: Vec<&str> = conversion
+            .diagnostics
+            .iter()
+            .filter(|x| x.message.contains("already used"))
+            .map(|x| x.pointer.as_str())
+            .collect();
+        assert_eq!(
+            renamed,
+            vec![
+                "/properties/foo/oneOf/1",
+                "/properties/foo/oneOf/3",
+                "/properties/baz/enum/1"
+            ]
+        );
+    }
 }
@@ -0,0 +1,422 @@
+//! Resolution of `sdfRef` qualities within an SDF model.
+//!
+//! Resolution is performed on the raw JSON representation of the model, as
+//! local overrides of a referencing definition (e.g., a `default` without a
+//! `type`) cannot be represented by the typed definitions before the
+//! referenced qualities have been merged in.
+
+use crate::json_pointer::member_pointer;
+use crate::{ConversionError, Result};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+const SDF_REF: &str = "sdfRef";
+
+/// Resolves all local `sdfRef` JSON pointers in an SDF model.
+///
+/// Namespaced references to other models can be resolved using an
+/// [`SDFModelSet`](super::model_set::SDFModelSet).
+///
+/// The qualities of a referenced definition are copied into the referencing
+/// definition, which are then patched with the remaining qualities of the
+/// referencing definition according to JSON Merge Patch
+/// ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)) semantics, as defined
+/// by the SDF specification. The `sdfRef` quality itself is removed after
+/// resolution.
+///
+/// Returns an error if a reference cannot be resolved or if a cycle of
+/// references is detected.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::sdf::sdf_ref::resolve_sdf_refs;
+/// use serde_json::json;
+///
+/// let sdf_model = json!({
+///     "sdfProperty": {
+///         "foo": {
+///             "sdfRef": "#/sdfData/bar",
+///             "default": 5
+///         }
+///     },
+///     "sdfData": {
+///         "bar": {
+///             "type": "integer",
+///             "minimum": 0
+///         }
+///     }
+/// });
+///
+/// let resolved_model = resolve_sdf_refs(&sdf_model).unwrap();
+///
+/// assert_eq!(
+///     resolved_model["sdfProperty"]["foo"],
+///     json!({"type": "integer", "minimum": 0, "default": 5})
+/// );
+/// ```
+pub fn resolve_sdf_refs(sdf_model: &Value) -> Result<Value> {
+    Resolver::new(vec![sdf_model]).resolve_document(0)
+}
+
+/// Returns the URI of the default namespace of an SDF model, if defined.
+pub(crate) fn default_namespace_uri(sdf_model: &Value) -> Option<&str> {
+    let default_namespace = sdf_model.get("defaultNamespace")?.as_str()?;
+    sdf_model.get("namespace")?.get(default_namespace)?.as_str()
+}
+
+/// Resolves `sdfRef`s across a number of SDF models.
+///
+/// Local references (`#/...`) are first looked up in the referencing model
+/// and then in all other models sharing its default namespace. Namespaced
+/// references (`prefix:#/...`) are looked up in all models whose default
+/// namespace URI matches the URI the prefix is mapped to in the `namespace`
+/// block of the referencing model.
+pub(crate) struct Resolver<'a> {
+    documents: Vec<&'a Value>,
+    namespace_index: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> Resolver<'a> {
+    pub(crate) fn new(documents: Vec<&'a Value>) -> Self {
+        let mut namespace_index: HashMap<&'a str, Vec<usize>> = HashMap::new();
+
+        for (index, document) in documents.iter().enumerate() {
+            if let Some(namespace_uri) = default_namespace_uri(document) {
+                namespace_index
+                    .entry(namespace_uri)
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        Resolver {
+            documents,
+            namespace_index,
+        }
+    }
+
+    pub(crate) fn resolve_document(&self, document: usize) -> Result<Value> {
+        self.resolve_value(document, self.documents[document], "", &mut Vec::new())
+    }
+
+    fn resolve_value(
+        &self,
+        document: usize,
+        value: &Value,
+        location: &str,
+        reference_stack: &mut Vec<(usize, String)>,
+    ) -> Result<Value> {
+        match value {
+            Value::Object(object) => match object.get(SDF_REF) {
+                Some(Value::String(sdf_ref)) => {
+                    self.resolve_reference(document, object, sdf_ref, location, reference_stack)
+                }
+                Some(_) => Err(ConversionError::Reference(format!(
+                    "sdfRef at \"{}\" must be a string!",
+                    location
+                ))),
+                None => Ok(Value::Object(self.resolve_members(
+                    document,
+                    object,
+                    location,
+                    reference_stack,
+                )?)),
+            },
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let item_location = format!("{}/{}", location, index);
+                    self.resolve_value(document, item, &item_location, reference_stack)
+                })
+                .collect::<Result<Vec<Value>>>()
+                .map(Value::Array),
+            _ => Ok(value.clone()),
+        }
+    }
+
+    fn resolve_members(
+        &self,
+        document: usize,
+        object: &Map<String, Value>,
+        location: &str,
+        reference_stack: &mut Vec<(usize, String)>,
+    ) -> Result<Map<String, Value>> {
+        let mut resolved_object = Map::new();
+
+        for (key, member) in object {
+            if key == SDF_REF {
+                continue;
+            }
+
+            let member_location = member_pointer(location, key);
+            let resolved_member =
+                self.resolve_value(document, member, &member_location, reference_stack)?;
+            resolved_object.insert(key.clone(), resolved_member);
+        }
+
+        Ok(resolved_object)
+    }
+
+    fn resolve_reference(
+        &self,
+        document: usize,
+        object: &Map<String, Value>,
+        sdf_ref: &str,
+        location: &str,
+        reference_stack: &mut Vec<(usize, String)>,
+    ) -> Result<Value> {
+        let (target_document, pointer, target) = self.find_target(document, sdf_ref, location)?;
+        let reference = (target_document, pointer.to_string());
+
+        if reference_stack.contains(&reference) {
+            return Err(ConversionError::Reference(format!(
+                "Cyclic sdfRef \"{}\" detected at \"{}\"!",
+                sdf_ref, location
+            )));
+        }
+
+        reference_stack.push(reference);
+        let mut resolved_target =
+            self.resolve_value(target_document, target, pointer, reference_stack)?;
+        reference_stack.pop();
+
+        let patch = self.resolve_members(document, object, location, reference_stack)?;
+        merge_patch(&mut resolved_target, &Value::Object(patch));
+
+        Ok(resolved_target)
+    }
+
+    /// Determines the model and the definition an `sdfRef` points to.
+    fn find_target<'b>(
+        &self,
+        document: usize,
+        sdf_ref: &'b str,
+        location: &str,
+    ) -> Result<(usize, &'b str, &'a Value)> {
+        let (prefix, pointer) = sdf_ref.split_once('#').ok_or_else(|| {
+            ConversionError::Reference(format!(
+                "sdfRef \"{}\" at \"{}\" is not a JSON pointer!",
+                sdf_ref, location
+            ))
+        })?;
+
+        let mut candidates: Vec<usize> = Vec::new();
+        let namespace_uri = if prefix.is_empty() {
+            candidates.push(document);
+            default_namespace_uri(self.documents[document])
+        } else {
+            let namespace_uri = prefix
+                .strip_suffix(':')
+                .and_then(|prefix| self.documents[document].get("namespace")?.get(prefix))
+                .and_then(Value::as_str)
+                .ok_or_else(|| {
+                    ConversionError::Reference(format!(
+                        "Namespace of sdfRef \"{}\" at \"{}\" is not defined!",
+                        sdf_ref, location
+                    ))
+                })?;
+            Some(namespace_uri)
+        };
+
+        if let Some(indexed_documents) = namespace_uri.and_then(|x| self.namespace_index.get(x)) {
+            candidates.extend(indexed_documents);
+        }
+
+        candidates
+            .into_iter()
+            .find_map(|candidate| {
+                self.documents[candidate]
+                    .pointer(pointer)
+                    .map(|target| (candidate, pointer, target))
+            })
+            .ok_or_else(|| {
+                ConversionError::Reference(format!(
+                    "sdfRef \"{}\" at \"{}\" does not point to an existing definition!",
+                    sdf_ref, location
+                ))
+            })
+    }
+}
+
+/// Applies a JSON Merge Patch as defined in
+/// [RFC 7396](https://www.rfc-editor.org/rfc/rfc7396) to a target value.
+pub(crate) fn merge_patch(target: &mut Value, patch: &Value) {
+    match patch {
+        Value::Object(patch_object) => {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+
+            if let Value::Object(target_object) = target {
+                for (key, value) in patch_object {
+                    if value.is_null() {
+                        target_object.shift_remove(key);
+                    } else {
+                        merge_patch(
+                            target_object.entry(key.clone()).or_insert(Value::Null),
+                            value,
+                        );
+                    }
+                }
+            }
+        }
+        _ => *target = patch.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_patch_test() {
+        let mut target = json!({"a": "b", "c": {"d": "e", "f": "g"}});
+        let patch = json!({"a": "z", "c": {"f": null}});
+        merge_patch(&mut target, &patch);
+        assert_eq!(target, json!({"a": "z", "c": {"d": "e"}}));
+
+        let mut target = json!(["a", "b"]);
+        let patch = json!({"a": "c"});
+        merge_patch(&mut target, &patch);
+        assert_eq!(target, json!({"a": "c"}));
+
+        let mut target = json!({"a": "b"});
+        let patch = json!(["c"]);
+        merge_patch(&mut target, &patch);
+        assert_eq!(target, json!(["c"]));
+    }
+
+    #[test]
+    fn resolve_nested_sdf_refs_test() {
+        let sdf_model = json!({
+            "sdfData": {
+                "foo": {
+                    "type": "number",
+                    "minimum": 0,
+                    "maximum": 5
+                },
+                "bar": {
+                    "sdfRef": "#/sdfData/foo",
+                    "maximum": 10
+                }
+            },
+            "sdfProperty": {
+                "baz": {
+                    "sdfRef": "#/sdfData/bar",
+                    "minimum": null
+                }
+            }
+        });
+
+        let resolved_model = resolve_sdf_refs(&sdf_model).unwrap();
+
+        assert_eq!(
+            resolved_model["sdfProperty"]["baz"],
+            json!({"type": "number", "maximum": 10})
+        );
+    }
+
+    #[test]
+    fn resolve_sdf_refs_escaped_pointer_test() {
+        let sdf_model = json!({
+            "sdfData": {
+                "foo/bar": {
+                    "type": "string"
+                }
+            },
+            "sdfProperty": {
+                "baz": {
+                    "sdfRef": "#/sdfData/foo~1bar"
+                }
+            }
+        });
+
+        let resolved_model = resolve_sdf_refs(&sdf_model).unwrap();
+
+        assert_eq!(
+            resolved_model["sdfProperty"]["baz"],
+            json!({"type": "string"})
+        );
+    }
+
+    #[test]
+    fn resolve_dangling_sdf_ref_test() {
+        let sdf_model = json!({
+            "sdfProperty": {
+                "foo": {
+                    "sdfRef": "#/sdfData/bar"
+                }
+            }
+        });
+
+        assert_eq!(
+            resolve_sdf_refs(&sdf_model).unwrap_err().to_string(),
+            "sdfRef \"#/sdfData/bar\" at \"/sdfProperty/foo\" does not point to an existing definition!"
+        );
+    }
+
+    #[test]
+    fn resolve_cyclic_sdf_ref_test() {
+        let sdf_model = json!({
+            "sdfData": {
+                "foo": {
+                    "sdfRef": "#/sdfData/bar"
+                },
+                "bar": {
+                    "sdfRef": "#/sdfData/foo"
+                }
+            }
+        });
+
+        assert!(resolve_sdf_refs(&sdf_model)
+            .unwrap_err()
+            .to_string()
+            .starts_with("Cyclic sdfRef"));
+    }
+
+    #[test]
+    fn resolve_own_namespace_sdf_ref_test() {
+        let sdf_model = json!({
+            "namespace": {
+                "foo": "https://example.org/foo"
+            },
+            "defaultNamespace": "foo",
+            "sdfProperty": {
+                "bar": {
+                    "sdfRef": "foo:#/sdfData/baz"
+                }
+            },
+            "sdfData": {
+                "baz": {
+                    "type": "boolean"
+                }
+            }
+        });
+
+        let resolved_model = resolve_sdf_refs(&sdf_model).unwrap();
+
+        assert_eq!(
+            resolved_model["sdfProperty"]["bar"],
+            json!({"type": "boolean"})
+        );
+    }
+
+    #[test]
+    fn resolve_undefined_namespace_sdf_ref_test() {
+        let sdf_model = json!({
+            "sdfProperty": {
+                "foo": {
+                    "sdfRef": "foo:#/sdfData/bar"
+                }
+            }
+        });
+
+        assert_eq!(
+            resolve_sdf_refs(&sdf_model).unwrap_err().to_string(),
+            "Namespace of sdfRef \"foo:#/sdfData/bar\" at \"/sdfProperty/foo\" is not defined!"
+        );
+    }
+}
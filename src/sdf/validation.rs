@@ -0,0 +1,1478 @@
+//! Validation of SDF models against the rules of the SDF specification.
+//!
+//! The raw JSON representation of a model is first checked against the
+//! structure of the CDDL definitions, i.e., the qualities each kind of
+//! definition may contain and the types of their values. As deserialization
+//! silently ignores unknown members and drops data qualities whose values do
+//! not match their `type`, this cannot be left to the typed definitions.
+//! The validation additionally checks the semantic constraints that the CDDL
+//! definitions cannot express, such as references that have to point to
+//! existing definitions of a suitable kind or ranges that have to be
+//! non-empty.
+
+use super::definitions::{
+    ActionQualities, ArrayItems, CommonQualities, DataQualities, EventQualities,
+    NumberTypeQualities, ObjectQualities, RegularTypes, SDFModel, ThingQualities, TypedQualities,
+    Types,
+};
+use crate::error::from_json_value;
+use crate::json_pointer::member_pointer;
+use crate::ConversionError;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The keywords that identify the kind of a definition within a JSON pointer.
+const DEFINITION_KEYWORDS: [&str; 13] = [
+    "sdfThing",
+    "sdfProduct",
+    "sdfObject",
+    "sdfProperty",
+    "sdfAction",
+    "sdfEvent",
+    "sdfData",
+    "sdfInputData",
+    "sdfOutputData",
+    "sdfChoice",
+    "properties",
+    "items",
+    "sdfRequired",
+];
+
+/// The kinds of definitions an `sdfRequired` entry may point to.
+const DECLARATION_KEYWORDS: [&str; 5] = [
+    "sdfProperty",
+    "sdfAction",
+    "sdfEvent",
+    "sdfObject",
+    "sdfThing",
+];
+
+/// The kinds of definitions whose names have to be unique within the
+/// definition containing them, regardless of their kind.
+const NAMED_KEYWORDS: [&str; 6] = [
+    "sdfThing",
+    "sdfProduct",
+    "sdfObject",
+    "sdfProperty",
+    "sdfAction",
+    "sdfEvent",
+];
+
+/// The kinds of definitions describing data, which may refer to each other.
+const DATA_KEYWORDS: [&str; 7] = [
+    "sdfProperty",
+    "sdfData",
+    "sdfInputData",
+    "sdfOutputData",
+    "sdfChoice",
+    "properties",
+    "items",
+];
+
+/// The kinds of definitions (and other blocks) of an SDF model, which differ in
+/// the qualities they may contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Model,
+    Info,
+    Thing,
+    Object,
+    Action,
+    Event,
+    Data,
+}
+
+/// The values a quality of an SDF model may have according to the CDDL
+/// definitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rule {
+    Any,
+    Text,
+    Boolean,
+    Number,
+    UnsignedInteger,
+    TextArray,
+    NonEmptyArray,
+    OneOf(&'static [&'static str]),
+    /// A map of names to text values, such as the namespace block.
+    TextMap,
+    /// A single block, such as `sdfInputData`.
+    Block(Block),
+    /// A map of names to blocks, such as `sdfProperty`.
+    Named(Block),
+    /// A data definition or an array of data definitions.
+    Items,
+}
+
+const DATA_TYPES: [&str; 6] = ["number", "string", "boolean", "integer", "array", "object"];
+const SDF_TYPES: [&str; 2] = ["byte-string", "unix-time"];
+
+const COMMON_QUALITIES: [(&str, Rule); 5] = [
+    ("description", Rule::Text),
+    ("label", Rule::Text),
+    ("comment", Rule::Text),
+    ("sdfRef", Rule::Text),
+    ("sdfRequired", Rule::TextArray),
+];
+
+const MODEL_QUALITIES: [(&str, Rule); 10] = [
+    ("info", Rule::Block(Block::Info)),
+    ("namespace", Rule::TextMap),
+    ("defaultNamespace", Rule::Text),
+    ("sdfThing", Rule::Named(Block::Thing)),
+    ("sdfProduct", Rule::Named(Block::Thing)),
+    ("sdfObject", Rule::Named(Block::Object)),
+    ("sdfProperty", Rule::Named(Block::Data)),
+    ("sdfAction", Rule::Named(Block::Action)),
+    ("sdfEvent", Rule::Named(Block::Event)),
+    ("sdfData", Rule::Named(Block::Data)),
+];
+
+const INFO_QUALITIES: [(&str, Rule); 7] = [
+    ("title", Rule::Text),
+    ("description", Rule::Text),
+    ("version", Rule::Text),
+    ("modified", Rule::Text),
+    ("copyright", Rule::Text),
+    ("license", Rule::Text),
+    ("features", Rule::TextArray),
+];
+
+const THING_QUALITIES: [(&str, Rule); 2] = [
+    ("sdfObject", Rule::Named(Block::Object)),
+    ("sdfThing", Rule::Named(Block::Thing)),
+];
+
+const OBJECT_QUALITIES: [(&str, Rule); 4] = [
+    ("sdfProperty", Rule::Named(Block::Data)),
+    ("sdfAction", Rule::Named(Block::Action)),
+    ("sdfEvent", Rule::Named(Block::Event)),
+    ("sdfData", Rule::Named(Block::Data)),
+];
+
+const ACTION_QUALITIES: [(&str, Rule); 3] = [
+    ("sdfInputData", Rule::Block(Block::Data)),
+    ("sdfOutputData", Rule::Block(Block::Data)),
+    ("sdfData", Rule::Named(Block::Data)),
+];
+
+const EVENT_QUALITIES: [(&str, Rule); 2] = [
+    ("sdfOutputData", Rule::Block(Block::Data)),
+    ("sdfData", Rule::Named(Block::Data)),
+];
+
+const DATA_QUALITIES: [(&str, Rule); 27] = [
+    ("type", Rule::OneOf(&DATA_TYPES)),
+    ("sdfChoice", Rule::Named(Block::Data)),
+    ("const", Rule::Any),
+    ("default", Rule::Any),
+    ("enum", Rule::NonEmptyArray),
+    ("minimum", Rule::Number),
+    ("maximum", Rule::Number),
+    ("exclusiveMinimum", Rule::Number),
+    ("exclusiveMaximum", Rule::Number),
+    ("multipleOf", Rule::Number),
+    ("minLength", Rule::UnsignedInteger),
+    ("maxLength", Rule::UnsignedInteger),
+    ("pattern", Rule::Text),
+    ("format", Rule::Text),
+    ("minItems", Rule::UnsignedInteger),
+    ("maxItems", Rule::UnsignedInteger),
+    ("uniqueItems", Rule::Boolean),
+    ("items", Rule::Items),
+    ("properties", Rule::Named(Block::Data)),
+    ("required", Rule::TextArray),
+    ("unit", Rule::Text),
+    ("observable", Rule::Boolean),
+    ("readable", Rule::Boolean),
+    ("writable", Rule::Boolean),
+    ("nullable", Rule::Boolean),
+    ("sdfType", Rule::OneOf(&SDF_TYPES)),
+    ("contentFormat", Rule::Text),
+];
+
+/// The data qualities that have to be integers if the `type` is `integer`.
+const INTEGER_QUALITIES: [&str; 5] = [
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+];
+
+/// A violation of a rule of the SDF specification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    /// A JSON pointer to the offending member of the model.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(pointer: &str, message: String) -> Self {
+        Violation {
+            pointer: pointer.to_string(),
+            message,
+        }
+    }
+}
+
+/// Validates the JSON representation of an SDF model, returning all violations
+/// of the rules of the SDF specification that have been found.
+///
+/// Compared to [`validate`], the model is first checked against the CDDL
+/// definitions of SDF. This way, the validation also reports
+///
+/// - members with a name starting with `sdf` that are not an SDF quality of
+///   the enclosing definition (names without this prefix are extensions),
+/// - qualities with values of the wrong type (e.g., a `minimum` that is not a
+///   number) or unknown values (e.g., an unknown `type`), and
+/// - `const`, `default`, and `enum` values that do not match the `type`.
+///
+/// The semantic constraints are only checked for models that match the CDDL
+/// definitions.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::sdf::validate_json;
+/// use serde_json::json;
+///
+/// let sdf_model = json!({
+///     "sdfObjekt": {},
+///     "sdfProperty": {
+///         "foo": {
+///             "type": "number",
+///             "minimum": "abc"
+///         }
+///     }
+/// });
+///
+/// let violations = validate_json(&sdf_model);
+///
+/// assert_eq!(violations.len(), 2);
+/// assert_eq!(violations[0].pointer, "/sdfObjekt");
+/// assert_eq!(violations[1].pointer, "/sdfProperty/foo/minimum");
+/// ```
+pub fn validate_json(sdf_model: &Value) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    validate_block(&mut violations, sdf_model, "", Block::Model);
+
+    match from_json_value::<SDFModel>(sdf_model.clone()) {
+        Ok(sdf_model) => violations.append(&mut validate(&sdf_model)),
+        Err(error) if violations.is_empty() => violations.push(match error {
+            ConversionError::Parse {
+                message, pointer, ..
+            } => Violation::new(pointer.as_deref().unwrap_or(""), message),
+            error => Violation::new("", error.to_string()),
+        }),
+        Err(_) => (),
+    }
+
+    violations
+}
+
+fn block_qualities(block: Block) -> Vec<(&'static str, Rule)> {
+    let (qualities, is_definition): (&[(&str, Rule)], bool) = match block {
+        Block::Model => (&MODEL_QUALITIES, false),
+        Block::Info => (&INFO_QUALITIES, false),
+        Block::Thing => (&THING_QUALITIES, true),
+        Block::Object => (&OBJECT_QUALITIES, true),
+        Block::Action => (&ACTION_QUALITIES, true),
+        Block::Event => (&EVENT_QUALITIES, true),
+        Block::Data => (&DATA_QUALITIES, true),
+    };
+
+    let common_qualities: &[(&str, Rule)] = if is_definition {
+        &COMMON_QUALITIES
+    } else {
+        &[]
+    };
+    common_qualities.iter().chain(qualities).copied().collect()
+}
+
+/// Checks the members of a block against the qualities it may contain.
+fn validate_block(violations: &mut Vec<Violation>, value: &Value, pointer: &str, block: Block) {
+    let members = match value.as_object() {
+        Some(members) => members,
+        None => {
+            violations.push(Violation::new(
+                pointer,
+                "Expected a JSON object".to_string(),
+            ));
+            return;
+        }
+    };
+
+    let qualities = block_qualities(block);
+    for (name, member) in members {
+        let member_pointer = member_pointer(pointer, name);
+        match qualities.iter().find(|(quality, _)| quality == name) {
+            Some((_, rule)) => validate_quality(violations, member, &member_pointer, name, *rule),
+            None if name.starts_with("sdf") => violations.push(Violation::new(
+                &member_pointer,
+                format!("Unknown SDF quality \"{}\"", name),
+            )),
+            None => (),
+        }
+    }
+
+    if block == Block::Data {
+        validate_typed_values(violations, members, pointer);
+    }
+}
+
+fn validate_quality(
+    violations: &mut Vec<Violation>,
+    value: &Value,
+    pointer: &str,
+    name: &str,
+    rule: Rule,
+) {
+    let expectation = match rule {
+        Rule::Any => return,
+        Rule::Text if !value.is_string() => "a string".to_string(),
+        Rule::Boolean if !value.is_boolean() => "a boolean".to_string(),
+        Rule::Number if !value.is_number() => "a number".to_string(),
+        Rule::UnsignedInteger if value.as_u64().is_none_or(|x| x > u32::MAX.into()) => {
+            "a non-negative integer".to_string()
+        }
+        Rule::TextArray
+            if !value
+                .as_array()
+                .is_some_and(|x| x.iter().all(Value::is_string)) =>
+        {
+            "an array of strings".to_string()
+        }
+        Rule::NonEmptyArray if value.as_array().is_none_or(|x| x.is_empty()) => {
+            "a non-empty array".to_string()
+        }
+        Rule::OneOf(values) if !value.as_str().is_some_and(|x| values.contains(&x)) => {
+            let values: Vec<String> = values.iter().map(|x| format!("\"{}\"", x)).collect();
+            format!("one of {}", values.join(", "))
+        }
+        Rule::TextMap
+            if !value
+                .as_object()
+                .is_some_and(|x| x.values().all(Value::is_string)) =>
+        {
+            "a map of strings".to_string()
+        }
+        Rule::Block(block) => return validate_block(violations, value, pointer, block),
+        Rule::Named(block) => match value.as_object() {
+            Some(definitions) => {
+                for (definition_name, definition) in definitions {
+                    let definition_pointer = member_pointer(pointer, definition_name);
+                    validate_block(violations, definition, &definition_pointer, block);
+                }
+                return;
+            }
+            None => "a map of definitions".to_string(),
+        },
+        Rule::Items => match value {
+            Value::Object(_) => return validate_block(violations, value, pointer, Block::Data),
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let item_pointer = format!("{}/{}", pointer, index);
+                    validate_block(violations, item, &item_pointer, Block::Data);
+                }
+                return;
+            }
+            _ => "a data definition or an array of data definitions".to_string(),
+        },
+        _ => return,
+    };
+
+    violations.push(Violation::new(
+        pointer,
+        format!("{} has to be {}", name, expectation),
+    ));
+}
+
+fn matches_type(value: &Value, data_type: &str) -> bool {
+    match data_type {
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+/// Checks that the values of a data definition match its `type`.
+fn validate_typed_values(
+    violations: &mut Vec<Violation>,
+    members: &serde_json::Map<String, Value>,
+    pointer: &str,
+) {
+    let data_type = match members.get("type").and_then(Value::as_str) {
+        Some(data_type) => data_type,
+        None => return,
+    };
+
+    for quality in ["const", "default"] {
+        if members
+            .get(quality)
+            .is_some_and(|x| !matches_type(x, data_type))
+        {
+            violations.push(Violation::new(
+                &member_pointer(pointer, quality),
+                format!(
+                    "The {} value does not match the type \"{}\"",
+                    quality, data_type
+                ),
+            ));
+        }
+    }
+    for (index, value) in members
+        .get("enum")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        if !matches_type(value, data_type) {
+            violations.push(Violation::new(
+                &format!("{}/enum/{}", pointer, index),
+                format!("The enum value does not match the type \"{}\"", data_type),
+            ));
+        }
+    }
+
+    if data_type == "integer" {
+        for quality in INTEGER_QUALITIES {
+            if members
+                .get(quality)
+                .is_some_and(|x| x.is_number() && !matches_type(x, data_type))
+            {
+                violations.push(Violation::new(
+                    &member_pointer(pointer, quality),
+                    format!("{} has to be an integer", quality),
+                ));
+            }
+        }
+    }
+}
+
+/// The model under validation, which references are resolved against.
+struct Context<'a> {
+    sdf_model: &'a SDFModel,
+    sdf_model_json: Value,
+    violations: Vec<Violation>,
+}
+
+impl Context<'_> {
+    fn report(&mut self, pointer: &str, message: String) {
+        self.violations.push(Violation::new(pointer, message));
+    }
+}
+
+/// Validates an SDF model, returning all violations of the rules of the SDF
+/// specification that have been found.
+///
+/// In particular, the validation reports
+///
+/// - undeclared default namespaces and namespace URIs bound to more than one
+///   prefix,
+/// - definitions of different kinds sharing a name within the same
+///   definition (e.g., an sdfProperty and an sdfAction of an sdfObject),
+/// - `sdfRef`s that cannot be resolved or point to a definition of a
+///   different kind (e.g., an `sdfProperty` referring to an `sdfAction`),
+/// - `sdfRequired` entries that do not point to a declaration within the
+///   definition they are part of,
+/// - empty ranges (e.g., a `minimum` greater than the `maximum`) and
+///   non-positive `multipleOf` values, and
+/// - `const` and `default` values that are not part of the `enum` or outside
+///   of the declared range.
+///
+/// Namespaced references to other models are only checked for a declared
+/// namespace prefix.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::sdf::definitions::SDFModel;
+/// use sdf_wot_converter::sdf::validate;
+/// use serde_json::json;
+///
+/// let sdf_model: SDFModel = serde_json::from_value(json!({
+///     "sdfProperty": {
+///         "foo": {
+///             "type": "integer",
+///             "minimum": 5,
+///             "maximum": 3
+///         }
+///     }
+/// }))
+/// .unwrap();
+///
+/// let violations = validate(&sdf_model);
+///
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].pointer, "/sdfProperty/foo/minimum");
+/// ```
+pub fn validate(sdf_model: &SDFModel) -> Vec<Violation> {
+    let mut context = Context {
+        sdf_model,
+        sdf_model_json: serde_json::to_value(sdf_model).unwrap_or_default(),
+        violations: Vec::new(),
+    };
+
+    validate_namespaces(&mut context);
+    validate_things(&mut context, &sdf_model.sdf_thing, "", "sdfThing");
+    validate_things(&mut context, &sdf_model.sdf_product, "", "sdfProduct");
+    validate_objects(&mut context, &sdf_model.sdf_object, "");
+    validate_data_definitions(&mut context, &sdf_model.sdf_property, "", "sdfProperty");
+    validate_actions(&mut context, &sdf_model.sdf_action, "");
+    validate_events(&mut context, &sdf_model.sdf_event, "");
+    validate_data_definitions(&mut context, &sdf_model.sdf_data, "", "sdfData");
+
+    context.violations
+}
+
+fn validate_namespaces(context: &mut Context) {
+    let sdf_model = context.sdf_model;
+
+    if let Some(default_namespace) = &sdf_model.default_namespace {
+        let is_declared = sdf_model
+            .namespace
+            .as_ref()
+            .is_some_and(|x| x.contains_key(default_namespace));
+        if !is_declared {
+            context.report(
+                "/defaultNamespace",
+                format!(
+                    "Default namespace \"{}\" is not declared in the namespace block",
+                    default_namespace
+                ),
+            );
+        }
+    }
+
+    let mut prefixes: HashMap<&String, &String> = HashMap::new();
+    for (prefix, uri) in sdf_model.namespace.iter().flatten() {
+        match prefixes.get(uri) {
+            Some(other_prefix) => context.report(
+                &member_pointer("/namespace", prefix),
+                format!(
+                    "Namespace \"{}\" is already bound to the prefix \"{}\"",
+                    uri, other_prefix
+                ),
+            ),
+            None => {
+                prefixes.insert(uri, prefix);
+            }
+        }
+    }
+
+    validate_names(&mut context.violations, &context.sdf_model_json, "");
+}
+
+/// Reports definitions that share their name with another definition of a
+/// different kind within the same definition, e.g., an sdfProperty and an
+/// sdfAction of an sdfObject that are both called `state`. At the top level,
+/// these names would clash within the default namespace of the model.
+fn validate_names(violations: &mut Vec<Violation>, definition: &Value, pointer: &str) {
+    let mut names: HashMap<&str, String> = HashMap::new();
+
+    for keyword in NAMED_KEYWORDS {
+        let keyword_pointer = member_pointer(pointer, keyword);
+        let definitions = definition.get(keyword).and_then(Value::as_object);
+
+        for (name, nested_definition) in definitions.into_iter().flatten() {
+            let definition_pointer = member_pointer(&keyword_pointer, name);
+            match names.get(name.as_str()) {
+                Some(other_pointer) => violations.push(Violation::new(
+                    &definition_pointer,
+                    format!(
+                        "The name \"{}\" is already used by the definition at \"{}\"",
+                        name, other_pointer
+                    ),
+                )),
+                None => {
+                    names.insert(name, definition_pointer.clone());
+                }
+            }
+            validate_names(violations, nested_definition, &definition_pointer);
+        }
+    }
+}
+
+/// Splits a reference into its namespace prefix (if any) and its JSON
+/// pointer, returning `None` for malformed references.
+fn split_reference(reference: &str) -> Option<(Option<&str>, &str)> {
+    let (prefix, pointer) = reference.split_once('#')?;
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+
+    match prefix {
+        "" => Some((None, pointer)),
+        prefix => prefix
+            .strip_suffix(':')
+            .filter(|x| !x.is_empty())
+            .map(|x| (Some(x), pointer)),
+    }
+}
+
+/// Determines the kind of the definition a JSON pointer points to.
+fn definition_kind(pointer: &str) -> Option<&str> {
+    pointer
+        .split('/')
+        .rev()
+        .find(|x| DEFINITION_KEYWORDS.contains(x))
+}
+
+/// Distinguishes pointers to named definitions (e.g., `/sdfProperty/foo`)
+/// from pointers to keywords (e.g., `/sdfProperty`).
+fn is_named_definition(pointer: &str) -> bool {
+    pointer
+        .rsplit('/')
+        .next()
+        .is_some_and(|x| !DEFINITION_KEYWORDS.contains(&x))
+}
+
+/// Resolves a reference within the model under validation, returning the
+/// JSON pointer of its target. Reports a violation and returns `None` if the
+/// reference is malformed, uses an undeclared namespace, or cannot be
+/// resolved. References to other models yield `None` without a violation.
+fn resolve_reference(
+    context: &mut Context,
+    reference: &str,
+    pointer: &str,
+    quality: &str,
+) -> Option<String> {
+    let (prefix, target_pointer) = match split_reference(reference) {
+        Some(reference) => reference,
+        None => {
+            context.report(
+                pointer,
+                format!("{} \"{}\" is not a valid reference", quality, reference),
+            );
+            return None;
+        }
+    };
+
+    if let Some(prefix) = prefix {
+        let sdf_model = context.sdf_model;
+        let namespaces = sdf_model.namespace.as_ref();
+        let uri = match namespaces.and_then(|x| x.get(prefix)) {
+            Some(uri) => uri,
+            None => {
+                context.report(
+                    pointer,
+                    format!(
+                        "Namespace \"{}\" of {} \"{}\" is not declared",
+                        prefix, quality, reference
+                    ),
+                );
+                return None;
+            }
+        };
+        let default_uri = sdf_model
+            .default_namespace
+            .as_ref()
+            .and_then(|x| namespaces?.get(x));
+        if default_uri != Some(uri) {
+            return None;
+        }
+    }
+
+    if context.sdf_model_json.pointer(target_pointer).is_none() {
+        context.report(
+            pointer,
+            format!(
+                "{} \"{}\" does not point to an existing definition",
+                quality, reference
+            ),
+        );
+        return None;
+    }
+
+    Some(target_pointer.to_string())
+}
+
+/// Checks that an `sdfRef` points to a definition of a kind compatible with
+/// the `keyword` of the referencing definition.
+fn validate_sdf_ref(context: &mut Context, sdf_ref: &str, pointer: &str, keyword: &str) {
+    let ref_pointer = member_pointer(pointer, "sdfRef");
+    let target = match resolve_reference(context, sdf_ref, &ref_pointer, "sdfRef") {
+        Some(target) => target,
+        None => return,
+    };
+
+    let target_kind = definition_kind(&target);
+    let is_compatible = match keyword {
+        "sdfThing" | "sdfProduct" => {
+            matches!(target_kind, Some("sdfThing") | Some("sdfProduct"))
+        }
+        "sdfObject" | "sdfAction" | "sdfEvent" => target_kind == Some(keyword),
+        _ => target_kind.is_some_and(|x| DATA_KEYWORDS.contains(&x)),
+    };
+
+    if !is_compatible {
+        context.report(
+            &ref_pointer,
+            format!(
+                "sdfRef \"{}\" of a {} definition points to a {} definition",
+                sdf_ref,
+                keyword,
+                target_kind.unwrap_or("non-definition")
+            ),
+        );
+    }
+}
+
+/// Checks that the entries of an `sdfRequired` quality point to declarations
+/// within the definition they are part of.
+fn validate_sdf_required(context: &mut Context, sdf_required: &[String], pointer: &str) {
+    let required_pointer = member_pointer(pointer, "sdfRequired");
+
+    for (index, entry) in sdf_required.iter().enumerate() {
+        let entry_pointer = format!("{}/{}", required_pointer, index);
+        let target = match resolve_reference(context, entry, &entry_pointer, "sdfRequired entry") {
+            Some(target) => target,
+            None => continue,
+        };
+
+        let is_declaration = definition_kind(&target)
+            .is_some_and(|x| DECLARATION_KEYWORDS.contains(&x) && is_named_definition(&target));
+        if !is_declaration {
+            context.report(
+                &entry_pointer,
+                format!(
+                    "sdfRequired entry \"{}\" does not point to a declaration",
+                    entry
+                ),
+            );
+        } else if target != pointer && !target.starts_with(&format!("{}/", pointer)) {
+            context.report(
+                &entry_pointer,
+                format!(
+                    "sdfRequired entry \"{}\" points outside of the enclosing definition",
+                    entry
+                ),
+            );
+        }
+    }
+}
+
+fn validate_common_qualities(
+    context: &mut Context,
+    common_qualities: &CommonQualities,
+    pointer: &str,
+    keyword: &str,
+) {
+    if let Some(sdf_ref) = &common_qualities.sdf_ref {
+        validate_sdf_ref(context, sdf_ref, pointer, keyword);
+    }
+    if let Some(sdf_required) = &common_qualities.sdf_required {
+        validate_sdf_required(context, sdf_required, pointer);
+    }
+}
+
+fn validate_things(
+    context: &mut Context,
+    things: &Option<IndexMap<String, ThingQualities>>,
+    pointer: &str,
+    keyword: &str,
+) {
+    let things_pointer = member_pointer(pointer, keyword);
+    for (name, thing) in things.iter().flatten() {
+        let thing_pointer = member_pointer(&things_pointer, name);
+        validate_common_qualities(context, &thing.common_qualities, &thing_pointer, keyword);
+        validate_things(context, &thing.sdf_thing, &thing_pointer, "sdfThing");
+        validate_objects(context, &thing.sdf_object, &thing_pointer);
+    }
+}
+
+fn validate_objects(
+    context: &mut Context,
+    objects: &Option<IndexMap<String, ObjectQualities>>,
+    pointer: &str,
+) {
+    let objects_pointer = member_pointer(pointer, "sdfObject");
+    for (name, object) in objects.iter().flatten() {
+        let object_pointer = member_pointer(&objects_pointer, name);
+        validate_common_qualities(
+            context,
+            &object.common_qualities,
+            &object_pointer,
+            "sdfObject",
+        );
+        validate_data_definitions(
+            context,
+            &object.sdf_property,
+            &object_pointer,
+            "sdfProperty",
+        );
+        validate_actions(context, &object.sdf_action, &object_pointer);
+        validate_events(context, &object.sdf_event, &object_pointer);
+        validate_data_definitions(context, &object.sdf_data, &object_pointer, "sdfData");
+    }
+}
+
+fn validate_actions(
+    context: &mut Context,
+    actions: &Option<IndexMap<String, ActionQualities>>,
+    pointer: &str,
+) {
+    let actions_pointer = member_pointer(pointer, "sdfAction");
+    for (name, action) in actions.iter().flatten() {
+        let action_pointer = member_pointer(&actions_pointer, name);
+        validate_common_qualities(
+            context,
+            &action.common_qualities,
+            &action_pointer,
+            "sdfAction",
+        );
+        if let Some(input_data) = &action.sdf_input_data {
+            let input_pointer = member_pointer(&action_pointer, "sdfInputData");
+            validate_data(context, input_data, &input_pointer, "sdfInputData");
+        }
+        if let Some(output_data) = &action.sdf_output_data {
+            let output_pointer = member_pointer(&action_pointer, "sdfOutputData");
+            validate_data(context, output_data, &output_pointer, "sdfOutputData");
+        }
+        validate_data_definitions(context, &action.sdf_data, &action_pointer, "sdfData");
+    }
+}
+
+fn validate_events(
+    context: &mut Context,
+    events: &Option<IndexMap<String, EventQualities>>,
+    pointer: &str,
+) {
+    let events_pointer = member_pointer(pointer, "sdfEvent");
+    for (name, event) in events.iter().flatten() {
+        let event_pointer = member_pointer(&events_pointer, name);
+        validate_common_qualities(context, &event.common_qualities, &event_pointer, "sdfEvent");
+        if let Some(output_data) = &event.sdf_output_data {
+            let output_pointer = member_pointer(&event_pointer, "sdfOutputData");
+            validate_data(context, output_data, &output_pointer, "sdfOutputData");
+        }
+        validate_data_definitions(context, &event.sdf_data, &event_pointer, "sdfData");
+    }
+}
+
+fn validate_data_definitions(
+    context: &mut Context,
+    definitions: &Option<IndexMap<String, DataQualities>>,
+    pointer: &str,
+    keyword: &str,
+) {
+    let definitions_pointer = member_pointer(pointer, keyword);
+    for (name, data) in definitions.iter().flatten() {
+        validate_data(
+            context,
+            data,
+            &member_pointer(&definitions_pointer, name),
+            keyword,
+        );
+    }
+}
+
+fn validate_data(context: &mut Context, data: &DataQualities, pointer: &str, keyword: &str) {
+    validate_common_qualities(context, &data.common_qualities, pointer, keyword);
+
+    match &data.jsonschema {
+        Some(Types::SdfChoice(choice)) => {
+            let choice_pointer = member_pointer(pointer, "sdfChoice");
+            for (name, alternative) in &choice.sdf_choice {
+                let alternative_pointer = member_pointer(&choice_pointer, name);
+                validate_data(context, alternative, &alternative_pointer, "sdfChoice");
+            }
+        }
+        Some(Types::Type(RegularTypes::Number(qualities))) => {
+            validate_number_qualities(context, qualities, pointer)
+        }
+        Some(Types::Type(RegularTypes::Integer(qualities))) => {
+            validate_number_qualities(context, qualities, pointer)
+        }
+        Some(Types::Type(RegularTypes::Boolean(qualities))) => {
+            validate_enum(context, qualities, pointer)
+        }
+        Some(Types::Type(RegularTypes::String(qualities))) => {
+            validate_enum(context, &qualities.common_qualities, pointer);
+            validate_bounds(
+                context,
+                pointer,
+                ("minLength", qualities.min_length),
+                ("maxLength", qualities.max_length),
+            );
+        }
+        Some(Types::Type(RegularTypes::Array(qualities))) => {
+            validate_enum(context, &qualities.common_qualities, pointer);
+            validate_bounds(
+                context,
+                pointer,
+                ("minItems", qualities.min_items),
+                ("maxItems", qualities.max_items),
+            );
+            let items_pointer = member_pointer(pointer, "items");
+            match &qualities.items {
+                Some(ArrayItems::Single(item)) => {
+                    validate_data(context, item, &items_pointer, "items")
+                }
+                Some(ArrayItems::Multiple(items)) => {
+                    for (index, item) in items.iter().enumerate() {
+                        let item_pointer = format!("{}/{}", items_pointer, index);
+                        validate_data(context, item, &item_pointer, "items");
+                    }
+                }
+                None => (),
+            }
+        }
+        Some(Types::Type(RegularTypes::Object(qualities))) => {
+            validate_enum(context, &qualities.common_qualities, pointer);
+            let properties = &qualities.properties;
+            for (index, name) in qualities.required.iter().flatten().enumerate() {
+                if !properties.as_ref().is_some_and(|x| x.contains_key(name)) {
+                    context.report(
+                        &format!("{}/required/{}", pointer, index),
+                        format!("Required property \"{}\" is not defined", name),
+                    );
+                }
+            }
+            validate_data_definitions(context, properties, pointer, "properties");
+        }
+        Some(Types::Untyped(qualities)) => validate_enum(context, &qualities.0, pointer),
+        None => (),
+    }
+}
+
+/// Checks that `const` and `default` values are part of the `enum`.
+fn validate_enum<T: PartialEq>(
+    context: &mut Context,
+    qualities: &TypedQualities<T>,
+    pointer: &str,
+) {
+    let enumeration = match &qualities.r#enum {
+        Some(enumeration) => enumeration,
+        None => return,
+    };
+
+    for (quality, value) in [
+        ("const", &qualities.r#const),
+        ("default", &qualities.default),
+    ] {
+        if value.as_ref().is_some_and(|x| !enumeration.contains(x)) {
+            context.report(
+                &member_pointer(pointer, quality),
+                format!("The {} value is not part of the enum", quality),
+            );
+        }
+    }
+}
+
+/// Reports a lower bound that is greater than the corresponding upper bound.
+fn validate_bounds<T: PartialOrd + Display>(
+    context: &mut Context,
+    pointer: &str,
+    (minimum_name, minimum): (&str, Option<T>),
+    (maximum_name, maximum): (&str, Option<T>),
+) {
+    if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+        if minimum > maximum {
+            context.report(
+                &member_pointer(pointer, minimum_name),
+                format!(
+                    "{} ({}) is greater than {} ({})",
+                    minimum_name, minimum, maximum_name, maximum
+                ),
+            );
+        }
+    }
+}
+
+fn is_in_range<T: PartialOrd + Copy>(value: T, qualities: &NumberTypeQualities<T>) -> bool {
+    qualities.minimum.is_none_or(|x| value >= x)
+        && qualities.maximum.is_none_or(|x| value <= x)
+        && qualities.exclusive_minimum.is_none_or(|x| value > x)
+        && qualities.exclusive_maximum.is_none_or(|x| value < x)
+}
+
+fn validate_number_qualities<T: PartialOrd + Display + Copy + Default>(
+    context: &mut Context,
+    qualities: &NumberTypeQualities<T>,
+    pointer: &str,
+) {
+    validate_enum(context, &qualities.common_qualities, pointer);
+
+    validate_bounds(
+        context,
+        pointer,
+        ("minimum", qualities.minimum),
+        ("maximum", qualities.maximum),
+    );
+    for (minimum_name, minimum, maximum_name, maximum) in [
+        (
+            "exclusiveMinimum",
+            qualities.exclusive_minimum,
+            "exclusiveMaximum",
+            qualities.exclusive_maximum,
+        ),
+        (
+            "minimum",
+            qualities.minimum,
+            "exclusiveMaximum",
+            qualities.exclusive_maximum,
+        ),
+        (
+            "exclusiveMinimum",
+            qualities.exclusive_minimum,
+            "maximum",
+            qualities.maximum,
+        ),
+    ] {
+        if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+            if minimum >= maximum {
+                context.report(
+                    &member_pointer(pointer, minimum_name),
+                    format!(
+                        "{} ({}) leaves no values below {} ({})",
+                        minimum_name, minimum, maximum_name, maximum
+                    ),
+                );
+            }
+        }
+    }
+
+    if qualities.multiple_of.is_some_and(|x| x <= T::default()) {
+        context.report(
+            &member_pointer(pointer, "multipleOf"),
+            "multipleOf has to be greater than zero".to_string(),
+        );
+    }
+
+    let typed_qualities = &qualities.common_qualities;
+    for (quality, value) in [
+        ("const", typed_qualities.r#const),
+        ("default", typed_qualities.default),
+    ] {
+        if value.is_some_and(|x| !is_in_range(x, qualities)) {
+            context.report(
+                &member_pointer(pointer, quality),
+                format!("The {} value is outside of the allowed range", quality),
+            );
+        }
+    }
+    for (index, value) in typed_qualities.r#enum.iter().flatten().enumerate() {
+        if !is_in_range(*value, qualities) {
+            context.report(
+                &format!("{}/enum/{}", pointer, index),
+                "The enum value is outside of the allowed range".to_string(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn validate_value(sdf_model: Value) -> Vec<Violation> {
+        validate(&serde_json::from_value(sdf_model).unwrap())
+    }
+
+    #[test]
+    fn split_reference_test() {
+        assert_eq!(
+            split_reference("#/sdfData/foo"),
+            Some((None, "/sdfData/foo"))
+        );
+        assert_eq!(
+            split_reference("cap:#/sdfData/foo"),
+            Some((Some("cap"), "/sdfData/foo"))
+        );
+        assert_eq!(split_reference("/sdfData/foo"), None);
+        assert_eq!(split_reference(":#/sdfData/foo"), None);
+    }
+
+    #[test]
+    fn structure_violations_test() {
+        let violations = validate_json(&json!({
+            "sdfObjekt": {},
+            "namespace": {"cap": 42},
+            "sdfObject": {
+                "switch": {
+                    "label": 1,
+                    "sdfAction": {
+                        "toggle": {
+                            "sdfInputData": {"type": "foo"},
+                            "sdfOutputData": []
+                        }
+                    },
+                    "sdfThing": {}
+                }
+            },
+            "sdfProperty": {
+                "number": {
+                    "type": "number",
+                    "minimum": "abc",
+                    "default": "1",
+                    "readable": "yes",
+                    "sdfType": "unix-time",
+                    "vendorExtension": true
+                },
+                "integer": {
+                    "type": "integer",
+                    "maximum": 1.5,
+                    "enum": [1, 2.5],
+                    "minLength": -1
+                },
+                "choice": {
+                    "sdfChoice": {
+                        "on": {"const": 1},
+                        "off": {"sdfChoices": {}}
+                    }
+                },
+                "array": {
+                    "type": "array",
+                    "items": {"type": "string", "const": true},
+                    "uniqueItems": 1
+                }
+            }
+        }));
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation::new(
+                    "/sdfObjekt",
+                    "Unknown SDF quality \"sdfObjekt\"".to_string()
+                ),
+                Violation::new(
+                    "/namespace",
+                    "namespace has to be a map of strings".to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/label",
+                    "label has to be a string".to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfAction/toggle/sdfInputData/type",
+                    "type has to be one of \"number\", \"string\", \"boolean\", \"integer\", \
+                    \"array\", \"object\""
+                        .to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfAction/toggle/sdfOutputData",
+                    "Expected a JSON object".to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfThing",
+                    "Unknown SDF quality \"sdfThing\"".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/number/minimum",
+                    "minimum has to be a number".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/number/readable",
+                    "readable has to be a boolean".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/number/default",
+                    "The default value does not match the type \"number\"".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/integer/minLength",
+                    "minLength has to be a non-negative integer".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/integer/enum/1",
+                    "The enum value does not match the type \"integer\"".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/integer/maximum",
+                    "maximum has to be an integer".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/choice/sdfChoice/off/sdfChoices",
+                    "Unknown SDF quality \"sdfChoices\"".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/array/items/const",
+                    "The const value does not match the type \"string\"".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/array/uniqueItems",
+                    "uniqueItems has to be a boolean".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn structure_and_semantic_violations_test() {
+        let violations = validate_json(&json!({
+            "sdfData": {
+                "foo": {"type": "integer", "minimum": 2, "maximum": 1}
+            },
+            "sdfProperty": {
+                "bar": {"sdfRef": "#/sdfData/baz", "sdfWritable": false}
+            }
+        }));
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation::new(
+                    "/sdfProperty/bar/sdfWritable",
+                    "Unknown SDF quality \"sdfWritable\"".to_string()
+                ),
+                Violation::new(
+                    "/sdfProperty/bar/sdfRef",
+                    "sdfRef \"#/sdfData/baz\" does not point to an existing definition".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/foo/minimum",
+                    "minimum (2) is greater than maximum (1)".to_string()
+                ),
+            ]
+        );
+
+        let violations = validate_json(&json!({
+            "sdfProperty": {
+                "foo": {"type": "integer", "minimum": 2, "maximum": 1, "writable": "no"}
+            }
+        }));
+        assert_eq!(
+            violations,
+            vec![Violation::new(
+                "/sdfProperty/foo/writable",
+                "writable has to be a boolean".to_string()
+            )]
+        );
+
+        let violations = validate_json(&json!({"info": {"title": "Foo"}}));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].pointer, "/info");
+        assert_eq!(validate_json(&json!([])).len(), 1);
+    }
+
+    #[test]
+    fn valid_model_test() {
+        let violations = validate_value(json!({
+            "namespace": {"cap": "https://example.com/capability/cap"},
+            "defaultNamespace": "cap",
+            "sdfObject": {
+                "switch": {
+                    "sdfRequired": ["#/sdfObject/switch/sdfProperty/value"],
+                    "sdfProperty": {
+                        "value": {"sdfRef": "cap:#/sdfData/state"}
+                    }
+                }
+            },
+            "sdfData": {
+                "state": {"type": "integer", "minimum": 0, "maximum": 1, "default": 0}
+            }
+        }));
+
+        assert_eq!(violations, vec![]);
+    }
+
+    #[test]
+    fn namespace_violations_test() {
+        let violations = validate_value(json!({
+            "namespace": {
+                "cap": "https://example.com/capability/cap",
+                "other": "https://example.com/capability/cap"
+            },
+            "defaultNamespace": "foo",
+            "sdfObject": {
+                "switch": {
+                    "sdfProperty": {
+                        "state": {"type": "boolean"}
+                    },
+                    "sdfAction": {
+                        "state": {}
+                    },
+                    "sdfData": {
+                        "state": {"type": "boolean"}
+                    }
+                }
+            },
+            "sdfThing": {
+                "switch": {}
+            }
+        }));
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation::new(
+                    "/defaultNamespace",
+                    "Default namespace \"foo\" is not declared in the namespace block".to_string()
+                ),
+                Violation::new(
+                    "/namespace/other",
+                    "Namespace \"https://example.com/capability/cap\" is already bound to the \
+                    prefix \"cap\""
+                        .to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch",
+                    "The name \"switch\" is already used by the definition at \
+                    \"/sdfThing/switch\""
+                        .to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfAction/state",
+                    "The name \"state\" is already used by the definition at \
+                    \"/sdfObject/switch/sdfProperty/state\""
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reference_violations_test() {
+        let violations = validate_value(json!({
+            "sdfObject": {
+                "switch": {
+                    "sdfRequired": [
+                        "#/sdfObject/switch/sdfProperty/missing",
+                        "#/sdfObject/lamp/sdfProperty/value",
+                        "#/sdfObject/switch/sdfData"
+                    ],
+                    "sdfProperty": {
+                        "value": {"sdfRef": "#/sdfObject/switch/sdfAction/toggle"},
+                        "other": {"sdfRef": "foo:#/sdfData/bar"}
+                    },
+                    "sdfAction": {
+                        "toggle": {}
+                    },
+                    "sdfData": {}
+                },
+                "lamp": {
+                    "sdfProperty": {
+                        "value": {"type": "boolean"}
+                    }
+                }
+            }
+        }));
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation::new(
+                    "/sdfObject/switch/sdfRequired/0",
+                    "sdfRequired entry \"#/sdfObject/switch/sdfProperty/missing\" does not \
+                    point to an existing definition"
+                        .to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfRequired/1",
+                    "sdfRequired entry \"#/sdfObject/lamp/sdfProperty/value\" points outside \
+                    of the enclosing definition"
+                        .to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfRequired/2",
+                    "sdfRequired entry \"#/sdfObject/switch/sdfData\" does not point to a \
+                    declaration"
+                        .to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfProperty/value/sdfRef",
+                    "sdfRef \"#/sdfObject/switch/sdfAction/toggle\" of a sdfProperty \
+                    definition points to a sdfAction definition"
+                        .to_string()
+                ),
+                Violation::new(
+                    "/sdfObject/switch/sdfProperty/other/sdfRef",
+                    "Namespace \"foo\" of sdfRef \"foo:#/sdfData/bar\" is not declared".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn data_violations_test() {
+        let violations = validate_value(json!({
+            "sdfData": {
+                "number": {
+                    "type": "number",
+                    "exclusiveMinimum": 2.0,
+                    "maximum": 2.0,
+                    "multipleOf": 0.0
+                },
+                "integer": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "enum": [1, -1],
+                    "const": 2
+                },
+                "string": {
+                    "type": "string",
+                    "minLength": 4,
+                    "maxLength": 2
+                },
+                "choice": {
+                    "sdfChoice": {
+                        "on": {"type": "integer", "minimum": 1, "maximum": 0},
+                        "off": {"type": "string", "minLength": 1, "maxLength": 0}
+                    }
+                },
+                "object": {
+                    "type": "object",
+                    "required": ["foo"],
+                    "properties": {
+                        "bar": {"type": "array", "minItems": 2, "maxItems": 1}
+                    }
+                }
+            }
+        }));
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation::new(
+                    "/sdfData/number/exclusiveMinimum",
+                    "exclusiveMinimum (2) leaves no values below maximum (2)".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/number/multipleOf",
+                    "multipleOf has to be greater than zero".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/integer/const",
+                    "The const value is not part of the enum".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/integer/enum/1",
+                    "The enum value is outside of the allowed range".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/string/minLength",
+                    "minLength (4) is greater than maxLength (2)".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/choice/sdfChoice/on/minimum",
+                    "minimum (1) is greater than maximum (0)".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/choice/sdfChoice/off/minLength",
+                    "minLength (1) is greater than maxLength (0)".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/object/required/0",
+                    "Required property \"foo\" is not defined".to_string()
+                ),
+                Violation::new(
+                    "/sdfData/object/properties/bar/minItems",
+                    "minItems (2) is greater than maxItems (1)".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -14,10 +14,10 @@ pub enum Context {
 #[serde(untagged)]
 pub enum ContextEntry {
     String(String),
-    Map(HashMap<String, String>),
+    Map(IndexMap<String, String>),
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum TypeOrTypeArray<T> {
     Type(T),
@@ -32,12 +32,12 @@ pub struct ThingDescription {
     pub base_thing: BaseThing,
     pub title: String,
     pub security: TypeOrTypeArray<String>,
-    pub security_definitions: HashMap<String, SecurityScheme>,
-    pub links: Option<Vec<TMLink>>,
-    pub forms: Option<Vec<TMForm>>,
-    pub actions: Option<HashMap<String, TDActionAffordance>>,
-    pub properties: Option<HashMap<String, TDPropertyAffordance>>,
-    pub events: Option<HashMap<String, TDEventAffordance>>,
+    pub security_definitions: IndexMap<String, SecurityScheme>,
+    pub links: Option<Vec<TDLink>>,
+    pub forms: Option<Vec<TDForm>>,
+    pub actions: Option<IndexMap<String, TDActionAffordance>>,
+    pub properties: Option<IndexMap<String, TDPropertyAffordance>>,
+    pub events: Option<IndexMap<String, TDEventAffordance>>,
 }
 
 #[skip_serializing_none]
@@ -48,12 +48,12 @@ pub struct ThingModel {
     pub base_thing: BaseThing,
     pub title: Option<String>,
     pub security: Option<TypeOrTypeArray<String>>,
-    pub security_definitions: Option<HashMap<String, SecurityScheme>>,
+    pub security_definitions: Option<IndexMap<String, SecurityScheme>>,
     pub links: Option<Vec<TMLink>>,
     pub forms: Option<Vec<TMForm>>,
-    pub actions: Option<HashMap<String, TMActionAffordance>>,
-    pub properties: Option<HashMap<String, TMPropertyAffordance>>,
-    pub events: Option<HashMap<String, TMEventAffordance>>,
+    pub actions: Option<IndexMap<String, TMActionAffordance>>,
+    pub properties: Option<IndexMap<String, TMPropertyAffordance>>,
+    pub events: Option<IndexMap<String, TMEventAffordance>>,
 }
 
 #[skip_serializing_none]
@@ -65,16 +65,16 @@ pub struct BaseThing {
     #[serde(rename = "@type")]
     pub r#type: Option<TypeOrTypeArray<String>>,
     pub id: Option<String>,
-    pub titles: Option<HashMap<String, String>>, // TODO: Consider using a MultiLanguage struct instead
+    pub titles: Option<IndexMap<String, String>>, // TODO: Consider using a MultiLanguage struct instead
     pub description: Option<String>,
-    pub descriptions: Option<HashMap<String, String>>,
+    pub descriptions: Option<IndexMap<String, String>>,
     pub version: Option<VersionInfo>,
     pub created: Option<DateTime<Utc>>,
     pub modified: Option<DateTime<Utc>>,
     pub support: Option<String>,
     pub base: Option<String>,
     pub profile: Option<TypeOrTypeArray<String>>,
-    pub schema_definitions: Option<HashMap<String, DataSchema>>,
+    pub schema_definitions: Option<IndexMap<String, DataSchema>>,
 }
 
 impl Default for BaseThing {
@@ -104,7 +104,7 @@ impl Default for ThingDescription {
         let nosec_sc = SecurityScheme::Nosec {
             common: SecuritySchemeCommon::default(),
         };
-        let security_definitions: HashMap<String, SecurityScheme> =
+        let security_definitions: IndexMap<String, SecurityScheme> =
             vec![("nosec_sc".to_string(), nosec_sc)]
                 .into_iter()
                 .collect();
@@ -195,6 +195,7 @@ pub enum SecurityScheme {
         r#in: Option<SchemeIn>,
         name: Option<String>,
     },
+    #[serde(rename = "psk")]
     PSK {
         #[serde(flatten)]
         common: SecuritySchemeCommon,
@@ -224,7 +225,7 @@ pub struct SecuritySchemeCommon {
     #[serde(rename = "@type")]
     pub r#type: Option<TypeOrTypeArray<String>>,
     pub description: Option<String>,
-    pub descriptions: Option<HashMap<String, String>>,
+    pub descriptions: Option<IndexMap<String, String>>,
     pub proxy: Option<String>,
 }
 
@@ -237,7 +238,7 @@ pub struct VersionInfo {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NumberSchema<T> {
     pub minimum: Option<T>,
@@ -248,7 +249,7 @@ pub struct NumberSchema<T> {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StringSchema {
     pub min_length: Option<u32>,
@@ -259,7 +260,7 @@ pub struct StringSchema {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArraySchema {
     pub items: Option<Box<TypeOrTypeArray<DataSchema>>>,
@@ -268,14 +269,14 @@ pub struct ArraySchema {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectSchema {
-    pub properties: Option<HashMap<String, DataSchema>>,
+    pub properties: Option<IndexMap<String, DataSchema>>,
     pub required: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum JSONSchemaTypes {
@@ -289,15 +290,15 @@ pub enum JSONSchemaTypes {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DataSchema {
     #[serde(rename = "@type")]
     pub r#type: Option<TypeOrTypeArray<String>>,
     pub title: Option<String>,
-    pub titles: Option<HashMap<String, String>>, // TODO: Consider using a MultiLanguage struct instead
+    pub titles: Option<IndexMap<String, String>>, // TODO: Consider using a MultiLanguage struct instead
     pub description: Option<String>,
-    pub descriptions: Option<HashMap<String, String>>,
+    pub descriptions: Option<IndexMap<String, String>>,
     #[serde(flatten)]
     pub data_type: Option<JSONSchemaTypes>,
     pub r#const: Option<serde_json::Value>,
@@ -335,10 +336,10 @@ pub struct InteractionAffordance {
     #[serde(rename = "@type")]
     pub r#type: Option<TypeOrTypeArray<String>>,
     pub title: Option<String>,
-    pub titles: Option<HashMap<String, String>>,
+    pub titles: Option<IndexMap<String, String>>,
     pub description: Option<String>,
-    pub descriptions: Option<HashMap<String, String>>,
-    pub uri_variables: Option<HashMap<String, DataSchema>>,
+    pub descriptions: Option<IndexMap<String, String>>,
+    pub uri_variables: Option<IndexMap<String, DataSchema>>,
 }
 
 #[skip_serializing_none]
@@ -435,7 +436,7 @@ pub struct EventAffordance {
     pub cancellation: Option<DataSchema>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum OperationType {
     Readproperty,
@@ -457,7 +458,7 @@ pub enum OperationType {
 pub struct TDForm {
     pub href: String,
     #[serde(flatten)]
-    form_fields: Form,
+    pub form_fields: Form,
 }
 
 #[skip_serializing_none]
@@ -466,11 +467,11 @@ pub struct TDForm {
 pub struct TMForm {
     pub href: Option<String>,
     #[serde(flatten)]
-    form_fields: Form,
+    pub form_fields: Form,
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Default, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Form {
     // TODO: Define forms for different affordance types
@@ -482,6 +483,9 @@ pub struct Form {
     pub scopes: Option<TypeOrTypeArray<String>>,
     pub response: Option<ExpectedResponse>,
     pub additional_responses: Option<TypeOrTypeArray<AdditionalExpectedResponse>>,
+    /// Protocol-specific terms like `htv:methodName` or `cov:method`.
+    #[serde(flatten)]
+    pub protocol_fields: IndexMap<String, serde_json::Value>,
 }
 
 #[skip_serializing_none]
@@ -512,13 +516,14 @@ pub struct TMLink {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Default, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Link {
     pub r#type: Option<String>,
     pub rel: Option<String>,
     pub anchor: Option<String>,
     pub sizes: Option<String>,
+    pub instance_name: Option<String>,
 }
 
 #[skip_serializing_none]
@@ -0,0 +1,385 @@
+//! Application of SDF mapping files to Thing Models converted from SDF.
+//!
+//! Mapping entries can provide complete forms (`td:forms`) or the shorthands
+//! `td:href`, `td:method`, and `td:contentType`, which are applied to all forms
+//! of the affordance the entry points to. Entries pointing to an `sdfObject`,
+//! an `sdfThing`, or the model itself may additionally provide `td:id`,
+//! `td:base`, `td:security`, `td:securityDefinitions`, and Thing-level
+//! `td:forms`.
+
+use super::definitions as wot;
+use super::tm_from_sdf::get_prefixed_key;
+use crate::json_pointer::unescape_pointer_token;
+use crate::sdf::definitions as sdf;
+use crate::{ConversionError, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+const HTTP_METHOD_TERM: &str = "htv:methodName";
+const COAP_METHOD_TERM: &str = "cov:method";
+const HTTP_VOCABULARY: (&str, &str) = ("htv", "http://www.w3.org/2011/http#");
+const COAP_VOCABULARY: (&str, &str) = ("cov", "http://www.example.org/coap-binding#");
+
+/// The part of a Thing Model a mapping entry applies to.
+#[derive(Debug, PartialEq)]
+enum MappingTarget {
+    Thing,
+    Property(String),
+    Action(String),
+    Event(String),
+}
+
+/// Splits the key of a mapping entry into a JSON pointer and its tokens,
+/// ignoring a potential namespace prefix.
+fn parse_pointer(key: &str) -> Result<(&str, Vec<String>)> {
+    let pointer = key
+        .split_once('#')
+        .map(|(_, pointer)| pointer)
+        .ok_or_else(|| {
+            ConversionError::Mapping(format!("Mapping entry \"{}\" is not a JSON pointer!", key))
+        })?;
+
+    let tokens = pointer
+        .split('/')
+        .skip(1)
+        .map(unescape_pointer_token)
+        .collect();
+
+    Ok((pointer, tokens))
+}
+
+/// Determines the Thing Model definition corresponding to the SDF definition a
+/// mapping entry points to.
+///
+/// Affordances of nested `sdfObject`s and `sdfThing`s are named in the same way
+/// as during the conversion, i.e., prefixed with the names of their parents.
+fn get_mapping_target(key: &str, tokens: &[String]) -> Result<MappingTarget> {
+    let unsupported_error = || {
+        ConversionError::Mapping(format!(
+            "Mapping entry \"{}\" has an unsupported target!",
+            key
+        ))
+    };
+
+    if !tokens.len().is_multiple_of(2) {
+        return Err(unsupported_error());
+    }
+
+    let mut prefix: Option<String> = None;
+    let mut target = MappingTarget::Thing;
+
+    for (index, pair) in tokens.chunks(2).enumerate() {
+        let is_last_pair = index == tokens.len() / 2 - 1;
+        let name = get_prefixed_key(prefix.clone(), pair[1].clone());
+
+        match pair[0].as_str() {
+            "sdfThing" | "sdfObject" => prefix = Some(name),
+            "sdfProperty" if is_last_pair => target = MappingTarget::Property(name),
+            "sdfAction" if is_last_pair => target = MappingTarget::Action(name),
+            "sdfEvent" if is_last_pair => target = MappingTarget::Event(name),
+            _ => return Err(unsupported_error()),
+        }
+    }
+
+    Ok(target)
+}
+
+fn deserialize_entry_value<T: DeserializeOwned>(key: &str, term: &str, value: &Value) -> Result<T> {
+    serde_json::from_value(value.clone()).map_err(|error| {
+        ConversionError::Mapping(format!(
+            "Invalid value for \"{}\" in mapping entry \"{}\": {}",
+            term, key, error
+        ))
+    })
+}
+
+/// Adds a prefix definition to the `@context` of a Thing Model if not present.
+pub(crate) fn add_context_prefix(context: &mut wot::Context, prefix: &str, uri: &str) {
+    if let wot::Context::String(context_uri) = context {
+        *context = wot::Context::Array(vec![wot::ContextEntry::String(context_uri.clone())]);
+    }
+
+    let entries = match context {
+        wot::Context::Array(entries) => entries,
+        wot::Context::String(_) => return,
+    };
+
+    let is_defined = entries.iter().any(|entry| match entry {
+        wot::ContextEntry::Map(map) => map.contains_key(prefix),
+        wot::ContextEntry::String(_) => false,
+    });
+    if is_defined {
+        return;
+    }
+
+    let existing_map = entries.iter_mut().find_map(|entry| match entry {
+        wot::ContextEntry::Map(map) => Some(map),
+        wot::ContextEntry::String(_) => None,
+    });
+    match existing_map {
+        Some(map) => {
+            map.insert(prefix.to_string(), uri.to_string());
+        }
+        None => entries.push(wot::ContextEntry::Map(
+            vec![(prefix.to_string(), uri.to_string())]
+                .into_iter()
+                .collect(),
+        )),
+    }
+}
+
+fn apply_thing_entry(
+    thing_model: &mut wot::ThingModel,
+    key: &str,
+    entry: &sdf::MappingEntry,
+) -> Result<()> {
+    for (term, value) in entry {
+        match term.as_str() {
+            "td:id" => thing_model.base_thing.id = deserialize_entry_value(key, term, value)?,
+            "td:base" => thing_model.base_thing.base = deserialize_entry_value(key, term, value)?,
+            "td:security" => thing_model.security = deserialize_entry_value(key, term, value)?,
+            "td:securityDefinitions" => {
+                thing_model.security_definitions = deserialize_entry_value(key, term, value)?
+            }
+            "td:forms" => thing_model.forms = deserialize_entry_value(key, term, value)?,
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn get_method_vocabulary(href: Option<&str>) -> ((&'static str, &'static str), &'static str) {
+    match href {
+        Some(href) if href.starts_with("coap") => (COAP_VOCABULARY, COAP_METHOD_TERM),
+        _ => (HTTP_VOCABULARY, HTTP_METHOD_TERM),
+    }
+}
+
+fn apply_affordance_entry(
+    interaction_affordance: &mut wot::TMInteractionAffordance,
+    context: &mut wot::Context,
+    base: Option<&str>,
+    key: &str,
+    entry: &sdf::MappingEntry,
+) -> Result<()> {
+    if let Some(forms) = entry.get("td:forms") {
+        interaction_affordance.forms = deserialize_entry_value(key, "td:forms", forms)?;
+    }
+
+    let href: Option<String> = entry
+        .get("td:href")
+        .map(|x| deserialize_entry_value(key, "td:href", x))
+        .transpose()?;
+    let method: Option<String> = entry
+        .get("td:method")
+        .map(|x| deserialize_entry_value(key, "td:method", x))
+        .transpose()?;
+    let content_type: Option<String> = entry
+        .get("td:contentType")
+        .map(|x| deserialize_entry_value(key, "td:contentType", x))
+        .transpose()?;
+
+    if href.is_none() && method.is_none() && content_type.is_none() {
+        return Ok(());
+    }
+
+    let forms = interaction_affordance.forms.get_or_insert_with(Vec::new);
+    if forms.is_empty() {
+        forms.push(wot::TMForm {
+            href: None,
+            form_fields: wot::Form::default(),
+        });
+    }
+
+    for form in forms {
+        if let Some(href) = &href {
+            form.href = Some(href.clone());
+        }
+        if let Some(content_type) = &content_type {
+            form.form_fields.content_type = Some(content_type.clone());
+        }
+        if let Some(method) = &method {
+            let scheme_source = form.href.as_deref().filter(|x| x.contains(':')).or(base);
+            let ((prefix, uri), term) = get_method_vocabulary(scheme_source);
+            add_context_prefix(context, prefix, uri);
+            form.form_fields
+                .protocol_fields
+                .insert(term.to_string(), Value::String(method.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies an SDF mapping file to a Thing Model that has been converted from
+/// the SDF model with the given JSON representation.
+///
+/// Returns an error if a mapping entry does not point to an existing
+/// definition of the SDF model or contains invalid values.
+pub fn apply_mapping(
+    sdf_model: &Value,
+    thing_model: &mut wot::ThingModel,
+    mapping: &sdf::SDFMapping,
+) -> Result<()> {
+    let mut targets = Vec::new();
+    for (key, entry) in &mapping.map {
+        let (pointer, tokens) = parse_pointer(key)?;
+
+        if sdf_model.pointer(pointer).is_none() {
+            return Err(ConversionError::Mapping(format!(
+                "Mapping entry \"{}\" does not point to an existing definition!",
+                key
+            )));
+        }
+
+        targets.push((get_mapping_target(key, &tokens)?, key, entry));
+    }
+
+    // Thing-level entries are applied first, as they may define the base URI
+    // used for determining the protocol of affordance-level entries. Otherwise,
+    // the entries keep the order of the mapping file.
+    targets.sort_by_key(|(target, _, _)| *target != MappingTarget::Thing);
+
+    for (target, key, entry) in targets {
+        let base = thing_model.base_thing.base.clone();
+
+        let interaction_affordance = match &target {
+            MappingTarget::Thing => {
+                apply_thing_entry(thing_model, key, entry)?;
+                continue;
+            }
+            MappingTarget::Property(name) => thing_model
+                .properties
+                .as_mut()
+                .and_then(|x| x.get_mut(name))
+                .map(|x| &mut x.interaction_affordance),
+            MappingTarget::Action(name) => thing_model
+                .actions
+                .as_mut()
+                .and_then(|x| x.get_mut(name))
+                .map(|x| &mut x.interaction_affordance),
+            MappingTarget::Event(name) => thing_model
+                .events
+                .as_mut()
+                .and_then(|x| x.get_mut(name))
+                .map(|x| &mut x.interaction_affordance),
+        }
+        .ok_or_else(|| {
+            ConversionError::Mapping(format!(
+                "Mapping entry \"{}\" has no corresponding affordance!",
+                key
+            ))
+        })?;
+
+        apply_affordance_entry(
+            interaction_affordance,
+            &mut thing_model.base_thing.context,
+            base.as_deref(),
+            key,
+            entry,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_mapping_target_test() {
+        let get_target = |key: &str| {
+            let (_, tokens) = parse_pointer(key).unwrap();
+            get_mapping_target(key, &tokens)
+        };
+
+        assert_eq!(get_target("#").unwrap(), MappingTarget::Thing);
+        assert_eq!(
+            get_target("#/sdfObject/Switch").unwrap(),
+            MappingTarget::Thing
+        );
+        assert_eq!(
+            get_target("cap:#/sdfObject/switch/sdfProperty/value").unwrap(),
+            MappingTarget::Property("switchValue".to_string())
+        );
+        assert_eq!(
+            get_target("#/sdfThing/lamp/sdfObject/switch/sdfAction/toggle").unwrap(),
+            MappingTarget::Action("lampSwitchToggle".to_string())
+        );
+        assert_eq!(
+            get_target("#/sdfEvent/foo~1bar").unwrap(),
+            MappingTarget::Event("foo/bar".to_string())
+        );
+        assert!(get_target("#/sdfData/foo").is_err());
+        assert!(get_target("#/sdfProperty/foo/type").is_err());
+        assert!(parse_pointer("/sdfProperty/foo").is_err());
+    }
+
+    #[test]
+    fn add_context_prefix_test() {
+        let mut context = wot::Context::String("https://www.w3.org/2019/wot/td/v1".to_string());
+
+        add_context_prefix(&mut context, "htv", HTTP_VOCABULARY.1);
+        add_context_prefix(&mut context, "htv", HTTP_VOCABULARY.1);
+
+        assert_eq!(
+            serde_json::to_value(&context).unwrap(),
+            json!([
+                "https://www.w3.org/2019/wot/td/v1",
+                {"htv": "http://www.w3.org/2011/http#"}
+            ])
+        );
+    }
+
+    #[test]
+    fn apply_mapping_test() {
+        let sdf_json = json!({
+            "sdfObject": {
+                "switch": {
+                    "sdfProperty": {
+                        "value": {"type": "boolean"}
+                    }
+                }
+            }
+        });
+        let mut thing_model = wot::ThingModel::from(
+            serde_json::from_value::<sdf::SDFModel>(sdf_json.clone()).unwrap(),
+        );
+        let mapping: sdf::SDFMapping = serde_json::from_value(json!({
+            "map": {
+                "#/sdfObject/switch": {
+                    "td:base": "coap://example.org/"
+                },
+                "#/sdfObject/switch/sdfProperty/value": {
+                    "td:href": "value",
+                    "td:method": "PUT"
+                }
+            }
+        }))
+        .unwrap();
+
+        apply_mapping(&sdf_json, &mut thing_model, &mapping).unwrap();
+
+        assert_eq!(
+            thing_model.base_thing.base,
+            Some("coap://example.org/".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(&thing_model.properties.unwrap()["switchValue"]).unwrap()["forms"],
+            json!([{"href": "value", "cov:method": "PUT"}])
+        );
+
+        let mapping: sdf::SDFMapping = serde_json::from_value(json!({
+            "map": {
+                "#/sdfObject/lamp": {}
+            }
+        }))
+        .unwrap();
+        let mut thing_model = wot::ThingModel::default();
+
+        assert!(apply_mapping(&sdf_json, &mut thing_model, &mapping).is_err());
+    }
+}
@@ -1,2 +1,8 @@
 pub mod definitions;
+pub mod mapping;
+pub mod td_from_sdf;
+pub mod td_from_tm;
 pub mod tm_from_sdf;
+pub mod tm_resolver;
+pub mod units;
+pub mod validation;
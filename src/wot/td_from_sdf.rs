@@ -0,0 +1,436 @@
+//! Direct conversion of SDF models into deployable Thing Descriptions.
+//!
+//! The SDF model is first converted into a Thing Model, which is then
+//! supplemented with forms for the chosen protocol and a security scheme
+//! before it is turned into a Thing Description.
+
+use super::definitions as wot;
+use super::mapping::apply_mapping;
+use super::td_from_tm::create_thing_description;
+use super::tm_from_sdf::convert_to_thing_model;
+use super::units::{translate_units, UnitVocabulary};
+use crate::diagnostics::{report, Conversion, Diagnostic, DiagnosticKind};
+use crate::sdf::definitions as sdf;
+use crate::{ConversionError, Result};
+use indexmap::IndexMap;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use std::str::FromStr;
+use url::Url;
+
+/// The content type of generated forms and the default of Thing Descriptions.
+pub(crate) const DEFAULT_CONTENT_TYPE: &str = "application/json";
+
+/// The protocol the generated forms of a Thing Description use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Http,
+    Coap,
+}
+
+impl Protocol {
+    fn uri_schemes(&self) -> &'static [&'static str] {
+        match self {
+            Protocol::Http => &["http", "https"],
+            Protocol::Coap => &["coap", "coaps", "coap+tcp", "coaps+tcp"],
+        }
+    }
+
+    fn observe_subprotocol(&self) -> &'static str {
+        match self {
+            Protocol::Http => "longpoll",
+            Protocol::Coap => "cov:observe",
+        }
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = ConversionError;
+
+    fn from_str(protocol: &str) -> Result<Self> {
+        match protocol.to_lowercase().as_str() {
+            "http" => Ok(Protocol::Http),
+            "coap" => Ok(Protocol::Coap),
+            _ => Err(ConversionError::Unsupported(format!(
+                "Unsupported protocol \"{}\"!",
+                protocol
+            ))),
+        }
+    }
+}
+
+/// Protocol binding information used for creating a Thing Description.
+#[derive(Debug, Clone)]
+pub struct BindingOptions {
+    /// The base URI all generated form `href`s are relative to.
+    pub base: String,
+    /// The protocol of the generated forms.
+    pub protocol: Protocol,
+    /// The name of the security scheme (e.g., `nosec`, `basic`, or `bearer`).
+    pub security_scheme: String,
+    /// The vocabulary the units of the Thing Description refer to.
+    pub units: UnitVocabulary,
+}
+
+/// Creates a security scheme without any further parameters from its name.
+pub fn create_security_scheme(scheme: &str) -> Result<wot::SecurityScheme> {
+    let common = wot::SecuritySchemeCommon::default();
+
+    let security_scheme = match scheme {
+        "nosec" => wot::SecurityScheme::Nosec { common },
+        "basic" => wot::SecurityScheme::Basic {
+            common,
+            r#in: None,
+            name: None,
+        },
+        "digest" => wot::SecurityScheme::Digest {
+            common,
+            qop: None,
+            r#in: None,
+            name: None,
+        },
+        "bearer" => wot::SecurityScheme::Bearer {
+            common,
+            authorization: None,
+            alg: None,
+            format: None,
+            r#in: None,
+            name: None,
+        },
+        "psk" => wot::SecurityScheme::PSK {
+            common,
+            identity: None,
+        },
+        "apikey" => wot::SecurityScheme::Apikey {
+            common,
+            r#in: None,
+            name: None,
+        },
+        _ => {
+            return Err(ConversionError::Unsupported(format!(
+                "Unsupported security scheme \"{}\"!",
+                scheme
+            )))
+        }
+    };
+
+    Ok(security_scheme)
+}
+
+/// Converts an SDF model into a Thing Description.
+///
+/// Every affordance is assigned forms relative to the `base` URI of the
+/// `options`. Properties receive the operations `readproperty`,
+/// `writeproperty`, and `observeproperty` unless they are write-only,
+/// read-only, or not observable, respectively. Actions are assigned
+/// `invokeaction` and events `subscribeevent`. The forms of a property use the
+/// media type of its `contentFormat` as `contentType` if it is not a string,
+/// as do the forms of actions and events for their sdfInputData and
+/// sdfOutputData, respectively.
+///
+/// The Thing Description is returned together with diagnostics about the
+/// parts of the SDF model that have been dropped, approximated, or defaulted.
+pub fn convert_to_thing_description(
+    sdf_model: sdf::SDFModel,
+    options: &BindingOptions,
+) -> Result<Conversion<wot::ThingDescription>> {
+    create_thing_description_from_sdf(sdf_model, options, None)
+}
+
+/// Converts an SDF model into a Thing Description like
+/// [`convert_to_thing_description`], using the information of an SDF mapping
+/// file for overriding the generated forms and Thing-level information.
+///
+/// See [`apply_mapping`] for how the mapping file is applied.
+pub fn convert_to_thing_description_with_mapping(
+    sdf_model: sdf::SDFModel,
+    options: &BindingOptions,
+    mapping: &sdf::SDFMapping,
+) -> Result<Conversion<wot::ThingDescription>> {
+    create_thing_description_from_sdf(sdf_model, options, Some(mapping))
+}
+
+fn create_thing_description_from_sdf(
+    sdf_model: sdf::SDFModel,
+    options: &BindingOptions,
+    mapping: Option<&sdf::SDFMapping>,
+) -> Result<Conversion<wot::ThingDescription>> {
+    let base = Url::parse(&options.base).map_err(|error| {
+        ConversionError::Invalid(format!("Invalid base URI \"{}\": {}", options.base, error))
+    })?;
+    if !options.protocol.uri_schemes().contains(&base.scheme()) {
+        return Err(ConversionError::Unsupported(format!(
+            "The scheme of the base URI \"{}\" does not match the protocol {:?}!",
+            options.base, options.protocol
+        )));
+    }
+
+    let security_name = format!("{}_sc", options.security_scheme);
+    let security_scheme = create_security_scheme(&options.security_scheme)?;
+
+    let sdf_model_json = mapping
+        .map(|_| serde_json::to_value(&sdf_model))
+        .transpose()?;
+
+    let Conversion {
+        output: mut thing_model,
+        mut diagnostics,
+    } = convert_to_thing_model(&sdf_model);
+    thing_model.base_thing.base = Some(options.base.clone());
+    thing_model.security = Some(wot::TypeOrTypeArray::Type(security_name.clone()));
+    thing_model.security_definitions =
+        Some(vec![(security_name, security_scheme)].into_iter().collect());
+
+    add_property_forms(&mut thing_model.properties, options.protocol);
+    add_forms(
+        &mut thing_model.actions,
+        "actions",
+        &[wot::OperationType::Invokeaction],
+        |x| &mut x.interaction_affordance,
+    );
+    add_forms(
+        &mut thing_model.events,
+        "events",
+        &[wot::OperationType::Subscribeevent],
+        |x| &mut x.interaction_affordance,
+    );
+
+    if let (Some(mapping), Some(sdf_model_json)) = (mapping, sdf_model_json) {
+        apply_mapping(&sdf_model_json, &mut thing_model, mapping)?;
+    }
+
+    if thing_model.title.is_none() {
+        thing_model.title = default_title(&sdf_model, &mut diagnostics);
+    }
+
+    translate_units(&mut thing_model, options.units);
+
+    let mut conversion = create_thing_description(thing_model)?;
+    diagnostics.append(&mut conversion.diagnostics);
+    conversion.diagnostics = diagnostics;
+
+    Ok(conversion)
+}
+
+/// Names a Thing Description without an `info` title after the first
+/// top-level sdfThing, sdfProduct, or sdfObject of the SDF model.
+fn default_title(sdf_model: &sdf::SDFModel, diagnostics: &mut Vec<Diagnostic>) -> Option<String> {
+    let title = sdf_model
+        .sdf_thing
+        .iter()
+        .chain(sdf_model.sdf_product.iter())
+        .flat_map(|things| things.keys())
+        .chain(
+            sdf_model
+                .sdf_object
+                .iter()
+                .flat_map(|objects| objects.keys()),
+        )
+        .next()?;
+
+    report(
+        diagnostics,
+        "/info/title",
+        DiagnosticKind::Defaulted,
+        format!(
+            "The SDF model has no title, the Thing Description has been named after \"{}\"",
+            title
+        ),
+    );
+
+    Some(title.clone())
+}
+
+fn create_form(
+    href: &str,
+    operation_types: Vec<wot::OperationType>,
+    content_type: &str,
+    subprotocol: Option<&str>,
+) -> wot::TMForm {
+    let op = match operation_types.len() {
+        1 => wot::TypeOrTypeArray::Type(operation_types[0]),
+        _ => wot::TypeOrTypeArray::Array(operation_types),
+    };
+
+    wot::TMForm {
+        href: Some(href.to_string()),
+        form_fields: wot::Form {
+            op: Some(op),
+            content_type: Some(content_type.to_string()),
+            subprotocol: subprotocol.map(|x| x.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+/// The characters that have to be percent-encoded in a URI path segment.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// Creates the `href` of an affordance relative to the base URI, encoding the
+/// affordance name as a single path segment.
+fn create_href(affordance_type: &str, name: &str) -> String {
+    format!(
+        "{}/{}",
+        affordance_type,
+        utf8_percent_encode(name, PATH_SEGMENT)
+    )
+}
+
+fn add_forms<T>(
+    affordances: &mut Option<IndexMap<String, T>>,
+    affordance_type: &str,
+    operation_types: &[wot::OperationType],
+    get_interaction_affordance: fn(&mut T) -> &mut wot::TMInteractionAffordance,
+) {
+    for (name, affordance) in affordances.iter_mut().flatten() {
+        let href = create_href(affordance_type, name);
+        let forms = get_interaction_affordance(affordance)
+            .forms
+            .get_or_insert_with(Vec::new);
+        let content_type = take_content_type(forms);
+        forms.push(create_form(
+            &href,
+            operation_types.to_vec(),
+            &content_type,
+            None,
+        ));
+    }
+}
+
+/// Removes the forms without an `href`, which only carry the content type of
+/// the payload of an affordance, and returns that content type.
+fn take_content_type(forms: &mut Vec<wot::TMForm>) -> String {
+    let content_type = forms
+        .iter()
+        .find(|form| form.href.is_none())
+        .and_then(|form| form.form_fields.content_type.clone())
+        .unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string());
+    forms.retain(|form| form.href.is_some());
+
+    content_type
+}
+
+fn add_property_forms(
+    properties: &mut Option<IndexMap<String, wot::TMPropertyAffordance>>,
+    protocol: Protocol,
+) {
+    for (name, property) in properties.iter_mut().flatten() {
+        let href = create_href("properties", name);
+        let data_schema = &property.property_affordance_fields.data_schema;
+        let mut operation_types = Vec::new();
+
+        if data_schema.write_only != Some(true) {
+            operation_types.push(wot::OperationType::Readproperty);
+        }
+        if data_schema.read_only != Some(true) {
+            operation_types.push(wot::OperationType::Writeproperty);
+        }
+
+        let observable = property.property_affordance_fields.observable == Some(true);
+        let forms = property
+            .interaction_affordance
+            .forms
+            .get_or_insert_with(Vec::new);
+
+        let content_type = take_content_type(forms);
+
+        if !operation_types.is_empty() {
+            forms.push(create_form(&href, operation_types, &content_type, None));
+        }
+        if observable {
+            forms.push(create_form(
+                &href,
+                vec![wot::OperationType::Observeproperty],
+                &content_type,
+                Some(protocol.observe_subprotocol()),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_href_test() {
+        assert_eq!(create_href("properties", "status"), "properties/status");
+        assert_eq!(
+            create_href("actions", "set level/mode?"),
+            "actions/set%20level%2Fmode%3F"
+        );
+        assert_eq!(create_href("events", "100%#ä"), "events/100%25%23%C3%A4");
+    }
+
+    #[test]
+    fn protocol_from_str_test() {
+        assert_eq!(Protocol::from_str("HTTP").unwrap(), Protocol::Http);
+        assert_eq!(Protocol::from_str("coap").unwrap(), Protocol::Coap);
+        assert!(Protocol::from_str("mqtt").is_err());
+    }
+
+    #[test]
+    fn create_security_scheme_test() {
+        assert!(matches!(
+            create_security_scheme("basic").unwrap(),
+            wot::SecurityScheme::Basic { .. }
+        ));
+        assert_eq!(
+            serde_json::to_value(create_security_scheme("psk").unwrap()).unwrap(),
+            serde_json::json!({"scheme": "psk"})
+        );
+        assert!(create_security_scheme("foobar").is_err());
+    }
+
+    #[test]
+    fn convert_to_thing_description_scheme_mismatch_test() {
+        let options = BindingOptions {
+            base: "http://example.org".to_string(),
+            protocol: Protocol::Coap,
+            security_scheme: "nosec".to_string(),
+            units: UnitVocabulary::Symbol,
+        };
+
+        assert!(convert_to_thing_description(sdf::SDFModel::default(), &options).is_err());
+    }
+
+    #[test]
+    fn convert_to_thing_description_default_title_test() {
+        let options = BindingOptions {
+            base: "http://example.org".to_string(),
+            protocol: Protocol::Http,
+            security_scheme: "nosec".to_string(),
+            units: UnitVocabulary::Symbol,
+        };
+        let sdf_model: sdf::SDFModel = serde_json::from_value(serde_json::json!({
+            "sdfObject": {
+                "lamp": {}
+            }
+        }))
+        .unwrap();
+
+        let conversion = convert_to_thing_description(sdf_model, &options).unwrap();
+        assert_eq!(conversion.output.title, "lamp");
+        assert!(conversion.diagnostics.contains(&Diagnostic::new(
+            "/info/title",
+            DiagnosticKind::Defaulted,
+            "The SDF model has no title, the Thing Description has been named after \"lamp\""
+                .to_string()
+        )));
+    }
+}
@@ -0,0 +1,604 @@
+//! Instantiation of Thing Descriptions from Thing Models.
+//!
+//! Placeholders are substituted on the raw JSON representation of the Thing
+//! Model, as they may occur in places (e.g., a `minimum`) where the typed
+//! definitions only accept values of a different type.
+
+use super::definitions::*;
+use crate::diagnostics::{report, Conversion, Diagnostic, DiagnosticKind};
+use crate::error::from_json_value;
+use crate::json_pointer::{escape_pointer_token, member_pointer};
+use crate::{ConversionError, Result};
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// The title of Thing Descriptions created from Thing Models without a title.
+const DEFAULT_TITLE: &str = "Thing";
+
+const THING_MODEL_TYPE: &str = "tm:ThingModel";
+const DEFAULT_SECURITY_NAME: &str = "nosec_sc";
+
+/// Maps placeholder names (without the surrounding `{{` and `}}`) to the
+/// values they are replaced with.
+pub type PlaceholderMap = HashMap<String, Value>;
+
+/// Instantiates a Thing Description from the JSON representation of a Thing
+/// Model.
+///
+/// All `{{PLACEHOLDER}}`s are substituted with the values from the
+/// `placeholders` map. A string consisting of a single placeholder is replaced
+/// by the value itself (which may be of any JSON type), while placeholders
+/// within longer strings are replaced by a textual representation of the value.
+///
+/// Afterwards, the Thing Model is converted into a Thing Description as
+/// described in [`create_thing_description`].
+///
+/// Returns an error listing all placeholders that could not be resolved and
+/// all other problems preventing the instantiation. Otherwise, the Thing
+/// Description is returned together with diagnostics about the defaults that
+/// have been applied.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::wot::td_from_tm::{instantiate_thing_model, PlaceholderMap};
+/// use serde_json::json;
+///
+/// let thing_model = json!({
+///     "@context": ["https://www.w3.org/2019/wot/td/v1"],
+///     "@type": "tm:ThingModel",
+///     "title": "Lamp {{LAMP_NUMBER}}",
+///     "base": "{{BASE_URI}}",
+///     "properties": {
+///         "status": {
+///             "type": "boolean",
+///             "forms": [{"href": "status"}]
+///         }
+///     }
+/// });
+/// let placeholders: PlaceholderMap = vec![
+///     ("LAMP_NUMBER".to_string(), json!(1)),
+///     ("BASE_URI".to_string(), json!("https://example.org/lamp1/")),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let thing_description = instantiate_thing_model(&thing_model, &placeholders)
+///     .unwrap()
+///     .output;
+///
+/// assert_eq!(thing_description.title, "Lamp 1");
+/// assert!(thing_description.base_thing.r#type.is_none());
+/// ```
+pub fn instantiate_thing_model(
+    thing_model: &Value,
+    placeholders: &PlaceholderMap,
+) -> Result<Conversion<ThingDescription>> {
+    let mut problems = Vec::new();
+    let mut diagnostics = Vec::new();
+    let thing_model = substitute_placeholders(thing_model, placeholders, "", &mut problems);
+
+    match from_json_value::<ThingModel>(thing_model) {
+        Ok(thing_model) => convert_thing_model(thing_model, &mut problems, &mut diagnostics),
+        Err(error) if problems.is_empty() => return Err(error),
+        Err(_) => None,
+    }
+    .filter(|_| problems.is_empty())
+    .map(|x| Conversion::new(x, diagnostics))
+    .ok_or_else(|| create_report(problems))
+}
+
+/// Converts a Thing Model without any placeholders into a Thing Description.
+///
+/// The `tm:ThingModel` type is removed from the `@type`, every affordance is
+/// required to provide at least one form, and all forms and links are required
+/// to provide an `href`. If the Thing Model does not define any
+/// `security`, a `nosec` security scheme is added and reported as defaulted,
+/// and a missing `title` is replaced with a placeholder title.
+///
+/// Returns an error listing all problems preventing the conversion, such as
+/// affordances without forms or forms without an `href`.
+pub fn create_thing_description(thing_model: ThingModel) -> Result<Conversion<ThingDescription>> {
+    let mut problems = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    convert_thing_model(thing_model, &mut problems, &mut diagnostics)
+        .filter(|_| problems.is_empty())
+        .map(|x| Conversion::new(x, diagnostics))
+        .ok_or_else(|| create_report(problems))
+}
+
+fn create_report(mut problems: Vec<String>) -> ConversionError {
+    problems.sort();
+
+    let problem_list: Vec<String> = problems.iter().map(|x| format!("  - {}", x)).collect();
+    ConversionError::Invalid(format!(
+        "Cannot instantiate Thing Model:\n{}",
+        problem_list.join("\n")
+    ))
+}
+
+fn substitute_placeholders(
+    value: &Value,
+    placeholders: &PlaceholderMap,
+    location: &str,
+    problems: &mut Vec<String>,
+) -> Value {
+    match value {
+        Value::String(string) => substitute_string(string, placeholders, location, problems),
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(key, member)| {
+                    let member_location = member_pointer(location, key);
+                    let member =
+                        substitute_placeholders(member, placeholders, &member_location, problems);
+                    (key.clone(), member)
+                })
+                .collect::<Map<String, Value>>(),
+        ),
+        Value::Array(array) => Value::Array(
+            array
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let item_location = format!("{}/{}", location, index);
+                    substitute_placeholders(item, placeholders, &item_location, problems)
+                })
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+fn get_placeholder_value<'a>(
+    name: &str,
+    placeholders: &'a PlaceholderMap,
+    location: &str,
+    problems: &mut Vec<String>,
+) -> Option<&'a Value> {
+    let value = placeholders.get(name);
+
+    if value.is_none() {
+        problems.push(format!(
+            "Unresolved placeholder \"{{{{{}}}}}\" at \"{}\"",
+            name, location
+        ));
+    }
+
+    value
+}
+
+fn substitute_string(
+    string: &str,
+    placeholders: &PlaceholderMap,
+    location: &str,
+    problems: &mut Vec<String>,
+) -> Value {
+    let single_placeholder = string
+        .strip_prefix("{{")
+        .and_then(|x| x.strip_suffix("}}"))
+        .filter(|x| !x.contains("{{") && !x.contains("}}"));
+
+    if let Some(name) = single_placeholder {
+        return get_placeholder_value(name, placeholders, location, problems)
+            .cloned()
+            .unwrap_or_else(|| Value::String(string.to_string()));
+    }
+
+    let mut result = String::new();
+    let mut remainder = string;
+
+    while let Some(start) = remainder.find("{{") {
+        let end = match remainder[start..].find("}}") {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = &remainder[start + 2..end];
+
+        result.push_str(&remainder[..start]);
+        match get_placeholder_value(name, placeholders, location, problems) {
+            Some(Value::String(value)) => result.push_str(value),
+            Some(value) => result.push_str(&value.to_string()),
+            None => result.push_str(&remainder[start..end + 2]),
+        }
+        remainder = &remainder[end + 2..];
+    }
+
+    result.push_str(remainder);
+    Value::String(result)
+}
+
+fn remove_thing_model_type(
+    r#type: Option<TypeOrTypeArray<String>>,
+) -> Option<TypeOrTypeArray<String>> {
+    match r#type {
+        Some(TypeOrTypeArray::Type(r#type)) if r#type == THING_MODEL_TYPE => None,
+        Some(TypeOrTypeArray::Array(types)) => {
+            let types: Vec<String> = types
+                .into_iter()
+                .filter(|x| x != THING_MODEL_TYPE)
+                .collect();
+
+            match types.len() {
+                0 => None,
+                _ => Some(TypeOrTypeArray::Array(types)),
+            }
+        }
+        r#type => r#type,
+    }
+}
+
+fn convert_forms(
+    forms: Option<Vec<TMForm>>,
+    location: &str,
+    problems: &mut Vec<String>,
+) -> Option<Vec<TDForm>> {
+    forms.map(|forms| {
+        forms
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, form)| match form.href {
+                Some(href) => Some(TDForm {
+                    href,
+                    form_fields: form.form_fields,
+                }),
+                None => {
+                    problems.push(format!(
+                        "Form at \"{}/forms/{}\" has no href",
+                        location, index
+                    ));
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+fn convert_links(links: Option<Vec<TMLink>>, problems: &mut Vec<String>) -> Option<Vec<TDLink>> {
+    links.map(|links| {
+        links
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, link)| match link.href {
+                Some(href) => Some(TDLink {
+                    href,
+                    link_fields: link.link_fields,
+                }),
+                None => {
+                    problems.push(format!("Link at \"/links/{}\" has no href", index));
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+fn convert_interaction_affordance(
+    interaction_affordance: TMInteractionAffordance,
+    location: &str,
+    problems: &mut Vec<String>,
+) -> TDInteractionAffordance {
+    if interaction_affordance
+        .forms
+        .as_ref()
+        .is_none_or(|forms| forms.is_empty())
+    {
+        problems.push(format!("Affordance at \"{}\" has no forms", location));
+    }
+
+    TDInteractionAffordance {
+        forms: convert_forms(interaction_affordance.forms, location, problems),
+        interaction_affordance_fields: interaction_affordance.interaction_affordance_fields,
+    }
+}
+
+fn convert_affordances<T, U>(
+    affordances: Option<IndexMap<String, T>>,
+    affordance_type: &str,
+    problems: &mut Vec<String>,
+    convert_affordance: &dyn Fn(T, &str, &mut Vec<String>) -> U,
+) -> Option<IndexMap<String, U>> {
+    affordances.map(|affordances| {
+        affordances
+            .into_iter()
+            .map(|(name, affordance)| {
+                let location = format!("/{}/{}", affordance_type, escape_pointer_token(&name));
+                let affordance = convert_affordance(affordance, &location, problems);
+                (name, affordance)
+            })
+            .collect()
+    })
+}
+
+fn convert_security(
+    thing_model: &mut ThingModel,
+    problems: &mut Vec<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> (TypeOrTypeArray<String>, IndexMap<String, SecurityScheme>) {
+    let security = thing_model.security.take();
+    let mut security_definitions = thing_model.security_definitions.take().unwrap_or_default();
+
+    let security = match security {
+        Some(security) => security,
+        None => {
+            report(
+                diagnostics,
+                "/security",
+                DiagnosticKind::Defaulted,
+                "No security has been defined, a nosec security scheme has been added".to_string(),
+            );
+            security_definitions
+                .entry(DEFAULT_SECURITY_NAME.to_string())
+                .or_insert(SecurityScheme::Nosec {
+                    common: SecuritySchemeCommon::default(),
+                });
+            TypeOrTypeArray::Type(DEFAULT_SECURITY_NAME.to_string())
+        }
+    };
+
+    let security_names = match &security {
+        TypeOrTypeArray::Type(name) => vec![name],
+        TypeOrTypeArray::Array(names) => names.iter().collect(),
+    };
+    for name in security_names {
+        if !security_definitions.contains_key(name) {
+            problems.push(format!(
+                "Security scheme \"{}\" is not defined in securityDefinitions",
+                name
+            ));
+        }
+    }
+
+    (security, security_definitions)
+}
+
+fn convert_thing_model(
+    mut thing_model: ThingModel,
+    problems: &mut Vec<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<ThingDescription> {
+    let (security, security_definitions) =
+        convert_security(&mut thing_model, problems, diagnostics);
+
+    let mut base_thing = thing_model.base_thing;
+    base_thing.r#type = remove_thing_model_type(base_thing.r#type);
+
+    let properties = convert_affordances(
+        thing_model.properties,
+        "properties",
+        problems,
+        &|property: TMPropertyAffordance, location, problems| TDPropertyAffordance {
+            property_affordance_fields: property.property_affordance_fields,
+            interaction_affordance: convert_interaction_affordance(
+                property.interaction_affordance,
+                location,
+                problems,
+            ),
+        },
+    );
+    let actions = convert_affordances(
+        thing_model.actions,
+        "actions",
+        problems,
+        &|action: TMActionAffordance, location, problems| TDActionAffordance {
+            action_affordance_fields: action.action_affordance_fields,
+            interaction_affordance: convert_interaction_affordance(
+                action.interaction_affordance,
+                location,
+                problems,
+            ),
+        },
+    );
+    let events = convert_affordances(
+        thing_model.events,
+        "events",
+        problems,
+        &|event: TMEventAffordance, location, problems| TDEventAffordance {
+            event_affordance_fields: event.event_affordance_fields,
+            interaction_affordance: convert_interaction_affordance(
+                event.interaction_affordance,
+                location,
+                problems,
+            ),
+        },
+    );
+
+    let forms = convert_forms(thing_model.forms, "", problems);
+    let links = convert_links(thing_model.links, problems);
+
+    let title = thing_model.title.unwrap_or_else(|| {
+        report(
+            diagnostics,
+            "/title",
+            DiagnosticKind::Defaulted,
+            format!(
+                "The Thing Model has no title, the title \"{}\" has been used",
+                DEFAULT_TITLE
+            ),
+        );
+        DEFAULT_TITLE.to_string()
+    });
+
+    Some(ThingDescription {
+        base_thing,
+        title,
+        security,
+        security_definitions,
+        links,
+        forms,
+        actions,
+        properties,
+        events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn create_placeholders() -> PlaceholderMap {
+        vec![
+            ("NAME".to_string(), json!("Lamp")),
+            ("MINIMUM".to_string(), json!(5)),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn substitute_placeholders_test() {
+        let mut problems = Vec::new();
+        let value = json!({
+            "title": "{{NAME}} {{MINIMUM}}",
+            "minimum": "{{MINIMUM}}",
+            "description": "{{UNKNOWN}} {{NAME}} {{",
+        });
+
+        let substituted_value =
+            substitute_placeholders(&value, &create_placeholders(), "", &mut problems);
+
+        assert_eq!(
+            substituted_value,
+            json!({
+                "title": "Lamp 5",
+                "minimum": 5,
+                "description": "{{UNKNOWN}} Lamp {{",
+            })
+        );
+        assert_eq!(
+            problems,
+            vec!["Unresolved placeholder \"{{UNKNOWN}}\" at \"/description\"".to_string()]
+        );
+    }
+
+    #[test]
+    fn remove_thing_model_type_test() {
+        assert!(
+            remove_thing_model_type(Some(TypeOrTypeArray::Type(THING_MODEL_TYPE.to_string())))
+                .is_none()
+        );
+
+        let r#type = remove_thing_model_type(Some(TypeOrTypeArray::Array(vec![
+            THING_MODEL_TYPE.to_string(),
+            "saref:LightSwitch".to_string(),
+        ])));
+        match r#type {
+            Some(TypeOrTypeArray::Array(types)) => assert_eq!(types, vec!["saref:LightSwitch"]),
+            _ => panic!("Expected an array of types"),
+        }
+    }
+
+    #[test]
+    fn instantiate_thing_model_report_test() {
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "properties": {
+                "status": {
+                    "type": "integer",
+                    "minimum": "{{UNKNOWN}}",
+                    "forms": [{}]
+                }
+            }
+        });
+
+        let error = instantiate_thing_model(&thing_model, &create_placeholders()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot instantiate Thing Model:\n  \
+            - Form at \"/properties/status/forms/0\" has no href\n  \
+            - Unresolved placeholder \"{{UNKNOWN}}\" at \"/properties/status/minimum\""
+        );
+
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "security": "basic_sc",
+            "properties": {
+                "status": {
+                    "type": "integer",
+                    "forms": [{"href": "status"}, {}]
+                }
+            }
+        });
+
+        let error = instantiate_thing_model(&thing_model, &create_placeholders()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot instantiate Thing Model:\n  \
+            - Form at \"/properties/status/forms/1\" has no href\n  \
+            - Security scheme \"basic_sc\" is not defined in securityDefinitions"
+        );
+
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "title": "Lamp",
+            "properties": {
+                "status": {
+                    "type": "integer"
+                }
+            },
+            "actions": {
+                "toggle": {
+                    "forms": []
+                }
+            }
+        });
+
+        let error = instantiate_thing_model(&thing_model, &create_placeholders()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot instantiate Thing Model:\n  \
+            - Affordance at \"/actions/toggle\" has no forms\n  \
+            - Affordance at \"/properties/status\" has no forms"
+        );
+    }
+
+    #[test]
+    fn instantiate_thing_model_diagnostics_test() {
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "title": "Lamp"
+        });
+
+        let diagnostics = instantiate_thing_model(&thing_model, &create_placeholders())
+            .unwrap()
+            .diagnostics;
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::new(
+                "/security",
+                DiagnosticKind::Defaulted,
+                "No security has been defined, a nosec security scheme has been added".to_string()
+            )]
+        );
+
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "title": "Lamp",
+            "security": "nosec_sc",
+            "securityDefinitions": {"nosec_sc": {"scheme": "nosec"}}
+        });
+
+        let conversion = instantiate_thing_model(&thing_model, &create_placeholders()).unwrap();
+        assert!(conversion.diagnostics.is_empty());
+
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "security": "nosec_sc",
+            "securityDefinitions": {"nosec_sc": {"scheme": "nosec"}}
+        });
+
+        let conversion = instantiate_thing_model(&thing_model, &create_placeholders()).unwrap();
+        assert_eq!(conversion.output.title, "Thing");
+        assert_eq!(
+            conversion.diagnostics,
+            vec![Diagnostic::new(
+                "/title",
+                DiagnosticKind::Defaulted,
+                "The Thing Model has no title, the title \"Thing\" has been used".to_string()
+            )]
+        );
+    }
+}
@@ -1,86 +1,581 @@
 use super::definitions as wot;
+use super::mapping::add_context_prefix;
+use crate::diagnostics::{report, report_dropped, Conversion, Diagnostic, DiagnosticKind};
+use crate::json_pointer::{definition_pointer, member_pointer};
+use crate::sdf::content_format::resolve_media_type;
 use crate::sdf::definitions as sdf;
+use crate::{ConversionError, Result};
+use indexmap::IndexMap;
 use serde_variant::to_variant_name;
-use std::collections::HashMap;
+
+/// The JSON-LD vocabulary for SDF terms used in Thing Models.
+pub(crate) const SDF_VOCABULARY: (&str, &str) = (
+    "sdf",
+    "https://datatracker.ietf.org/doc/html/draft-ietf-asdf-sdf#",
+);
+
+/// The `contentEncoding` of string schemas converted from an SDF `byte-string`.
+pub(crate) const BYTE_STRING_ENCODING: &str = "base64";
+
+/// The semantic annotation of number schemas converted from an SDF `unix-time`.
+pub(crate) const UNIX_TIME_TYPE: &str = "sdf:UnixTime";
+
+/// The unit of number schemas converted from an SDF `unix-time`.
+pub(crate) const UNIX_TIME_UNIT: &str = "s";
+
+/// The prefix of the semantic annotation that carries the name of an sdfChoice
+/// alternative converted into a subschema of a `oneOf`.
+pub(crate) const CHOICE_TYPE_PREFIX: &str = "sdf:choice/";
+
+const TARGET: &str = "a Thing Model";
 
 impl From<sdf::SDFModel> for wot::ThingModel {
     fn from(sdf_model: sdf::SDFModel) -> Self {
-        let mut context_entries: Vec<wot::ContextEntry> = vec![wot::ContextEntry::String(
-            "https://www.w3.org/2019/wot/td/v1".to_string(),
-        )];
-        let r#type = Some(wot::TypeOrTypeArray::<String>::Type("Thing".to_string()));
-        let links;
-
-        let infoblock: &Option<sdf::InfoBlock> = &sdf_model.info;
-        let title: Option<String>;
-        let description: Option<String>;
-        let version: Option<wot::VersionInfo>;
-        match infoblock {
-            None => {
-                title = None;
-                version = None;
-                description = None;
-                links = None;
-            }
-            Some(infoblock) => {
-                title = Some(infoblock.title.clone());
-                let link_fields = wot::Link {
-                    rel: Some("license".to_string()),
-                    r#type: None,
-                    anchor: None,
-                    sizes: None,
-                };
-                version = Some(wot::VersionInfo {
-                    // TODO: Revisit use of "instance" and "model"
-                    instance: infoblock.version.clone(),
-                    model: None,
-                });
-                description = Some(infoblock.copyright.clone());
-                links = Some(vec![wot::TMLink {
-                    link_fields,
-                    href: Some(infoblock.license.clone()),
-                }]);
-            }
-        };
+        convert_to_thing_model(&sdf_model).output
+    }
+}
 
-        if let Some(x) = sdf_model.namespace.clone() {
-            context_entries.push(wot::ContextEntry::Map(x));
-        };
+/// Converts an SDF model into a Thing Model.
+///
+/// The affordances of all sdfObjects and sdfThings are flattened into the
+/// Thing Model, prefixing their names with the names of their parents. The
+/// diagnostics of the conversion refer to the members of the SDF model that
+/// have been dropped, approximated, or defaulted.
+pub fn convert_to_thing_model(sdf_model: &sdf::SDFModel) -> Conversion<wot::ThingModel> {
+    let mut diagnostics = Vec::new();
+    report_model_qualities(&mut diagnostics, sdf_model);
 
-        let base_thing = wot::BaseThing {
-            context: wot::Context::Array(context_entries),
-            description,
-            version,
-
-            // Not covered by SDF yet:
-            r#type,
-            titles: None,
-            descriptions: None,
-            id: None,
-            modified: None,
-            profile: None,
-            schema_definitions: None,
-            base: None,
-            created: None,
-            support: None,
-        };
+    let mut affordances = Affordances::default();
+    affordances.convert(
+        &mut diagnostics,
+        "",
+        &sdf_model.sdf_property,
+        &sdf_model.sdf_action,
+        &sdf_model.sdf_event,
+        None,
+    );
+    convert_object_affordances(
+        &mut affordances,
+        &mut diagnostics,
+        "",
+        &sdf_model.sdf_object,
+        None,
+    );
+    convert_thing_affordances(
+        &mut affordances,
+        &mut diagnostics,
+        "",
+        &sdf_model.sdf_thing,
+        None,
+    );
+
+    Conversion::new(create_thing_model(sdf_model, affordances), diagnostics)
+}
+
+/// The affordances of a Thing Model converted from the definitions of an SDF
+/// model.
+#[derive(Default)]
+struct Affordances {
+    properties: IndexMap<String, wot::TMPropertyAffordance>,
+    actions: IndexMap<String, wot::TMActionAffordance>,
+    events: IndexMap<String, wot::TMEventAffordance>,
+}
+
+impl Affordances {
+    /// Converts the sdfProperties, sdfActions, and sdfEvents of the definition
+    /// `pointer` refers to, prefixing their names with the `prefix`.
+    fn convert(
+        &mut self,
+        diagnostics: &mut Vec<Diagnostic>,
+        pointer: &str,
+        sdf_properties: &Option<IndexMap<String, sdf::PropertyQualities>>,
+        sdf_actions: &Option<IndexMap<String, sdf::ActionQualities>>,
+        sdf_events: &Option<IndexMap<String, sdf::EventQualities>>,
+        prefix: Option<String>,
+    ) {
+        for (key, sdf_property) in sdf_properties.iter().flatten() {
+            let property_pointer = definition_pointer(pointer, "sdfProperty", key);
+            let property = convert_property(sdf_property, diagnostics, &property_pointer);
+            let prefixed_key = get_prefixed_key(prefix.clone(), key.to_string());
+            self.properties.insert(prefixed_key, property);
+        }
+
+        for (key, sdf_action) in sdf_actions.iter().flatten() {
+            let action_pointer = definition_pointer(pointer, "sdfAction", key);
+            let action = convert_action(sdf_action, diagnostics, &action_pointer);
+            let prefixed_key = get_prefixed_key(prefix.clone(), key.to_string());
+            self.actions.insert(prefixed_key, action);
+        }
 
-        wot::ThingModel {
-            base_thing,
+        for (key, sdf_event) in sdf_events.iter().flatten() {
+            let event_pointer = definition_pointer(pointer, "sdfEvent", key);
+            let event = convert_event(sdf_event, diagnostics, &event_pointer);
+            let prefixed_key = get_prefixed_key(prefix.clone(), key.to_string());
+            self.events.insert(prefixed_key, event);
+        }
+    }
+
+    /// Adds the affordances to a Thing Model, omitting empty kinds of
+    /// affordances.
+    fn add_to(self, thing_model: &mut wot::ThingModel) {
+        thing_model.properties = Some(self.properties).filter(|x| !x.is_empty());
+        thing_model.actions = Some(self.actions).filter(|x| !x.is_empty());
+        thing_model.events = Some(self.events).filter(|x| !x.is_empty());
+    }
+}
+
+/// Flattens the affordances of sdfObjects into a Thing Model.
+fn convert_object_affordances(
+    affordances: &mut Affordances,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    sdf_objects: &Option<IndexMap<String, sdf::ObjectQualities>>,
+    prefix: Option<String>,
+) {
+    for (key, sdf_object) in sdf_objects.iter().flatten() {
+        let object_pointer = definition_pointer(pointer, "sdfObject", key);
+        report_flattened(diagnostics, &object_pointer, "sdfObject");
+        report_common_qualities(
+            diagnostics,
+            &sdf_object.common_qualities,
+            &object_pointer,
+            &[],
+        );
+        report_sdf_data(diagnostics, &sdf_object.sdf_data, &object_pointer);
 
-            title,
-            actions: convert_actions(&sdf_model),
-            properties: convert_properties(&sdf_model),
-            events: convert_events(&sdf_model),
-            links,
+        affordances.convert(
+            diagnostics,
+            &object_pointer,
+            &sdf_object.sdf_property,
+            &sdf_object.sdf_action,
+            &sdf_object.sdf_event,
+            Some(get_prefixed_key(prefix.clone(), key.to_string())),
+        );
+    }
+}
+
+/// Flattens the affordances of sdfThings (and of the sdfObjects and sdfThings
+/// they contain) into a Thing Model.
+fn convert_thing_affordances(
+    affordances: &mut Affordances,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    sdf_things: &Option<IndexMap<String, sdf::ThingQualities>>,
+    prefix: Option<String>,
+) {
+    for (key, sdf_thing) in sdf_things.iter().flatten() {
+        let thing_pointer = definition_pointer(pointer, "sdfThing", key);
+        report_flattened(diagnostics, &thing_pointer, "sdfThing");
+        report_common_qualities(
+            diagnostics,
+            &sdf_thing.common_qualities,
+            &thing_pointer,
+            &[],
+        );
+
+        let prefixed_key = Some(get_prefixed_key(prefix.clone(), key.to_string()));
+        convert_thing_affordances(
+            affordances,
+            diagnostics,
+            &thing_pointer,
+            &sdf_thing.sdf_thing,
+            prefixed_key.clone(),
+        );
+        convert_object_affordances(
+            affordances,
+            diagnostics,
+            &thing_pointer,
+            &sdf_thing.sdf_object,
+            prefixed_key,
+        );
+    }
+}
 
-            forms: None,
-            security: None,
-            security_definitions: None,
+fn report_flattened(diagnostics: &mut Vec<Diagnostic>, pointer: &str, quality_name: &str) {
+    report(
+        diagnostics,
+        pointer,
+        DiagnosticKind::Approximated,
+        format!(
+            "The affordances of the {} have been flattened into the Thing Model using prefixed names",
+            quality_name
+        ),
+    );
+}
+
+/// Reports the common qualities of a definition that are dropped, i.e., all
+/// but the `mapped` ones.
+fn report_common_qualities(
+    diagnostics: &mut Vec<Diagnostic>,
+    common_qualities: &sdf::CommonQualities,
+    pointer: &str,
+    mapped: &[&str],
+) {
+    let qualities = [
+        ("label", common_qualities.label.is_some()),
+        ("description", common_qualities.description.is_some()),
+        ("comment", common_qualities.comment.is_some()),
+        ("sdfRef", common_qualities.sdf_ref.is_some()),
+        ("sdfRequired", common_qualities.sdf_required.is_some()),
+    ];
+
+    for (name, is_present) in qualities {
+        if is_present && !mapped.contains(&name) {
+            report_dropped(diagnostics, pointer, name, TARGET);
         }
     }
 }
 
+/// Reports sdfData definitions, which are only converted where they are
+/// referenced using an `sdfRef`.
+fn report_sdf_data(
+    diagnostics: &mut Vec<Diagnostic>,
+    sdf_data: &Option<IndexMap<String, sdf::DataQualities>>,
+    pointer: &str,
+) {
+    if sdf_data.is_some() {
+        report(
+            diagnostics,
+            &member_pointer(pointer, "sdfData"),
+            DiagnosticKind::Dropped,
+            "sdfData definitions are only converted where they are referenced".to_string(),
+        );
+    }
+}
+
+/// Reports the model-level qualities of an SDF model that are not converted
+/// into a Thing Model or only approximated.
+fn report_model_qualities(diagnostics: &mut Vec<Diagnostic>, sdf_model: &sdf::SDFModel) {
+    if sdf_model.info.is_some() {
+        report(
+            diagnostics,
+            "/info/copyright",
+            DiagnosticKind::Approximated,
+            "The copyright notice has been used as the description of the Thing Model".to_string(),
+        );
+    }
+    if sdf_model.default_namespace.is_some() {
+        report_dropped(diagnostics, "", "defaultNamespace", TARGET);
+    }
+    if sdf_model.sdf_product.is_some() {
+        report_dropped(diagnostics, "", "sdfProduct", TARGET);
+    }
+    report_sdf_data(diagnostics, &sdf_model.sdf_data, "");
+}
+
+fn create_context(sdf_model: &sdf::SDFModel) -> wot::Context {
+    let mut context_entries: Vec<wot::ContextEntry> = vec![wot::ContextEntry::String(
+        "https://www.w3.org/2019/wot/td/v1".to_string(),
+    )];
+
+    if let Some(x) = sdf_model.namespace.clone() {
+        context_entries.push(wot::ContextEntry::Map(x));
+    };
+
+    wot::Context::Array(context_entries)
+}
+
+fn is_sdf_term(semantic_type: &serde_json::Value) -> bool {
+    semantic_type
+        .as_str()
+        .is_some_and(|x| x == UNIX_TIME_TYPE || x.starts_with(CHOICE_TYPE_PREFIX))
+}
+
+/// Determines whether a data schema (or one of its subschemas) within a JSON
+/// value is annotated with a semantic type from the SDF vocabulary.
+fn contains_sdf_term(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(object) => object.iter().any(|(key, value)| match key.as_str() {
+            "@type" => match value {
+                serde_json::Value::Array(x) => x.iter().any(is_sdf_term),
+                x => is_sdf_term(x),
+            },
+            _ => contains_sdf_term(value),
+        }),
+        serde_json::Value::Array(array) => array.iter().any(contains_sdf_term),
+        _ => false,
+    }
+}
+
+/// Declares the prefix of the SDF vocabulary in the `@context` of a Thing Model
+/// that uses SDF terms as semantic annotations.
+fn add_sdf_vocabulary(thing_model: &mut wot::ThingModel) {
+    let uses_sdf_terms = serde_json::to_value(&*thing_model).is_ok_and(|x| contains_sdf_term(&x));
+
+    if uses_sdf_terms {
+        add_context_prefix(
+            &mut thing_model.base_thing.context,
+            SDF_VOCABULARY.0,
+            SDF_VOCABULARY.1,
+        );
+    }
+}
+
+fn create_thing_model(sdf_model: &sdf::SDFModel, affordances: Affordances) -> wot::ThingModel {
+    let r#type = Some(wot::TypeOrTypeArray::<String>::Type("Thing".to_string()));
+    let links;
+
+    let infoblock: &Option<sdf::InfoBlock> = &sdf_model.info;
+    let title: Option<String>;
+    let description: Option<String>;
+    let version: Option<wot::VersionInfo>;
+    match infoblock {
+        None => {
+            title = None;
+            version = None;
+            description = None;
+            links = None;
+        }
+        Some(infoblock) => {
+            title = Some(infoblock.title.clone());
+            let link_fields = wot::Link {
+                rel: Some("license".to_string()),
+                ..Default::default()
+            };
+            version = Some(wot::VersionInfo {
+                // TODO: Revisit use of "instance" and "model"
+                instance: infoblock.version.clone(),
+                model: None,
+            });
+            description = Some(infoblock.copyright.clone());
+            links = Some(vec![wot::TMLink {
+                link_fields,
+                href: Some(infoblock.license.clone()),
+            }]);
+        }
+    };
+
+    let base_thing = wot::BaseThing {
+        context: create_context(sdf_model),
+        description,
+        version,
+
+        // Not covered by SDF yet:
+        r#type,
+        titles: None,
+        descriptions: None,
+        id: None,
+        modified: None,
+        profile: None,
+        schema_definitions: None,
+        base: None,
+        created: None,
+        support: None,
+    };
+
+    let mut thing_model = wot::ThingModel {
+        base_thing,
+
+        title,
+        links,
+
+        actions: None,
+        properties: None,
+        events: None,
+        forms: None,
+        security: None,
+        security_definitions: None,
+    };
+    affordances.add_to(&mut thing_model);
+    add_sdf_vocabulary(&mut thing_model);
+    thing_model
+}
+
+/// Converts an SDF model into a set of composed Thing Models.
+///
+/// Every sdfObject and sdfThing is converted into a Thing Model of its own.
+/// The resulting models are referenced by the Thing Model of their parent
+/// (or the top-level Thing Model named `root_name`) using links with the
+/// relation type `tm:submodel`, the `instanceName` of which corresponds to the
+/// key of the sdfObject or sdfThing.
+///
+/// # Return value
+///
+/// A list of document names and Thing Models, starting with the top-level
+/// Thing Model, together with the diagnostics of the conversion. Submodels are
+/// named after their parent model and their key, separated by an underscore.
+///
+/// # Errors
+///
+/// Returns [`ConversionError::Invalid`] if two models would be given the same
+/// name, e.g., an sdfObject `b` of an sdfThing `a` and a top-level sdfObject
+/// `a_b`.
+pub fn convert_to_composed_thing_models(
+    sdf_model: &sdf::SDFModel,
+    root_name: &str,
+) -> Result<Conversion<Vec<(String, wot::ThingModel)>>> {
+    let mut diagnostics = Vec::new();
+    report_model_qualities(&mut diagnostics, sdf_model);
+
+    let mut affordances = Affordances::default();
+    affordances.convert(
+        &mut diagnostics,
+        "",
+        &sdf_model.sdf_property,
+        &sdf_model.sdf_action,
+        &sdf_model.sdf_event,
+        None,
+    );
+    let mut root_model = create_thing_model(sdf_model, affordances);
+
+    let mut submodels: Vec<(String, wot::ThingModel)> = Vec::new();
+    let submodel_links = convert_submodels(
+        sdf_model,
+        (root_name, ""),
+        &sdf_model.sdf_object,
+        &sdf_model.sdf_thing,
+        &mut submodels,
+        &mut diagnostics,
+    );
+    add_links(&mut root_model, submodel_links);
+
+    let mut documents = vec![(root_name.to_string(), root_model)];
+    documents.append(&mut submodels);
+
+    for (index, (name, _)) in documents.iter().enumerate() {
+        if documents[..index].iter().any(|(other, _)| other == name) {
+            return Err(ConversionError::Invalid(format!(
+                "The SDF model would be converted into more than one Thing Model named \"{}\"! \
+                 Rename the sdfObjects and sdfThings whose names are joined into it.",
+                name
+            )));
+        }
+    }
+
+    Ok(Conversion::new(documents, diagnostics))
+}
+
+fn add_links(thing_model: &mut wot::ThingModel, mut links: Vec<wot::TMLink>) {
+    if links.is_empty() {
+        return;
+    }
+
+    match &mut thing_model.links {
+        Some(existing_links) => existing_links.append(&mut links),
+        None => thing_model.links = Some(links),
+    }
+}
+
+fn get_submodel_name(parent_name: &str, key: &str) -> String {
+    format!("{}_{}", parent_name, key)
+}
+
+fn create_submodel_link(submodel_name: &str, instance_name: &str) -> wot::TMLink {
+    let link_fields = wot::Link {
+        rel: Some("tm:submodel".to_string()),
+        r#type: Some("application/tm+json".to_string()),
+        instance_name: Some(instance_name.to_string()),
+        ..Default::default()
+    };
+
+    wot::TMLink {
+        link_fields,
+        href: Some(format!("./{}.tm.json", submodel_name)),
+    }
+}
+
+/// Converts sdfObjects and sdfThings into Thing Models, which are added to
+/// `documents`, and returns the links the parent model uses to reference them.
+///
+/// The `parent` consists of the name of the parent model and the pointer to
+/// the definition it has been converted from.
+fn convert_submodels(
+    sdf_model: &sdf::SDFModel,
+    parent: (&str, &str),
+    sdf_objects: &Option<IndexMap<String, sdf::ObjectQualities>>,
+    sdf_things: &Option<IndexMap<String, sdf::ThingQualities>>,
+    documents: &mut Vec<(String, wot::ThingModel)>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<wot::TMLink> {
+    let (parent_name, parent_pointer) = parent;
+    let mut links: Vec<wot::TMLink> = Vec::new();
+
+    for (key, sdf_object) in sdf_objects.iter().flatten() {
+        let submodel_name = get_submodel_name(parent_name, key);
+        let object_pointer = definition_pointer(parent_pointer, "sdfObject", key);
+        let submodel =
+            convert_object_submodel(sdf_model, key, sdf_object, diagnostics, &object_pointer);
+        links.push(create_submodel_link(&submodel_name, key));
+        documents.push((submodel_name, submodel));
+    }
+
+    for (key, sdf_thing) in sdf_things.iter().flatten() {
+        let submodel_name = get_submodel_name(parent_name, key);
+        let thing_pointer = definition_pointer(parent_pointer, "sdfThing", key);
+        let mut submodel = create_submodel(sdf_model, key, &sdf_thing.common_qualities);
+        report_common_qualities(
+            diagnostics,
+            &sdf_thing.common_qualities,
+            &thing_pointer,
+            &["label", "description"],
+        );
+        let mut nested_documents: Vec<(String, wot::ThingModel)> = Vec::new();
+        let submodel_links = convert_submodels(
+            sdf_model,
+            (&submodel_name, &thing_pointer),
+            &sdf_thing.sdf_object,
+            &sdf_thing.sdf_thing,
+            &mut nested_documents,
+            diagnostics,
+        );
+        add_links(&mut submodel, submodel_links);
+        links.push(create_submodel_link(&submodel_name, key));
+        documents.push((submodel_name, submodel));
+        documents.append(&mut nested_documents);
+    }
+
+    links
+}
+
+fn create_submodel(
+    sdf_model: &sdf::SDFModel,
+    key: &str,
+    common_qualities: &sdf::CommonQualities,
+) -> wot::ThingModel {
+    let mut thing_model = wot::ThingModel::default();
+    thing_model.base_thing.context = create_context(sdf_model);
+    thing_model.base_thing.r#type = Some(wot::TypeOrTypeArray::Type("Thing".to_string()));
+    thing_model.base_thing.description = common_qualities.description.clone();
+    thing_model.title = Some(
+        common_qualities
+            .label
+            .clone()
+            .unwrap_or_else(|| key.to_string()),
+    );
+    thing_model
+}
+
+fn convert_object_submodel(
+    sdf_model: &sdf::SDFModel,
+    key: &str,
+    sdf_object: &sdf::ObjectQualities,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> wot::ThingModel {
+    report_common_qualities(
+        diagnostics,
+        &sdf_object.common_qualities,
+        pointer,
+        &["label", "description"],
+    );
+    report_sdf_data(diagnostics, &sdf_object.sdf_data, pointer);
+
+    let mut affordances = Affordances::default();
+    affordances.convert(
+        diagnostics,
+        pointer,
+        &sdf_object.sdf_property,
+        &sdf_object.sdf_action,
+        &sdf_object.sdf_event,
+        None,
+    );
+
+    let mut thing_model = create_submodel(sdf_model, key, &sdf_object.common_qualities);
+    affordances.add_to(&mut thing_model);
+    add_sdf_vocabulary(&mut thing_model);
+    thing_model
+}
+
 fn first_letter_to_uppper_case(s1: &str) -> String {
     let mut c = s1.chars();
     match c.next() {
@@ -89,7 +584,7 @@ fn first_letter_to_uppper_case(s1: &str) -> String {
     }
 }
 
-fn get_prefixed_key(prefix: Option<String>, key: String) -> String {
+pub(crate) fn get_prefixed_key(prefix: Option<String>, key: String) -> String {
     match prefix {
         Some(prefix) => {
             let capitalized_affordance_name = first_letter_to_uppper_case(&key);
@@ -121,22 +616,28 @@ fn create_interaction_affordance(
     }
 }
 
-fn convert_action(sdf_action: &sdf::ActionQualities) -> wot::TMActionAffordance {
-    let input;
-    match &sdf_action.sdf_input_data {
-        None => input = None,
-        Some(input_data) => {
-            input = Some(convert_to_data_schema(input_data));
-        }
-    };
+fn convert_action(
+    sdf_action: &sdf::ActionQualities,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> wot::TMActionAffordance {
+    report_common_qualities(
+        diagnostics,
+        &sdf_action.common_qualities,
+        pointer,
+        &["label", "description"],
+    );
+    report_sdf_data(diagnostics, &sdf_action.sdf_data, pointer);
 
-    let output;
-    match &sdf_action.sdf_output_data {
-        None => output = None,
-        Some(output_data) => {
-            output = Some(convert_to_data_schema(output_data));
-        }
-    };
+    let input = sdf_action.sdf_input_data.as_ref().map(|x| {
+        let input_pointer = member_pointer(pointer, "sdfInputData");
+        convert_to_data_schema(x, diagnostics, &input_pointer, DataContext::Payload)
+    });
+
+    let output = sdf_action.sdf_output_data.as_ref().map(|x| {
+        let output_pointer = member_pointer(pointer, "sdfOutputData");
+        convert_to_data_schema(x, diagnostics, &output_pointer, DataContext::Nested)
+    });
 
     let action_affordance_fields = wot::ActionAffordance {
         input,
@@ -145,27 +646,23 @@ fn convert_action(sdf_action: &sdf::ActionQualities) -> wot::TMActionAffordance
         idempotent: None,
     };
 
+    let mut interaction_affordance = create_interaction_affordance(&sdf_action.common_qualities);
+    interaction_affordance.forms = sdf_action
+        .sdf_input_data
+        .as_ref()
+        .and_then(create_content_type_forms);
+
     wot::TMActionAffordance {
         action_affordance_fields,
-        interaction_affordance: create_interaction_affordance(&sdf_action.common_qualities),
-    }
-}
-
-fn convert_actions(sdf_model: &sdf::SDFModel) -> Option<HashMap<String, wot::TMActionAffordance>> {
-    let mut actions_map: HashMap<String, wot::TMActionAffordance> = HashMap::new();
-
-    convert_sdf_actions(sdf_model, &mut actions_map, &sdf_model.sdf_action, None);
-    convert_sdf_object_actions(sdf_model, &mut actions_map, &sdf_model.sdf_object, None);
-    convert_sdf_thing_actions(sdf_model, &mut actions_map, &sdf_model.sdf_thing, None);
-
-    if !actions_map.is_empty() {
-        Some(actions_map)
-    } else {
-        None
+        interaction_affordance,
     }
 }
 
-fn map_regular_type(sdf_type: &sdf::RegularTypes) -> Option<wot::JSONSchemaTypes> {
+fn map_regular_type(
+    sdf_type: &sdf::RegularTypes,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> Option<wot::JSONSchemaTypes> {
     match sdf_type {
         sdf::RegularTypes::Number(sdf_schema) => {
             let mapping = wot::JSONSchemaTypes::Number(wot::NumberSchema::<f64> {
@@ -189,7 +686,6 @@ fn map_regular_type(sdf_type: &sdf::RegularTypes) -> Option<wot::JSONSchemaTypes
         }
         sdf::RegularTypes::String(sdf_schema) => {
             let mapping = wot::JSONSchemaTypes::String(wot::StringSchema {
-                // TODO: format is not mapped yet
                 min_length: sdf_schema.min_length,
                 max_length: sdf_schema.max_length,
                 pattern: sdf_schema.pattern.clone(),
@@ -199,19 +695,40 @@ fn map_regular_type(sdf_type: &sdf::RegularTypes) -> Option<wot::JSONSchemaTypes
             Some(mapping)
         }
         sdf::RegularTypes::Array(sdf_schema) => {
-            // TODO: Should SDF arrays only specify one data quality?
-            let items;
-            if let Some(array_items) = &sdf_schema.items {
-                let data_schema = array_items.iter().map(convert_to_data_schema).collect();
-                items = Some(Box::new(wot::TypeOrTypeArray::Array::<wot::DataSchema>(
-                    data_schema,
-                )));
-            } else {
-                items = None;
+            let items_pointer = member_pointer(pointer, "items");
+            let items = sdf_schema.items.as_ref().map(|array_items| {
+                Box::new(match array_items {
+                    sdf::ArrayItems::Single(item) => {
+                        wot::TypeOrTypeArray::Type(convert_to_data_schema(
+                            item,
+                            diagnostics,
+                            &items_pointer,
+                            DataContext::Nested,
+                        ))
+                    }
+                    sdf::ArrayItems::Multiple(items) => wot::TypeOrTypeArray::Array(
+                        items
+                            .iter()
+                            .enumerate()
+                            .map(|(index, item)| {
+                                let item_pointer = format!("{}/{}", items_pointer, index);
+                                convert_to_data_schema(
+                                    item,
+                                    diagnostics,
+                                    &item_pointer,
+                                    DataContext::Nested,
+                                )
+                            })
+                            .collect(),
+                    ),
+                })
+            });
+
+            if sdf_schema.unique_items.is_some() {
+                report_dropped(diagnostics, pointer, "uniqueItems", TARGET);
             }
 
             let mapping = wot::JSONSchemaTypes::Array(wot::ArraySchema {
-                // TODO: Can unique_items be mapped?
                 min_items: sdf_schema.min_items,
                 max_items: sdf_schema.max_items,
                 items,
@@ -219,20 +736,25 @@ fn map_regular_type(sdf_type: &sdf::RegularTypes) -> Option<wot::JSONSchemaTypes
             Some(mapping)
         }
         sdf::RegularTypes::Object(sdf_schema) => {
-            let properties;
-            let mut wot_properties: HashMap<String, wot::DataSchema> = HashMap::new();
+            let mut wot_properties: IndexMap<String, wot::DataSchema> = IndexMap::new();
             if let Some(sdf_properties) = &sdf_schema.properties {
                 for (key, data_quality) in sdf_properties {
-                    let data_schema = convert_to_data_schema(data_quality);
+                    let property_pointer = definition_pointer(pointer, "properties", key);
+                    let data_schema = convert_to_data_schema(
+                        data_quality,
+                        diagnostics,
+                        &property_pointer,
+                        DataContext::Nested,
+                    );
                     wot_properties.insert(key.clone(), data_schema);
                 }
             }
 
-            if !wot_properties.is_empty() {
-                properties = Some(wot_properties);
+            let properties = if !wot_properties.is_empty() {
+                Some(wot_properties)
             } else {
-                properties = None;
-            }
+                None
+            };
 
             let mapping = wot::JSONSchemaTypes::Object(wot::ObjectSchema {
                 required: sdf_schema.required.clone(),
@@ -244,16 +766,220 @@ fn map_regular_type(sdf_type: &sdf::RegularTypes) -> Option<wot::JSONSchemaTypes
     }
 }
 
-fn map_data_type(jsonschema: &Option<sdf::Types>) -> Option<wot::JSONSchemaTypes> {
+fn map_data_type(
+    jsonschema: &Option<sdf::Types>,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> Option<wot::JSONSchemaTypes> {
     match jsonschema {
         None => None,
         Some(jsonschema_type) => match jsonschema_type {
-            sdf::Types::Type(regular_type) => map_regular_type(regular_type),
-            sdf::Types::SdfChoice(_) => {
-                // TODO: How should sdfChoice be covered?
-                None
+            sdf::Types::Type(regular_type) => map_regular_type(regular_type, diagnostics, pointer),
+            // sdfChoice is covered by map_sdf_choice
+            sdf::Types::SdfChoice(_) | sdf::Types::Untyped(_) => None,
+        },
+    }
+}
+
+/// Returns the value of an sdfChoice alternative if it only consists of a
+/// constant or, in the case of an empty alternative of string data, its name.
+fn get_choice_constant(
+    name: &str,
+    choice: &sdf::DataQualities,
+    data_type: &Option<wot::JSONSchemaTypes>,
+) -> Option<serde_json::Value> {
+    let mut choice_value = serde_json::to_value(choice).ok()?;
+    let choice_object = choice_value.as_object_mut()?;
+
+    for key in ["description", "label", "comment", "type"] {
+        choice_object.shift_remove(key);
+    }
+
+    let is_string_data = matches!(data_type, None | Some(wot::JSONSchemaTypes::String(_)));
+
+    match choice_object.shift_remove("const") {
+        Some(constant) if choice_object.is_empty() => Some(constant),
+        None if choice_object.is_empty() && choice.jsonschema.is_none() && is_string_data => {
+            Some(serde_json::Value::String(name.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Creates the data type named by the `type` accompanying an sdfChoice.
+fn create_data_type(type_name: &str) -> Option<wot::JSONSchemaTypes> {
+    serde_json::from_value(serde_json::json!({ "type": type_name })).ok()
+}
+
+/// Adds the semantic annotation carrying the name of an sdfChoice alternative
+/// to the `@type` of a data schema.
+fn add_choice_type(data_schema: &mut wot::DataSchema, name: &str) {
+    let choice_type = format!("{}{}", CHOICE_TYPE_PREFIX, name);
+
+    data_schema.r#type = Some(match data_schema.r#type.take() {
+        None => wot::TypeOrTypeArray::Type(choice_type),
+        Some(wot::TypeOrTypeArray::Type(x)) => wot::TypeOrTypeArray::Array(vec![x, choice_type]),
+        Some(wot::TypeOrTypeArray::Array(mut x)) => {
+            x.push(choice_type);
+            wot::TypeOrTypeArray::Array(x)
+        }
+    });
+}
+
+/// Determines the common data type of a list of `enum` values, if there is one.
+fn get_enum_data_type(values: &[serde_json::Value]) -> Option<wot::JSONSchemaTypes> {
+    let data_types: Vec<&str> = values
+        .iter()
+        .map(|value| match value {
+            serde_json::Value::String(_) => Some("string"),
+            serde_json::Value::Bool(_) => Some("boolean"),
+            serde_json::Value::Number(number) if number.is_f64() => Some("number"),
+            serde_json::Value::Number(_) => Some("integer"),
+            _ => None,
+        })
+        .collect::<Option<Vec<&str>>>()?;
+
+    let (first_data_type, other_data_types) = data_types.split_first()?;
+    if other_data_types.iter().any(|x| x != first_data_type) {
+        return None;
+    }
+
+    match *first_data_type {
+        "string" => Some(wot::JSONSchemaTypes::String(wot::StringSchema::default())),
+        "boolean" => Some(wot::JSONSchemaTypes::Boolean),
+        "number" => Some(wot::JSONSchemaTypes::Number(wot::NumberSchema::default())),
+        _ => Some(wot::JSONSchemaTypes::Integer(wot::NumberSchema::default())),
+    }
+}
+
+/// Maps an sdfChoice to an `enum` if all of its alternatives are plain constants
+/// and to a `oneOf` otherwise.
+///
+/// The `type` accompanying the sdfChoice becomes the type of the `enum` or of
+/// the alternatives that do not specify a type of their own.
+///
+/// # Return value
+///
+/// A tuple containing the `type`, the `enum`, and the `oneOf` of the resulting data schema.
+fn map_sdf_choice(
+    sdf_choice: &sdf::SdfChoice,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> (
+    Option<wot::JSONSchemaTypes>,
+    Option<Vec<serde_json::Value>>,
+    Option<Vec<wot::DataSchema>>,
+) {
+    let data_type = sdf_choice.r#type.as_deref().and_then(create_data_type);
+    if sdf_choice.r#type.is_some() && data_type.is_none() {
+        report_dropped(diagnostics, pointer, "type", TARGET);
+    }
+
+    let choices = &sdf_choice.sdf_choice;
+    let choice_pointer = member_pointer(pointer, "sdfChoice");
+
+    let constants: Option<Vec<serde_json::Value>> = choices
+        .iter()
+        .map(|(name, choice)| get_choice_constant(name, choice, &data_type))
+        .collect();
+
+    if let Some(constants) = constants {
+        report(
+            diagnostics,
+            &choice_pointer,
+            DiagnosticKind::Approximated,
+            "The sdfChoice has been converted into an enum of its constant values".to_string(),
+        );
+        for (name, choice) in choices {
+            let alternative_pointer = member_pointer(&choice_pointer, name);
+            report_common_qualities(
+                diagnostics,
+                &choice.common_qualities,
+                &alternative_pointer,
+                &[],
+            );
+        }
+
+        let enum_data_type = data_type.or_else(|| get_enum_data_type(&constants));
+        return (enum_data_type, Some(constants), None);
+    }
+
+    report(
+        diagnostics,
+        &choice_pointer,
+        DiagnosticKind::Approximated,
+        "The sdfChoice has been converted into a oneOf, using the names of its alternatives \
+         as titles and semantic types"
+            .to_string(),
+    );
+
+    let one_of = choices
+        .iter()
+        .map(|(name, choice)| {
+            let alternative_pointer = member_pointer(&choice_pointer, name);
+            let mut data_schema = convert_to_data_schema(
+                choice,
+                diagnostics,
+                &alternative_pointer,
+                DataContext::Choice,
+            );
+            if data_schema.data_type.is_none() {
+                data_schema.data_type = data_type.clone();
             }
+            add_choice_type(&mut data_schema, name);
+            data_schema.title = Some(name.to_string());
+            data_schema.description = choice.common_qualities.description.clone();
+            data_schema
+        })
+        .collect();
+
+    (None, None, Some(one_of))
+}
+
+/// Converts the `const`, `default`, and `enum` qualities of an SDF type into
+/// JSON values.
+fn map_typed_qualities<T: serde::Serialize>(
+    typed_qualities: &sdf::TypedQualities<T>,
+) -> (
+    Option<serde_json::Value>,
+    Option<serde_json::Value>,
+    Option<Vec<serde_json::Value>>,
+) {
+    let to_value = |x: &T| serde_json::to_value(x).ok();
+
+    let r#const = typed_qualities.r#const.as_ref().and_then(to_value);
+    let default = typed_qualities.default.as_ref().and_then(to_value);
+    let r#enum = typed_qualities
+        .r#enum
+        .as_ref()
+        .and_then(|x| x.iter().map(to_value).collect());
+
+    (r#const, default, r#enum)
+}
+
+/// Maps the `const`, `default`, and `enum` qualities of an SDF data quality.
+///
+/// # Return value
+///
+/// A tuple containing the `const`, the `default`, and the `enum` of the resulting data schema.
+fn map_constant_values(
+    jsonschema: &Option<sdf::Types>,
+) -> (
+    Option<serde_json::Value>,
+    Option<serde_json::Value>,
+    Option<Vec<serde_json::Value>>,
+) {
+    match jsonschema {
+        Some(sdf::Types::Type(regular_type)) => match regular_type {
+            sdf::RegularTypes::Number(x) => map_typed_qualities(&x.common_qualities),
+            sdf::RegularTypes::Integer(x) => map_typed_qualities(&x.common_qualities),
+            sdf::RegularTypes::String(x) => map_typed_qualities(&x.common_qualities),
+            sdf::RegularTypes::Boolean(x) => map_typed_qualities(x),
+            sdf::RegularTypes::Array(x) => map_typed_qualities(&x.common_qualities),
+            sdf::RegularTypes::Object(x) => map_typed_qualities(&x.common_qualities),
         },
+        Some(sdf::Types::Untyped(x)) => map_typed_qualities(&x.0),
+        _ => (None, None, None),
     }
 }
 
@@ -274,216 +1000,292 @@ fn map_readable_writable(
 fn map_format(sdf_property: &sdf::DataQualities) -> Option<String> {
     let json_schema = sdf_property.jsonschema.as_ref()?;
     if let sdf::Types::Type(sdf::RegularTypes::String(string_type)) = json_schema {
-        let format = string_type.format.as_ref()?;
-        return to_variant_name(format).map(String::from).ok();
+        return match string_type.format.as_ref()? {
+            sdf::FormatQualities::Other(format) => Some(format.clone()),
+            format => to_variant_name(format).map(String::from).ok(),
+        };
     }
 
     None
 }
 
-fn convert_to_data_schema(sdf_property: &sdf::DataQualities) -> wot::DataSchema {
-    // TODO: How should nullable be mapped?
+/// Maps an SDF `sdfType` to the corresponding annotations of a data schema.
+///
+/// A `byte-string` becomes a string schema with a base64 `contentEncoding`,
+/// while a `unix-time` becomes a number schema annotated with the semantic
+/// type `sdf:UnixTime` and measured in seconds (unless another unit is given).
+/// `sdfType`s that do not match the data type are dropped.
+///
+/// # Return value
+///
+/// The `@type` of the resulting data schema.
+fn map_sdf_type(
+    sdf_type: &Option<sdf::SDFType>,
+    data_type: &mut Option<wot::JSONSchemaTypes>,
+    unit: &mut Option<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> Option<wot::TypeOrTypeArray<String>> {
+    match (sdf_type, data_type) {
+        (Some(sdf::SDFType::ByteString), Some(wot::JSONSchemaTypes::String(string_schema))) => {
+            string_schema.content_encoding = Some(BYTE_STRING_ENCODING.to_string());
+            None
+        }
+        (
+            Some(sdf::SDFType::UnixTime),
+            Some(wot::JSONSchemaTypes::Number(_)) | Some(wot::JSONSchemaTypes::Integer(_)),
+        ) => {
+            unit.get_or_insert_with(|| UNIX_TIME_UNIT.to_string());
+            Some(wot::TypeOrTypeArray::Type(UNIX_TIME_TYPE.to_string()))
+        }
+        (Some(_), _) => {
+            report(
+                diagnostics,
+                &member_pointer(pointer, "sdfType"),
+                DiagnosticKind::Dropped,
+                "The sdfType does not match the type of the data".to_string(),
+            );
+            None
+        }
+        (None, _) => None,
+    }
+}
+
+fn is_string_data(sdf_data: &sdf::DataQualities) -> bool {
+    matches!(
+        sdf_data.jsonschema,
+        Some(sdf::Types::Type(sdf::RegularTypes::String(_)))
+    )
+}
+
+/// Maps the `contentFormat` of string data to the `contentMediaType` of the
+/// string schema. The `contentFormat` of other data is mapped to the forms of
+/// the corresponding affordance instead, which is only possible for
+/// properties, the sdfInputData of actions, and the sdfOutputData of events.
+fn map_content_format(
+    content_format: &Option<String>,
+    data_type: &mut Option<wot::JSONSchemaTypes>,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    context: DataContext,
+) {
+    let content_format = match content_format {
+        Some(content_format) => content_format,
+        None => return,
+    };
+
+    match (resolve_media_type(content_format), data_type) {
+        (Some(media_type), Some(wot::JSONSchemaTypes::String(string_schema))) => {
+            string_schema.content_media_type = Some(media_type.to_string());
+        }
+        (Some(_), _) if matches!(context, DataContext::Property | DataContext::Payload) => (),
+        (Some(_), _) => report_dropped(diagnostics, pointer, "contentFormat", TARGET),
+        (None, _) => report(
+            diagnostics,
+            &member_pointer(pointer, "contentFormat"),
+            DiagnosticKind::Dropped,
+            format!("The content format \"{}\" is unknown", content_format),
+        ),
+    }
+}
+
+/// Creates a form without an `href` that only indicates the media type of the
+/// payload of an affordance, if the `contentFormat` of its data is not
+/// represented by the data schema.
+///
+/// Forms like this are completed when a Thing Description is created.
+fn create_content_type_forms(sdf_data: &sdf::DataQualities) -> Option<Vec<wot::TMForm>> {
+    if is_string_data(sdf_data) {
+        return None;
+    }
+
+    let media_type = sdf_data
+        .content_format
+        .as_deref()
+        .and_then(resolve_media_type)?;
+
+    Some(vec![wot::TMForm {
+        href: None,
+        form_fields: wot::Form {
+            content_type: Some(media_type.to_string()),
+            ..Default::default()
+        },
+    }])
+}
+
+fn create_null_schema() -> wot::DataSchema {
+    wot::DataSchema {
+        data_type: Some(wot::JSONSchemaTypes::Null),
+        ..Default::default()
+    }
+}
+
+/// Allows a data schema to be `null` by combining it with a `null` schema in a
+/// `oneOf`.
+///
+/// Schemas that already consist of a `oneOf` (converted from an sdfChoice)
+/// receive the `null` schema as an additional alternative. Otherwise, the
+/// schema becomes the first alternative, while `readOnly` and `writeOnly`
+/// remain at the outer schema.
+fn make_nullable(mut data_schema: wot::DataSchema) -> wot::DataSchema {
+    if let (Some(one_of), None) = (&mut data_schema.one_of, &data_schema.data_type) {
+        one_of.push(create_null_schema());
+        return data_schema;
+    }
+
+    wot::DataSchema {
+        read_only: data_schema.read_only.take(),
+        write_only: data_schema.write_only.take(),
+        one_of: Some(vec![data_schema, create_null_schema()]),
+        ..Default::default()
+    }
+}
+
+/// The location of a set of data qualities, which determines how they are
+/// mapped.
+#[derive(Clone, Copy, PartialEq)]
+enum DataContext {
+    /// An sdfProperty, whose label, description, and `observable` quality are
+    /// mapped to the property affordance and whose `contentFormat` can be
+    /// indicated by its forms.
+    Property,
+    /// The sdfInputData of an sdfAction or the sdfOutputData of an sdfEvent,
+    /// whose `contentFormat` can be indicated by the forms of the affordance.
+    Payload,
+    /// Other nested data qualities like the sdfOutputData of an sdfAction or
+    /// the items of an array.
+    Nested,
+    /// An alternative of an sdfChoice converted into a subschema of a `oneOf`,
+    /// whose name is used as its title.
+    Choice,
+}
+
+fn convert_to_data_schema(
+    sdf_property: &sdf::DataQualities,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+    context: DataContext,
+) -> wot::DataSchema {
+    let mapped_qualities: &[&str] = match context {
+        DataContext::Property => &["label", "description"],
+        DataContext::Choice => &["description"],
+        DataContext::Payload | DataContext::Nested => &[],
+    };
+    report_common_qualities(
+        diagnostics,
+        &sdf_property.common_qualities,
+        pointer,
+        mapped_qualities,
+    );
+
+    if sdf_property.observable.is_some() && context != DataContext::Property {
+        report_dropped(diagnostics, pointer, "observable", TARGET);
+    }
+    // Data schemas do not allow null by default.
+    if sdf_property.nullable == Some(false) {
+        report_dropped(diagnostics, pointer, "nullable", TARGET);
+    }
+
     let (write_only, read_only) =
         map_readable_writable(sdf_property.readable, sdf_property.writable);
 
     let format = map_format(sdf_property);
 
-    wot::DataSchema {
+    let (r#const, default, typed_enum) = map_constant_values(&sdf_property.jsonschema);
+
+    let (mut data_type, r#enum, one_of) = match &sdf_property.jsonschema {
+        Some(sdf::Types::SdfChoice(sdf_choice)) => map_sdf_choice(sdf_choice, diagnostics, pointer),
+        jsonschema => (
+            map_data_type(jsonschema, diagnostics, pointer),
+            typed_enum,
+            None,
+        ),
+    };
+
+    // SenML unit symbols are kept, see `units::translate_units` for IRIs.
+    let mut unit = sdf_property.unit.clone();
+    let r#type = map_sdf_type(
+        &sdf_property.sdf_type,
+        &mut data_type,
+        &mut unit,
+        diagnostics,
+        pointer,
+    );
+    map_content_format(
+        &sdf_property.content_format,
+        &mut data_type,
+        diagnostics,
+        pointer,
+        context,
+    );
+
+    let data_schema = wot::DataSchema {
         write_only,
         read_only,
         format,
-
-        r#enum: None,  // Still TODO
-        r#const: None, // Still TODO
-        default: None, // Still TODO
-        data_type: map_data_type(&sdf_property.jsonschema),
-        one_of: None, // TODO: Can this be mapped using sdfChoice?
-
-        unit: sdf_property.unit.clone(), // TODO: Check if this kind of mapping is appropriate
+        data_type,
+        r#enum,
+        one_of,
+        r#const,
+        default,
+        unit,
+        r#type,
 
         title: None,       // Set to None to avoid duplication
         description: None, // Set to None to avoid duplication
         titles: None,
         descriptions: None,
-        r#type: None,
+    };
+
+    match sdf_property.nullable {
+        Some(true) => make_nullable(data_schema),
+        _ => data_schema,
     }
 }
 
-fn convert_property(sdf_property: &sdf::PropertyQualities) -> wot::TMPropertyAffordance {
-    // TODO: How should contentFormat be mapped?
-
+fn convert_property(
+    sdf_property: &sdf::PropertyQualities,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> wot::TMPropertyAffordance {
     // TODO: Refactor as sdfProperty is an alias for sdfData
     let property_affordance_fields = wot::PropertyAffordance {
         observable: sdf_property.observable,
 
-        data_schema: convert_to_data_schema(sdf_property),
+        data_schema: convert_to_data_schema(
+            sdf_property,
+            diagnostics,
+            pointer,
+            DataContext::Property,
+        ),
     };
 
+    let mut interaction_affordance = create_interaction_affordance(&sdf_property.common_qualities);
+    interaction_affordance.forms = create_content_type_forms(sdf_property);
+
     wot::TMPropertyAffordance {
         property_affordance_fields,
-        interaction_affordance: create_interaction_affordance(&sdf_property.common_qualities),
+        interaction_affordance,
     }
 }
 
-fn convert_properties(
-    sdf_model: &sdf::SDFModel,
-) -> Option<HashMap<String, wot::TMPropertyAffordance>> {
-    let mut properties: HashMap<String, wot::TMPropertyAffordance> = HashMap::new();
-
-    convert_sdf_properties(sdf_model, &mut properties, &sdf_model.sdf_property, None);
-    convert_sdf_object_properties(sdf_model, &mut properties, &sdf_model.sdf_object, None);
-    convert_sdf_thing_properties(sdf_model, &mut properties, &sdf_model.sdf_thing, None);
-
-    if !properties.is_empty() {
-        Some(properties)
-    } else {
-        None
-    }
-}
-
-macro_rules! create_object_conversion_function {
-    ($wot_type:ty, $sdf_type:ty, $function_name:ident, $function_call:ident, $field_name:ident) => {
-        fn $function_name(
-            _sdf_model: &sdf::SDFModel, // Might be used later for resolving references
-            wot_definitions: &mut HashMap<String, $wot_type>,
-            sdf_definitions: &Option<HashMap<String, $sdf_type>>,
-            prefix: Option<String>,
-        ) -> () {
-            match sdf_definitions {
-                Some(sdf_definitions) => {
-                    for (key, value) in sdf_definitions {
-                        let prefixed_key = get_prefixed_key(prefix.clone(), key.to_string());
-
-                        $function_call(
-                            _sdf_model,
-                            wot_definitions,
-                            &value.$field_name,
-                            Some(prefixed_key),
-                        );
-                    }
-                }
-                None => (),
-            }
-        }
-    };
-}
-
-macro_rules! create_affordance_conversion_function {
-    ($wot_type:ty, $sdf_type:ty, $function_name:ident, $function_call:ident) => {
-        fn $function_name(
-            _sdf_model: &sdf::SDFModel, // Might be used later for resolving references
-            wot_definitions: &mut HashMap<String, $wot_type>,
-            sdf_definitions: &Option<HashMap<String, $sdf_type>>,
-            prefix: Option<String>,
-        ) -> () {
-            match sdf_definitions {
-                Some(sdf_definitions) => {
-                    for (key, value) in sdf_definitions {
-                        let prefixed_key = get_prefixed_key(prefix.clone(), key.to_string());
-                        wot_definitions.insert(prefixed_key, $function_call(&value));
-                    }
-                }
-                None => (),
-            }
-        }
-    };
-}
+fn convert_event(
+    sdf_event: &sdf::EventQualities,
+    diagnostics: &mut Vec<Diagnostic>,
+    pointer: &str,
+) -> wot::TMEventAffordance {
+    report_common_qualities(
+        diagnostics,
+        &sdf_event.common_qualities,
+        pointer,
+        &["label", "description"],
+    );
+    report_sdf_data(diagnostics, &sdf_event.sdf_data, pointer);
 
-create_affordance_conversion_function!(
-    wot::TMPropertyAffordance,
-    sdf::PropertyQualities,
-    convert_sdf_properties,
-    convert_property
-);
-create_affordance_conversion_function!(
-    wot::TMActionAffordance,
-    sdf::ActionQualities,
-    convert_sdf_actions,
-    convert_action
-);
-create_affordance_conversion_function!(
-    wot::TMEventAffordance,
-    sdf::EventQualities,
-    convert_sdf_events,
-    convert_event
-);
-create_object_conversion_function!(
-    wot::TMPropertyAffordance,
-    sdf::ObjectQualities,
-    convert_sdf_object_properties,
-    convert_sdf_properties,
-    sdf_property
-);
-create_object_conversion_function!(
-    wot::TMActionAffordance,
-    sdf::ObjectQualities,
-    convert_sdf_object_actions,
-    convert_sdf_actions,
-    sdf_action
-);
-create_object_conversion_function!(
-    wot::TMEventAffordance,
-    sdf::ObjectQualities,
-    convert_sdf_object_events,
-    convert_sdf_events,
-    sdf_event
-);
-
-macro_rules! create_thing_conversion_function {
-    ($wot_type:ty, $function_name:ident, $object_function:ident) => {
-        fn $function_name(
-            _sdf_model: &sdf::SDFModel, // Might be used later for resolving references
-            wot_definitions: &mut HashMap<String, $wot_type>,
-            sdf_definitions: &Option<HashMap<String, sdf::ThingQualities>>,
-            prefix: Option<String>,
-        ) -> () {
-            match sdf_definitions {
-                None => (),
-                Some(sdf_definitions) => {
-                    for (key, value) in sdf_definitions {
-                        let prefixed_key = get_prefixed_key(prefix.clone(), key.to_string());
-
-                        $function_name(
-                            _sdf_model,
-                            wot_definitions,
-                            &value.sdf_thing,
-                            Some(prefixed_key.clone()),
-                        );
-                        $object_function(
-                            _sdf_model,
-                            wot_definitions,
-                            &value.sdf_object,
-                            Some(prefixed_key),
-                        );
-                    }
-                }
-            }
-        }
-    };
-}
-
-create_thing_conversion_function!(
-    wot::TMActionAffordance,
-    convert_sdf_thing_actions,
-    convert_sdf_object_actions
-);
-create_thing_conversion_function!(
-    wot::TMPropertyAffordance,
-    convert_sdf_thing_properties,
-    convert_sdf_object_properties
-);
-create_thing_conversion_function!(
-    wot::TMEventAffordance,
-    convert_sdf_thing_events,
-    convert_sdf_object_events
-);
-
-fn convert_event(sdf_event: &sdf::EventQualities) -> wot::TMEventAffordance {
-    // TODO: How should sdf_data be mapped?
-    let data = sdf_event
-        .sdf_output_data
-        .as_ref()
-        .map(convert_to_data_schema);
+    let data = sdf_event.sdf_output_data.as_ref().map(|x| {
+        let data_pointer = member_pointer(pointer, "sdfOutputData");
+        convert_to_data_schema(x, diagnostics, &data_pointer, DataContext::Payload)
+    });
 
     let event_affordance_fields = wot::EventAffordance {
         subscription: None, // Still TODO
@@ -491,29 +1293,23 @@ fn convert_event(sdf_event: &sdf::EventQualities) -> wot::TMEventAffordance {
         cancellation: None, // Still TODO
     };
 
+    let mut interaction_affordance = create_interaction_affordance(&sdf_event.common_qualities);
+    interaction_affordance.forms = sdf_event
+        .sdf_output_data
+        .as_ref()
+        .and_then(create_content_type_forms);
+
     wot::TMEventAffordance {
         event_affordance_fields,
-        interaction_affordance: create_interaction_affordance(&sdf_event.common_qualities),
-    }
-}
-
-fn convert_events(sdf_model: &sdf::SDFModel) -> Option<HashMap<String, wot::TMEventAffordance>> {
-    let mut events: HashMap<String, wot::TMEventAffordance> = HashMap::new();
-
-    convert_sdf_events(sdf_model, &mut events, &sdf_model.sdf_event, None);
-    convert_sdf_object_events(sdf_model, &mut events, &sdf_model.sdf_object, None);
-    convert_sdf_thing_events(sdf_model, &mut events, &sdf_model.sdf_thing, None);
-
-    if !events.is_empty() {
-        Some(events)
-    } else {
-        None
+        interaction_affordance,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostics::{report_ignored_members, sort_diagnostics};
+    use serde_json::json;
 
     #[test]
     fn first_letter_to_uppper_case_test() {
@@ -552,4 +1348,330 @@ mod tests {
         let expected_write_only_result_2 = (Some(true), Some(false));
         assert_eq!(write_only_2, expected_write_only_result_2);
     }
+
+    #[test]
+    fn get_enum_data_type_test() {
+        let string_values = vec![serde_json::json!("foo"), serde_json::json!("bar")];
+        let integer_values = vec![serde_json::json!(1), serde_json::json!(2)];
+        let mixed_values = vec![serde_json::json!(1), serde_json::json!("bar")];
+
+        assert!(matches!(
+            get_enum_data_type(&string_values),
+            Some(wot::JSONSchemaTypes::String(_))
+        ));
+        assert!(matches!(
+            get_enum_data_type(&integer_values),
+            Some(wot::JSONSchemaTypes::Integer(_))
+        ));
+        assert!(get_enum_data_type(&mixed_values).is_none());
+        assert!(get_enum_data_type(&[]).is_none());
+    }
+
+    /// Converts the JSON representation of an SDF model like the converter,
+    /// returning the pointers and kinds of the diagnostics in document order.
+    fn get_messages(sdf_model: serde_json::Value, composed: bool) -> Vec<(String, DiagnosticKind)> {
+        let typed_model: sdf::SDFModel = serde_json::from_value(sdf_model.clone()).unwrap();
+        let typed_json = serde_json::to_value(&typed_model).unwrap();
+        let mut diagnostics = Vec::new();
+        report_ignored_members(&mut diagnostics, &sdf_model, &typed_json, "");
+
+        let conversion_diagnostics = match composed {
+            true => {
+                convert_to_composed_thing_models(&typed_model, "root")
+                    .unwrap()
+                    .diagnostics
+            }
+            false => convert_to_thing_model(&typed_model).diagnostics,
+        };
+        diagnostics.extend(conversion_diagnostics);
+        sort_diagnostics(&mut diagnostics, &sdf_model, None);
+
+        diagnostics
+            .into_iter()
+            .map(|x| (x.pointer, x.kind))
+            .collect()
+    }
+
+    fn to_messages(messages: &[(&str, DiagnosticKind)]) -> Vec<(String, DiagnosticKind)> {
+        messages.iter().map(|(x, y)| (x.to_string(), *y)).collect()
+    }
+
+    #[test]
+    fn property_diagnostics_test() {
+        let sdf_model = json!({
+            "sdfProperty": {
+                "foo": {
+                    "label": "Foo",
+                    "type": "string",
+                    "minimum": 5,
+                    "nullable": false,
+                    "contentFormat": "11050",
+                    "sdfType": "unix-time"
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(sdf_model, false),
+            to_messages(&[
+                ("/sdfProperty/foo/minimum", DiagnosticKind::Dropped),
+                ("/sdfProperty/foo/nullable", DiagnosticKind::Dropped),
+                ("/sdfProperty/foo/contentFormat", DiagnosticKind::Dropped),
+                ("/sdfProperty/foo/sdfType", DiagnosticKind::Dropped),
+            ])
+        );
+    }
+
+    #[test]
+    fn content_format_diagnostics_test() {
+        let sdf_model = json!({
+            "sdfProperty": {
+                "foo": {"type": "string", "contentFormat": "0"},
+                "bar": {"type": "integer", "contentFormat": "application/cbor"},
+                "baz": {"type": "string", "contentFormat": "65000"}
+            },
+            "sdfAction": {
+                "qux": {
+                    "sdfInputData": {"type": "string", "contentFormat": "text/csv"},
+                    "sdfOutputData": {"type": "object", "contentFormat": "60"}
+                },
+                "quux": {
+                    "sdfInputData": {"type": "object", "contentFormat": "60"}
+                }
+            },
+            "sdfEvent": {
+                "corge": {
+                    "sdfOutputData": {"type": "array", "contentFormat": "112"}
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(sdf_model, false),
+            to_messages(&[
+                ("/sdfProperty/baz/contentFormat", DiagnosticKind::Dropped),
+                (
+                    "/sdfAction/qux/sdfOutputData/contentFormat",
+                    DiagnosticKind::Dropped
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn sdf_type_diagnostics_test() {
+        let sdf_model = json!({
+            "sdfProperty": {
+                "foo": {"type": "string", "sdfType": "byte-string"},
+                "bar": {"type": "integer", "sdfType": "unix-time"},
+                "baz": {"type": "boolean", "sdfType": "byte-string"}
+            }
+        });
+
+        assert_eq!(
+            get_messages(sdf_model, false),
+            to_messages(&[("/sdfProperty/baz/sdfType", DiagnosticKind::Dropped)])
+        );
+    }
+
+    #[test]
+    fn untyped_property_diagnostics_test() {
+        let sdf_model = json!({
+            "sdfProperty": {
+                "foo": {
+                    "const": 5,
+                    "minimum": 0
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(sdf_model, false),
+            to_messages(&[("/sdfProperty/foo/minimum", DiagnosticKind::Dropped)])
+        );
+    }
+
+    #[test]
+    fn nested_data_diagnostics_test() {
+        let sdf_model = json!({
+            "sdfAction": {
+                "foo": {
+                    "comment": "A comment",
+                    "sdfInputData": {
+                        "label": "Input",
+                        "type": "array",
+                        "uniqueItems": true,
+                        "items": [{"type": "integer", "observable": true}]
+                    }
+                }
+            },
+            "sdfEvent": {
+                "bar": {
+                    "sdfOutputData": {
+                        "type": "array",
+                        "items": {"type": "string", "observable": true}
+                    }
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(sdf_model, false),
+            to_messages(&[
+                ("/sdfAction/foo/comment", DiagnosticKind::Dropped),
+                ("/sdfAction/foo/sdfInputData/label", DiagnosticKind::Dropped),
+                (
+                    "/sdfAction/foo/sdfInputData/uniqueItems",
+                    DiagnosticKind::Dropped
+                ),
+                (
+                    "/sdfAction/foo/sdfInputData/items/0/observable",
+                    DiagnosticKind::Dropped
+                ),
+                (
+                    "/sdfEvent/bar/sdfOutputData/items/observable",
+                    DiagnosticKind::Dropped
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn mismatching_type_diagnostics_test() {
+        let sdf_model = json!({
+            "sdfProperty": {
+                "foo": {"type": "number", "minimum": "abc", "sdfType": "unix-time"},
+                "bar": {"type": "string", "default": "baz", "maxLength": -1},
+                "baz": {
+                    "type": "array",
+                    "items": {"type": "integer", "const": "qux"}
+                },
+                "qux": {"sdfChoice": {"quux": {"type": "foo"}}}
+            }
+        });
+
+        assert_eq!(
+            get_messages(sdf_model, false),
+            to_messages(&[
+                ("/sdfProperty/foo/type", DiagnosticKind::Dropped),
+                ("/sdfProperty/foo/minimum", DiagnosticKind::Dropped),
+                ("/sdfProperty/foo/sdfType", DiagnosticKind::Dropped),
+                ("/sdfProperty/bar/type", DiagnosticKind::Dropped),
+                ("/sdfProperty/bar/maxLength", DiagnosticKind::Dropped),
+                ("/sdfProperty/baz/items/type", DiagnosticKind::Dropped),
+                ("/sdfProperty/qux/sdfChoice", DiagnosticKind::Approximated),
+                (
+                    "/sdfProperty/qux/sdfChoice/quux/type",
+                    DiagnosticKind::Dropped
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn sdf_choice_diagnostics_test() {
+        let enum_model = json!({
+            "sdfProperty": {
+                "foo": {
+                    "sdfChoice": {
+                        "bar": {"label": "Bar"},
+                        "baz": {"const": "baz"}
+                    }
+                }
+            }
+        });
+        let one_of_model = json!({
+            "sdfProperty": {
+                "foo": {
+                    "type": "string",
+                    "sdfChoice": {
+                        "bar": {"label": "Bar", "type": "integer"}
+                    }
+                }
+            }
+        });
+        let typed_model = json!({
+            "sdfProperty": {
+                "foo": {
+                    "type": "integer",
+                    "sdfChoice": {
+                        "bar": {"const": 1},
+                        "baz": {"description": "Baz"}
+                    }
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(enum_model, false),
+            to_messages(&[
+                ("/sdfProperty/foo/sdfChoice", DiagnosticKind::Approximated),
+                (
+                    "/sdfProperty/foo/sdfChoice/bar/label",
+                    DiagnosticKind::Dropped
+                ),
+            ])
+        );
+        assert_eq!(
+            get_messages(one_of_model, false),
+            to_messages(&[
+                ("/sdfProperty/foo/sdfChoice", DiagnosticKind::Approximated),
+                (
+                    "/sdfProperty/foo/sdfChoice/bar/label",
+                    DiagnosticKind::Dropped
+                ),
+            ])
+        );
+        assert_eq!(
+            get_messages(typed_model, false),
+            to_messages(&[("/sdfProperty/foo/sdfChoice", DiagnosticKind::Approximated)])
+        );
+    }
+
+    #[test]
+    fn grouping_diagnostics_test() {
+        let sdf_model = json!({
+            "info": {
+                "title": "Foo",
+                "version": "2022-01-01",
+                "copyright": "Copyright",
+                "license": "MIT",
+                "features": []
+            },
+            "defaultNamespace": "foo",
+            "sdfProduct": {},
+            "sdfThing": {
+                "bar": {
+                    "label": "Bar",
+                    "sdfObject": {
+                        "baz": {
+                            "sdfProperty": {}
+                        }
+                    }
+                }
+            }
+        });
+
+        assert_eq!(
+            get_messages(sdf_model.clone(), false),
+            to_messages(&[
+                ("/info/copyright", DiagnosticKind::Approximated),
+                ("/info/features", DiagnosticKind::Dropped),
+                ("/defaultNamespace", DiagnosticKind::Dropped),
+                ("/sdfProduct", DiagnosticKind::Dropped),
+                ("/sdfThing/bar", DiagnosticKind::Approximated),
+                ("/sdfThing/bar/label", DiagnosticKind::Dropped),
+                ("/sdfThing/bar/sdfObject/baz", DiagnosticKind::Approximated),
+            ])
+        );
+        assert_eq!(
+            get_messages(sdf_model, true),
+            to_messages(&[
+                ("/info/copyright", DiagnosticKind::Approximated),
+                ("/info/features", DiagnosticKind::Dropped),
+                ("/defaultNamespace", DiagnosticKind::Dropped),
+                ("/sdfProduct", DiagnosticKind::Dropped),
+            ])
+        );
+    }
 }
@@ -0,0 +1,577 @@
+//! Resolution of `tm:extends` links and `tm:ref` pointers in Thing Models.
+//!
+//! Like the resolution of `sdfRef`s, resolution is performed on the raw JSON
+//! representation of the Thing Model, as `tm:ref`s are not covered by the typed
+//! definitions.
+
+use crate::json_pointer::member_pointer;
+use crate::sdf::sdf_ref::merge_patch;
+use crate::{ConversionError, Result};
+use serde_json::{Map, Value};
+use url::Url;
+
+const TM_REF: &str = "tm:ref";
+const EXTENDS_RELATION: &str = "tm:extends";
+
+/// Loads the JSON document a `tm:extends` link or a `tm:ref` points to.
+///
+/// The loader is called with the `href` of the link or the part of the `tm:ref`
+/// preceding the `#`, respectively. Relative `href`s of documents loaded
+/// before are resolved against the `href` of the respective document, so that
+/// all `href`s passed to the loader are relative to the Thing Model the
+/// resolution has started from.
+pub type ThingModelLoader<'a> = &'a dyn Fn(&str) -> Result<Value>;
+
+/// Resolves all `tm:extends` links and `tm:ref` pointers of a Thing Model.
+///
+/// Referenced definitions are copied into the referencing definition, which then
+/// overrides them according to JSON Merge Patch
+/// ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)) semantics.
+/// Extended Thing Models are merged into the extending model in the same way,
+/// with the exception of `links`, which are combined.
+/// The `tm:extends` links and `tm:ref`s are removed after resolution.
+///
+/// External documents are retrieved using the `loader`. Returns an error if a
+/// document or definition cannot be found or if a cycle is detected.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::wot::tm_resolver::resolve_thing_model;
+/// use serde_json::json;
+///
+/// let base_model = json!({
+///     "properties": {
+///         "foo": {"type": "integer", "minimum": 0}
+///     }
+/// });
+/// let thing_model = json!({
+///     "links": [{"rel": "tm:extends", "href": "./base.tm.json"}],
+///     "properties": {
+///         "bar": {"tm:ref": "./base.tm.json#/properties/foo", "maximum": 5}
+///     }
+/// });
+/// let loader = |_: &str| Ok(base_model.clone());
+///
+/// let resolved_model = resolve_thing_model(&thing_model, &loader).unwrap();
+///
+/// assert_eq!(
+///     resolved_model,
+///     json!({
+///         "properties": {
+///             "foo": {"type": "integer", "minimum": 0},
+///             "bar": {"type": "integer", "minimum": 0, "maximum": 5}
+///         }
+///     })
+/// );
+/// ```
+pub fn resolve_thing_model(thing_model: &Value, loader: ThingModelLoader) -> Result<Value> {
+    resolve_document(thing_model, "", loader, &mut Vec::new())
+}
+
+/// A loader that does not support loading any external documents.
+pub fn no_loader(href: &str) -> Result<Value> {
+    Err(ConversionError::Reference(format!(
+        "Cannot load \"{}\" as no loader has been provided!",
+        href
+    )))
+}
+
+/// Resolves a (relative) `href` against the `href` of the document containing
+/// it.
+///
+/// Absolute URIs and paths are returned unchanged, as are `href`s found in the
+/// Thing Model the resolution has started from (whose `href` is empty).
+pub(crate) fn resolve_href(document_href: &str, href: &str) -> String {
+    if document_href.is_empty() || href.starts_with('/') || Url::parse(href).is_ok() {
+        return href.to_string();
+    }
+
+    if let Ok(document_url) = Url::parse(document_href) {
+        return document_url
+            .join(href)
+            .map_or_else(|_| href.to_string(), |x| x.to_string());
+    }
+
+    let mut segments: Vec<&str> = document_href.split('/').collect();
+    segments.pop();
+
+    for segment in href.split('/') {
+        match segment {
+            "." => (),
+            ".." if segments.last().is_some_and(|x| !x.is_empty() && *x != "..") => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    segments.join("/")
+}
+
+fn resolve_document(
+    document: &Value,
+    document_href: &str,
+    loader: ThingModelLoader,
+    reference_stack: &mut Vec<String>,
+) -> Result<Value> {
+    let mut resolved_document = resolve_refs(
+        document,
+        document,
+        document_href,
+        "",
+        loader,
+        reference_stack,
+    )?;
+    resolve_extends(
+        &mut resolved_document,
+        document_href,
+        loader,
+        reference_stack,
+    )?;
+    Ok(resolved_document)
+}
+
+fn resolve_refs(
+    document: &Value,
+    value: &Value,
+    document_href: &str,
+    location: &str,
+    loader: ThingModelLoader,
+    reference_stack: &mut Vec<String>,
+) -> Result<Value> {
+    match value {
+        Value::Object(object) => {
+            let mut resolved_object = Map::new();
+
+            for (key, member) in object {
+                if key == TM_REF {
+                    continue;
+                }
+
+                let member_location = member_pointer(location, key);
+                let resolved_member = resolve_refs(
+                    document,
+                    member,
+                    document_href,
+                    &member_location,
+                    loader,
+                    reference_stack,
+                )?;
+                resolved_object.insert(key.clone(), resolved_member);
+            }
+
+            match object.get(TM_REF) {
+                None => Ok(Value::Object(resolved_object)),
+                Some(Value::String(tm_ref)) => {
+                    let mut resolved_target = resolve_ref(
+                        document,
+                        tm_ref,
+                        document_href,
+                        location,
+                        loader,
+                        reference_stack,
+                    )?;
+                    merge_patch(&mut resolved_target, &Value::Object(resolved_object));
+                    Ok(resolved_target)
+                }
+                Some(_) => Err(ConversionError::Reference(format!(
+                    "tm:ref at \"{}\" must be a string!",
+                    location
+                ))),
+            }
+        }
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let item_location = format!("{}/{}", location, index);
+                resolve_refs(
+                    document,
+                    item,
+                    document_href,
+                    &item_location,
+                    loader,
+                    reference_stack,
+                )
+            })
+            .collect::<Result<Vec<Value>>>()
+            .map(Value::Array),
+        _ => Ok(value.clone()),
+    }
+}
+
+fn resolve_ref(
+    document: &Value,
+    tm_ref: &str,
+    document_href: &str,
+    location: &str,
+    loader: ThingModelLoader,
+    reference_stack: &mut Vec<String>,
+) -> Result<Value> {
+    let (target_href, pointer) = match tm_ref.split_once('#') {
+        Some(("", pointer)) => (document_href.to_string(), pointer),
+        Some((target_href, pointer)) => (resolve_href(document_href, target_href), pointer),
+        None => {
+            return Err(ConversionError::Reference(format!(
+                "tm:ref \"{}\" at \"{}\" does not contain a JSON pointer!",
+                tm_ref, location
+            )))
+        }
+    };
+
+    let reference = format!("{}#{}", target_href, pointer);
+    if reference_stack.contains(&reference) {
+        return Err(ConversionError::Reference(format!(
+            "Cyclic tm:ref \"{}\" detected at \"{}\"!",
+            tm_ref, location
+        )));
+    }
+
+    let loaded_document;
+    let target_document = if target_href == document_href {
+        document
+    } else {
+        loaded_document = loader(&target_href)?;
+        &loaded_document
+    };
+
+    let target = target_document.pointer(pointer).ok_or_else(|| {
+        ConversionError::Reference(format!(
+            "tm:ref \"{}\" at \"{}\" does not point to an existing definition!",
+            tm_ref, location
+        ))
+    })?;
+
+    reference_stack.push(reference);
+    let resolved_target = resolve_refs(
+        target_document,
+        target,
+        &target_href,
+        pointer,
+        loader,
+        reference_stack,
+    )?;
+    reference_stack.pop();
+
+    Ok(resolved_target)
+}
+
+fn is_extends_link(link: &Value) -> bool {
+    link.get("rel").and_then(Value::as_str) == Some(EXTENDS_RELATION)
+}
+
+fn take_links(thing_model: &mut Value) -> Vec<Value> {
+    match thing_model
+        .as_object_mut()
+        .and_then(|x| x.shift_remove("links"))
+    {
+        Some(Value::Array(links)) => links,
+        _ => Vec::new(),
+    }
+}
+
+fn resolve_extends(
+    thing_model: &mut Value,
+    document_href: &str,
+    loader: ThingModelLoader,
+    reference_stack: &mut Vec<String>,
+) -> Result<()> {
+    let (extends_links, mut links): (Vec<Value>, Vec<Value>) = take_links(thing_model)
+        .into_iter()
+        .partition(is_extends_link);
+
+    let mut extended_model = Value::Object(Map::new());
+    let mut extended_links: Vec<Value> = Vec::new();
+
+    for link in extends_links {
+        let href = link.get("href").and_then(Value::as_str).ok_or_else(|| {
+            ConversionError::Reference("Encountered a tm:extends link without an href!".to_string())
+        })?;
+        let href = resolve_href(document_href, href);
+
+        if reference_stack.contains(&href) {
+            return Err(ConversionError::Reference(format!(
+                "Cyclic tm:extends link to \"{}\" detected!",
+                href
+            )));
+        }
+
+        let loaded_model = loader(&href)?;
+        reference_stack.push(href.clone());
+        let mut resolved_model = resolve_document(&loaded_model, &href, loader, reference_stack)?;
+        reference_stack.pop();
+
+        extended_links.append(&mut take_links(&mut resolved_model));
+        merge_patch(&mut extended_model, &resolved_model);
+    }
+
+    merge_patch(&mut extended_model, thing_model);
+    *thing_model = extended_model;
+
+    extended_links.append(&mut links);
+    if !extended_links.is_empty() {
+        if let Value::Object(object) = thing_model {
+            object.insert("links".to_string(), Value::Array(extended_links));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_loader(href: &str) -> Result<Value> {
+        match href {
+            "base.tm.json" => Ok(json!({
+                "title": "Base",
+                "links": [
+                    {
+                        "rel": "tm:extends",
+                        "href": "basis.tm.json"
+                    },
+                    {
+                        "rel": "license",
+                        "href": "https://example.org/license"
+                    }
+                ],
+                "properties": {
+                    "foo": {
+                        "type": "integer",
+                        "minimum": 0
+                    }
+                }
+            })),
+            "basis.tm.json" => Ok(json!({
+                "description": "Basis",
+                "actions": {
+                    "toggle": {}
+                }
+            })),
+            "cyclic.tm.json" => Ok(json!({
+                "links": [
+                    {
+                        "rel": "tm:extends",
+                        "href": "cyclic.tm.json"
+                    }
+                ]
+            })),
+            "models/lamp.tm.json" => Ok(json!({
+                "links": [
+                    {
+                        "rel": "tm:extends",
+                        "href": "../base.tm.json"
+                    }
+                ],
+                "properties": {
+                    "level": {
+                        "tm:ref": "common/level.tm.json#/properties/level"
+                    }
+                }
+            })),
+            "models/common/level.tm.json" => Ok(json!({
+                "properties": {
+                    "level": {
+                        "tm:ref": "./range.tm.json#/definitions/range"
+                    }
+                }
+            })),
+            "models/common/range.tm.json" => Ok(json!({
+                "definitions": {
+                    "range": {
+                        "type": "integer",
+                        "maximum": 100
+                    }
+                }
+            })),
+            _ => Err(ConversionError::Io("Not found".to_string())),
+        }
+    }
+
+    #[test]
+    fn resolve_href_test() {
+        assert_eq!(resolve_href("", "a.tm.json"), "a.tm.json");
+        assert_eq!(resolve_href("a.tm.json", "b.tm.json"), "b.tm.json");
+        assert_eq!(resolve_href("x/a.tm.json", "b.tm.json"), "x/b.tm.json");
+        assert_eq!(resolve_href("x/y/a.tm.json", "../b.tm.json"), "x/b.tm.json");
+        assert_eq!(
+            resolve_href("x/a.tm.json", "./y/b.tm.json"),
+            "x/y/b.tm.json"
+        );
+        assert_eq!(resolve_href("a.tm.json", "../b.tm.json"), "../b.tm.json");
+        assert_eq!(
+            resolve_href("../a.tm.json", "../b.tm.json"),
+            "../../b.tm.json"
+        );
+        assert_eq!(resolve_href("x/a.tm.json", "/b.tm.json"), "/b.tm.json");
+        assert_eq!(
+            resolve_href("https://example.org/x/a.tm.json", "../b.tm.json"),
+            "https://example.org/b.tm.json"
+        );
+        assert_eq!(
+            resolve_href("x/a.tm.json", "https://example.org/b.tm.json"),
+            "https://example.org/b.tm.json"
+        );
+    }
+
+    #[test]
+    fn resolve_relative_hrefs_test() {
+        let thing_model = json!({
+            "links": [
+                {
+                    "rel": "tm:extends",
+                    "href": "models/lamp.tm.json"
+                }
+            ]
+        });
+
+        let resolved_model = resolve_thing_model(&thing_model, &test_loader).unwrap();
+
+        assert_eq!(resolved_model["description"], json!("Basis"));
+        assert_eq!(
+            resolved_model["properties"],
+            json!({
+                "foo": {
+                    "type": "integer",
+                    "minimum": 0
+                },
+                "level": {
+                    "type": "integer",
+                    "maximum": 100
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn resolve_extends_test() {
+        let thing_model = json!({
+            "title": "Extending",
+            "links": [
+                {
+                    "rel": "tm:extends",
+                    "href": "base.tm.json"
+                }
+            ],
+            "properties": {
+                "foo": {
+                    "minimum": 5
+                }
+            }
+        });
+
+        let resolved_model = resolve_thing_model(&thing_model, &test_loader).unwrap();
+
+        assert_eq!(
+            resolved_model,
+            json!({
+                "title": "Extending",
+                "description": "Basis",
+                "links": [
+                    {
+                        "rel": "license",
+                        "href": "https://example.org/license"
+                    }
+                ],
+                "properties": {
+                    "foo": {
+                        "type": "integer",
+                        "minimum": 5
+                    }
+                },
+                "actions": {
+                    "toggle": {}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn resolve_local_ref_test() {
+        let thing_model = json!({
+            "properties": {
+                "foo": {
+                    "type": "string",
+                    "readOnly": true
+                },
+                "bar": {
+                    "tm:ref": "#/properties/foo",
+                    "readOnly": false
+                }
+            }
+        });
+
+        let resolved_model = resolve_thing_model(&thing_model, &no_loader).unwrap();
+
+        assert_eq!(
+            resolved_model["properties"]["bar"],
+            json!({"type": "string", "readOnly": false})
+        );
+    }
+
+    #[test]
+    fn resolve_external_ref_test() {
+        let thing_model = json!({
+            "properties": {
+                "bar": {
+                    "tm:ref": "base.tm.json#/properties/foo"
+                }
+            }
+        });
+
+        let resolved_model = resolve_thing_model(&thing_model, &test_loader).unwrap();
+
+        assert_eq!(
+            resolved_model["properties"]["bar"],
+            json!({"type": "integer", "minimum": 0})
+        );
+        assert!(resolve_thing_model(&thing_model, &no_loader).is_err());
+    }
+
+    #[test]
+    fn resolve_cyclic_test() {
+        let cyclic_extends = json!({
+            "links": [
+                {
+                    "rel": "tm:extends",
+                    "href": "cyclic.tm.json"
+                }
+            ]
+        });
+        let cyclic_ref = json!({
+            "properties": {
+                "foo": {
+                    "tm:ref": "#/properties/bar"
+                },
+                "bar": {
+                    "tm:ref": "#/properties/foo"
+                }
+            }
+        });
+
+        assert!(resolve_thing_model(&cyclic_extends, &test_loader)
+            .unwrap_err()
+            .to_string()
+            .starts_with("Cyclic tm:extends"));
+        assert!(resolve_thing_model(&cyclic_ref, &test_loader)
+            .unwrap_err()
+            .to_string()
+            .starts_with("Cyclic tm:ref"));
+    }
+
+    #[test]
+    fn resolve_dangling_ref_test() {
+        let thing_model = json!({
+            "properties": {
+                "foo": {
+                    "tm:ref": "#/properties/bar"
+                }
+            }
+        });
+
+        assert!(resolve_thing_model(&thing_model, &no_loader).is_err());
+    }
+}
@@ -0,0 +1,395 @@
+//! Translation of units between SenML unit symbols and unit IRIs.
+//!
+//! SDF models use the unit symbols of the
+//! [SenML Units registry](https://www.iana.org/assignments/senml/senml.xhtml#senml-units),
+//! which are copied into Thing Models unchanged by default. As many Thing
+//! Descriptions refer to units using IRIs of the
+//! [QUDT](https://qudt.org/) or [OM](http://www.ontology-of-units-of-measure.org/)
+//! vocabularies instead, the units of a Thing Model can be translated into one
+//! of these vocabularies using the table in [`UNITS`]. Units of these
+//! vocabularies are translated back into SenML symbols when converting Thing
+//! Models into SDF models.
+
+use super::definitions as wot;
+use super::mapping::add_context_prefix;
+use crate::{ConversionError, Result};
+use indexmap::IndexMap;
+use std::str::FromStr;
+
+const QUDT_VOCABULARY: (&str, &str) = ("qudt", "http://qudt.org/vocab/unit/");
+const OM_VOCABULARY: (&str, &str) = (
+    "om",
+    "http://www.ontology-of-units-of-measure.org/resource/om-2/",
+);
+
+/// SenML unit symbols and the names of the corresponding units in the QUDT
+/// and OM vocabularies.
+pub const UNITS: &[(&str, &str, &str)] = &[
+    ("m", "M", "metre"),
+    ("kg", "KiloGM", "kilogram"),
+    ("g", "GM", "gram"),
+    ("s", "SEC", "second-Time"),
+    ("A", "A", "ampere"),
+    ("K", "K", "kelvin"),
+    ("cd", "CD", "candela"),
+    ("mol", "MOL", "mole"),
+    ("Hz", "HZ", "hertz"),
+    ("rad", "RAD", "radian"),
+    ("sr", "SR", "steradian"),
+    ("N", "N", "newton"),
+    ("Pa", "PA", "pascal"),
+    ("J", "J", "joule"),
+    ("W", "W", "watt"),
+    ("C", "C", "coulomb"),
+    ("V", "V", "volt"),
+    ("F", "FARAD", "farad"),
+    ("Ohm", "OHM", "ohm"),
+    ("S", "S", "siemens"),
+    ("Wb", "WB", "weber"),
+    ("T", "T", "tesla"),
+    ("H", "H", "henry"),
+    ("Cel", "DEG_C", "degreeCelsius"),
+    ("lm", "LM", "lumen"),
+    ("lx", "LUX", "lux"),
+    ("Bq", "BQ", "becquerel"),
+    ("Gy", "GRAY", "gray"),
+    ("Sv", "SV", "sievert"),
+    ("kat", "KAT", "katal"),
+    ("m2", "M2", "squareMetre"),
+    ("m3", "M3", "cubicMetre"),
+    ("l", "L", "litre"),
+    ("m/s", "M-PER-SEC", "metrePerSecond-Time"),
+    ("m/s2", "M-PER-SEC2", "metrePerSecond-TimeSquared"),
+    ("m3/s", "M3-PER-SEC", "cubicMetrePerSecond-Time"),
+    ("l/s", "L-PER-SEC", "litrePerSecond-Time"),
+    ("W/m2", "W-PER-M2", "wattPerSquareMetre"),
+    ("cd/m2", "CD-PER-M2", "candelaPerSquareMetre"),
+    ("bit", "BIT", "bit"),
+    ("bit/s", "BIT-PER-SEC", "bitPerSecond-Time"),
+    ("B", "BYTE", "byte"),
+    ("1/s", "PER-SEC", "reciprocalSecond-Time"),
+    ("S/m", "S-PER-M", "siemensPerMetre"),
+    ("%", "PERCENT", "percent"),
+    ("dB", "DeciB", "decibel"),
+    ("VA", "V-A", "voltAmpere"),
+    ("Wh", "W-HR", "wattHour"),
+    ("ms", "MilliSEC", "millisecond-Time"),
+    ("min", "MIN", "minute-Time"),
+    ("h", "HR", "hour"),
+    ("MHz", "MegaHZ", "megahertz"),
+    ("kW", "KiloW", "kilowatt"),
+    ("kVA", "KiloV-A", "kilovoltAmpere"),
+    ("Ah", "A-HR", "ampereHour"),
+    ("kWh", "KiloW-HR", "kilowattHour"),
+    ("mm/h", "MilliM-PER-HR", "millimetrePerHour"),
+    ("km/h", "KiloM-PER-HR", "kilometrePerHour"),
+    ("ppm", "PPM", "partsPerMillion"),
+];
+
+/// The vocabulary the units of a Thing Model converted from SDF refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitVocabulary {
+    /// SenML unit symbols like `Cel`, as used by SDF.
+    #[default]
+    Symbol,
+    /// Compact IRIs of QUDT units like `qudt:DEG_C`.
+    Qudt,
+    /// Compact IRIs of OM units like `om:degreeCelsius`.
+    Om,
+}
+
+impl UnitVocabulary {
+    /// Returns the default prefix and the namespace of the unit IRIs.
+    fn namespace(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            UnitVocabulary::Symbol => None,
+            UnitVocabulary::Qudt => Some(QUDT_VOCABULARY),
+            UnitVocabulary::Om => Some(OM_VOCABULARY),
+        }
+    }
+
+    /// Returns the name of a unit within the vocabulary.
+    fn unit_name(&self, unit: &(&'static str, &'static str, &'static str)) -> &'static str {
+        match self {
+            UnitVocabulary::Symbol => unit.0,
+            UnitVocabulary::Qudt => unit.1,
+            UnitVocabulary::Om => unit.2,
+        }
+    }
+}
+
+impl FromStr for UnitVocabulary {
+    type Err = ConversionError;
+
+    fn from_str(vocabulary: &str) -> Result<Self> {
+        match vocabulary.to_lowercase().as_str() {
+            "symbol" => Ok(UnitVocabulary::Symbol),
+            "qudt" => Ok(UnitVocabulary::Qudt),
+            "om" => Ok(UnitVocabulary::Om),
+            _ => Err(ConversionError::Unsupported(format!(
+                "Unsupported unit vocabulary \"{}\"!",
+                vocabulary
+            ))),
+        }
+    }
+}
+
+/// Returns `true` if the URI is the namespace of one of the unit vocabularies.
+pub(crate) fn is_unit_namespace(uri: &str) -> bool {
+    [QUDT_VOCABULARY.1, OM_VOCABULARY.1].contains(&uri)
+}
+
+/// Collects the prefixes defined in the `@context` of a Thing Model.
+fn get_context_prefixes(context: &wot::Context) -> IndexMap<String, String> {
+    let mut prefixes = IndexMap::new();
+
+    if let wot::Context::Array(entries) = context {
+        for entry in entries {
+            if let wot::ContextEntry::Map(map) = entry {
+                prefixes.extend(map.iter().map(|(x, y)| (x.clone(), y.clone())));
+            }
+        }
+    }
+
+    prefixes
+}
+
+fn visit_data_schema(
+    data_schema: &mut wot::DataSchema,
+    visit: &mut dyn FnMut(&mut wot::DataSchema),
+) {
+    visit(data_schema);
+
+    for alternative in data_schema.one_of.iter_mut().flatten() {
+        visit_data_schema(alternative, visit);
+    }
+
+    match &mut data_schema.data_type {
+        Some(wot::JSONSchemaTypes::Array(array_schema)) => {
+            match array_schema.items.as_deref_mut() {
+                Some(wot::TypeOrTypeArray::Type(item)) => visit_data_schema(item, visit),
+                Some(wot::TypeOrTypeArray::Array(items)) => {
+                    for item in items {
+                        visit_data_schema(item, visit);
+                    }
+                }
+                None => (),
+            }
+        }
+        Some(wot::JSONSchemaTypes::Object(object_schema)) => {
+            for property in object_schema.properties.iter_mut().flatten() {
+                visit_data_schema(property.1, visit);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Calls `visit` for every data schema of a Thing Model, including nested
+/// ones.
+fn visit_data_schemas(
+    thing_model: &mut wot::ThingModel,
+    visit: &mut dyn FnMut(&mut wot::DataSchema),
+) {
+    let mut data_schemas: Vec<&mut wot::DataSchema> = Vec::new();
+
+    data_schemas.extend(
+        thing_model
+            .base_thing
+            .schema_definitions
+            .iter_mut()
+            .flat_map(|x| x.values_mut()),
+    );
+
+    for property in thing_model
+        .properties
+        .iter_mut()
+        .flat_map(|x| x.values_mut())
+    {
+        data_schemas.push(&mut property.property_affordance_fields.data_schema);
+        data_schemas.extend(uri_variables(&mut property.interaction_affordance));
+    }
+    for action in thing_model.actions.iter_mut().flat_map(|x| x.values_mut()) {
+        let fields = &mut action.action_affordance_fields;
+        data_schemas.extend(fields.input.iter_mut().chain(fields.output.iter_mut()));
+        data_schemas.extend(uri_variables(&mut action.interaction_affordance));
+    }
+    for event in thing_model.events.iter_mut().flat_map(|x| x.values_mut()) {
+        let fields = &mut event.event_affordance_fields;
+        data_schemas.extend(
+            fields
+                .subscription
+                .iter_mut()
+                .chain(fields.data.iter_mut())
+                .chain(fields.cancellation.iter_mut()),
+        );
+        data_schemas.extend(uri_variables(&mut event.interaction_affordance));
+    }
+
+    for data_schema in data_schemas {
+        visit_data_schema(data_schema, visit);
+    }
+}
+
+fn uri_variables(
+    interaction_affordance: &mut wot::TMInteractionAffordance,
+) -> impl Iterator<Item = &mut wot::DataSchema> {
+    interaction_affordance
+        .interaction_affordance_fields
+        .uri_variables
+        .iter_mut()
+        .flat_map(|x| x.values_mut())
+}
+
+/// Translates the SenML unit symbols of a Thing Model into IRIs of the given
+/// vocabulary.
+///
+/// The IRIs are compacted using the prefix of the vocabulary, which is added
+/// to the `@context` of the Thing Model. If the prefix is already bound to
+/// another namespace, full IRIs are used instead. Units without an entry in
+/// [`UNITS`] are left unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::wot::definitions::ThingModel;
+/// use sdf_wot_converter::wot::units::{translate_units, UnitVocabulary};
+/// use serde_json::json;
+///
+/// let mut thing_model: ThingModel = serde_json::from_value(json!({
+///     "@context": "https://www.w3.org/2022/wot/td/v1.1",
+///     "properties": {"temperature": {"type": "number", "unit": "Cel"}}
+/// }))
+/// .unwrap();
+///
+/// translate_units(&mut thing_model, UnitVocabulary::Qudt);
+///
+/// let thing_model = serde_json::to_value(thing_model).unwrap();
+/// assert_eq!(thing_model["properties"]["temperature"]["unit"], "qudt:DEG_C");
+/// assert_eq!(thing_model["@context"][1]["qudt"], "http://qudt.org/vocab/unit/");
+/// ```
+pub fn translate_units(thing_model: &mut wot::ThingModel, vocabulary: UnitVocabulary) {
+    let (default_prefix, namespace) = match vocabulary.namespace() {
+        Some(vocabulary_namespace) => vocabulary_namespace,
+        None => return,
+    };
+
+    let prefixes = get_context_prefixes(&thing_model.base_thing.context);
+    let prefix = match prefixes.iter().find(|(_, uri)| *uri == namespace) {
+        Some((prefix, _)) => Some(prefix.clone()),
+        None if !prefixes.contains_key(default_prefix) => Some(default_prefix.to_string()),
+        None => None,
+    };
+
+    let mut is_prefix_used = false;
+    visit_data_schemas(thing_model, &mut |data_schema| {
+        let unit = data_schema
+            .unit
+            .as_deref()
+            .and_then(|symbol| UNITS.iter().find(|unit| unit.0 == symbol));
+
+        if let Some(unit) = unit {
+            let name = vocabulary.unit_name(unit);
+            data_schema.unit = Some(match &prefix {
+                Some(prefix) => format!("{}:{}", prefix, name),
+                None => format!("{}{}", namespace, name),
+            });
+            is_prefix_used |= prefix.is_some();
+        }
+    });
+
+    if let (true, Some(prefix)) = (is_prefix_used, prefix) {
+        add_context_prefix(&mut thing_model.base_thing.context, &prefix, namespace);
+    }
+}
+
+/// Translates a unit referring to the QUDT or OM vocabulary into a SenML unit
+/// symbol, using the prefixes of the `@context` for expanding compact IRIs.
+///
+/// Units without a SenML equivalent are returned as full IRIs, while units of
+/// other vocabularies are returned unchanged.
+fn normalize_unit(unit: &str, prefixes: &IndexMap<String, String>) -> String {
+    let iri = match unit.split_once(':') {
+        Some((prefix, name)) if prefixes.contains_key(prefix) => {
+            format!("{}{}", prefixes[prefix], name)
+        }
+        _ => unit.to_string(),
+    };
+
+    for vocabulary in [UnitVocabulary::Qudt, UnitVocabulary::Om] {
+        let namespace = vocabulary.namespace().map(|(_, x)| x).unwrap_or_default();
+        let name = match iri.strip_prefix(namespace) {
+            Some(name) => name,
+            None => continue,
+        };
+
+        return match UNITS.iter().find(|unit| vocabulary.unit_name(unit) == name) {
+            Some(unit) => unit.0.to_string(),
+            None => iri,
+        };
+    }
+
+    unit.to_string()
+}
+
+/// Translates all units of a Thing Model that refer to the QUDT or OM
+/// vocabulary into SenML unit symbols.
+pub(crate) fn normalize_units(thing_model: &mut wot::ThingModel) {
+    let prefixes = get_context_prefixes(&thing_model.base_thing.context);
+
+    visit_data_schemas(thing_model, &mut |data_schema| {
+        if let Some(unit) = &data_schema.unit {
+            data_schema.unit = Some(normalize_unit(unit, &prefixes));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_vocabulary_from_str_test() {
+        assert_eq!(
+            UnitVocabulary::from_str("QUDT").unwrap(),
+            UnitVocabulary::Qudt
+        );
+        assert_eq!(UnitVocabulary::from_str("om").unwrap(), UnitVocabulary::Om);
+        assert!(UnitVocabulary::from_str("ucum").is_err());
+    }
+
+    #[test]
+    fn normalize_unit_test() {
+        let prefixes: IndexMap<String, String> = vec![
+            ("unit".to_string(), QUDT_VOCABULARY.1.to_string()),
+            ("ex".to_string(), "https://example.org/".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(normalize_unit("unit:DEG_C", &prefixes), "Cel");
+        assert_eq!(
+            normalize_unit(
+                "http://www.ontology-of-units-of-measure.org/resource/om-2/kilowattHour",
+                &prefixes
+            ),
+            "kWh"
+        );
+        assert_eq!(
+            normalize_unit("unit:FT", &prefixes),
+            "http://qudt.org/vocab/unit/FT"
+        );
+        assert_eq!(normalize_unit("ex:DEG_C", &prefixes), "ex:DEG_C");
+        assert_eq!(normalize_unit("om:percent", &prefixes), "om:percent");
+        assert_eq!(normalize_unit("Cel", &prefixes), "Cel");
+    }
+
+    #[test]
+    fn unique_units_test() {
+        for (index, unit) in UNITS.iter().enumerate() {
+            assert!(UNITS[index + 1..]
+                .iter()
+                .all(|x| x.0 != unit.0 && x.1 != unit.1 && x.2 != unit.2));
+        }
+    }
+}
@@ -0,0 +1,739 @@
+//! Validation of Thing Descriptions and Thing Models against the assertions of
+//! the [WoT Thing Description](https://www.w3.org/TR/wot-thing-description11/)
+//! specification.
+//!
+//! Validation is performed on the raw JSON representation of the documents,
+//! as Thing Models may contain placeholders in places (e.g., a `minimum`)
+//! where the typed definitions only accept values of a different type.
+
+use crate::json_pointer::{member_pointer, unescape_pointer_token};
+use crate::sdf::Violation;
+use serde_json::{Map, Value};
+
+const TD_CONTEXT_URIS: [&str; 2] = [
+    "https://www.w3.org/2019/wot/td/v1",
+    "https://www.w3.org/2022/wot/td/v1.1",
+];
+const THING_MODEL_TYPE: &str = "tm:ThingModel";
+
+const PROPERTY_OPERATIONS: [&str; 4] = [
+    "readproperty",
+    "writeproperty",
+    "observeproperty",
+    "unobserveproperty",
+];
+const ACTION_OPERATIONS: [&str; 3] = ["invokeaction", "queryaction", "cancelaction"];
+const EVENT_OPERATIONS: [&str; 2] = ["subscribeevent", "unsubscribeevent"];
+const THING_OPERATIONS: [&str; 9] = [
+    "readallproperties",
+    "writeallproperties",
+    "readmultipleproperties",
+    "writemultipleproperties",
+    "observeallproperties",
+    "unobserveallproperties",
+    "queryallactions",
+    "subscribeallevents",
+    "unsubscribeallevents",
+];
+
+/// The kind of document under validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentKind {
+    ThingDescription,
+    ThingModel,
+}
+
+/// The document under validation together with the violations found so far.
+struct Context<'a> {
+    document: &'a Map<String, Value>,
+    kind: DocumentKind,
+    violations: Vec<Violation>,
+}
+
+impl Context<'_> {
+    fn report(&mut self, pointer: &str, message: String) {
+        self.violations.push(Violation::new(pointer, message));
+    }
+
+    fn is_thing_description(&self) -> bool {
+        self.kind == DocumentKind::ThingDescription
+    }
+}
+
+/// Validates the JSON representation of a Thing Description, returning all
+/// violations of the assertions of the TD specification that have been found.
+///
+/// In particular, the validation reports
+///
+/// - a missing TD `@context`, `title`, `security`, or `securityDefinitions`,
+/// - `security` names (of the Thing, its forms, or `combo` schemes) that are
+///   not defined in the `securityDefinitions`,
+/// - forms and links without an `href`,
+/// - form operations (`op`) that do not fit the kind of the affordance, or
+///   contradict a `readOnly` or `writeOnly` property,
+/// - data schemas that are both `readOnly` and `writeOnly` or require
+///   undefined properties, and
+/// - leftover Thing Model placeholders and types.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::wot::validation::validate_thing_description;
+/// use serde_json::json;
+///
+/// let thing_description = json!({
+///     "@context": "https://www.w3.org/2022/wot/td/v1.1",
+///     "title": "Lamp",
+///     "security": "basic_sc",
+///     "securityDefinitions": {"nosec_sc": {"scheme": "nosec"}}
+/// });
+///
+/// let violations = validate_thing_description(&thing_description);
+///
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].pointer, "/security");
+/// ```
+pub fn validate_thing_description(thing_description: &Value) -> Vec<Violation> {
+    validate_document(thing_description, DocumentKind::ThingDescription)
+}
+
+/// Validates the JSON representation of a Thing Model, returning all
+/// violations of the assertions of the TD specification that have been found.
+///
+/// Compared to [`validate_thing_description`], Thing Models have to be of
+/// the type `tm:ThingModel`, may omit the `title`, the security information,
+/// and the `href`s of forms, and may contain placeholders, which have to
+/// follow the `{{PLACEHOLDER}}` syntax. Additionally, all `tm:required`
+/// entries have to point to members of the Thing Model.
+///
+/// # Examples
+///
+/// ```rust
+/// use sdf_wot_converter::wot::validation::validate_thing_model;
+/// use serde_json::json;
+///
+/// let thing_model = json!({
+///     "@context": "https://www.w3.org/2022/wot/td/v1.1",
+///     "@type": "tm:ThingModel",
+///     "title": "Lamp {{LAMP_NUMBER}",
+/// });
+///
+/// let violations = validate_thing_model(&thing_model);
+///
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].pointer, "/title");
+/// ```
+pub fn validate_thing_model(thing_model: &Value) -> Vec<Violation> {
+    validate_document(thing_model, DocumentKind::ThingModel)
+}
+
+fn validate_document(document: &Value, kind: DocumentKind) -> Vec<Violation> {
+    let document = match document.as_object() {
+        Some(document) => document,
+        None => return vec![Violation::new("", "Expected a JSON object".to_string())],
+    };
+    let mut context = Context {
+        document,
+        kind,
+        violations: Vec::new(),
+    };
+
+    validate_context(&mut context);
+    validate_type(&mut context);
+    validate_required_members(&mut context);
+    validate_security(&mut context);
+    validate_forms(&mut context, document, "", &THING_OPERATIONS);
+    validate_links(&mut context);
+    for (keyword, operations) in [
+        ("properties", &PROPERTY_OPERATIONS[..]),
+        ("actions", &ACTION_OPERATIONS[..]),
+        ("events", &EVENT_OPERATIONS[..]),
+    ] {
+        validate_affordances(&mut context, keyword, operations);
+    }
+    if kind == DocumentKind::ThingModel {
+        validate_tm_required(&mut context);
+    }
+    for (key, member) in document {
+        validate_placeholders(&mut context, member, &member_pointer("", key));
+    }
+
+    context.violations
+}
+
+/// Returns the strings of a value that is either a string or an array of
+/// strings, together with their pointers.
+fn string_or_array<'a>(value: &'a Value, pointer: &str) -> Vec<(&'a str, String)> {
+    match value {
+        Value::String(string) => vec![(string.as_str(), pointer.to_string())],
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .filter_map(|(index, x)| Some((x.as_str()?, format!("{}/{}", pointer, index))))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn validate_context(context: &mut Context) {
+    let first_context = match context.document.get("@context") {
+        Some(Value::Array(array)) => array.first(),
+        first_context => first_context,
+    };
+
+    let has_td_context = first_context
+        .and_then(Value::as_str)
+        .is_some_and(|x| TD_CONTEXT_URIS.contains(&x));
+    if !has_td_context {
+        context.report(
+            "/@context",
+            format!(
+                "The @context has to start with one of {}",
+                TD_CONTEXT_URIS.join(", ")
+            ),
+        );
+    }
+}
+
+fn validate_type(context: &mut Context) {
+    let has_thing_model_type = context
+        .document
+        .get("@type")
+        .map(|x| string_or_array(x, "/@type"))
+        .unwrap_or_default()
+        .iter()
+        .any(|(x, _)| *x == THING_MODEL_TYPE);
+
+    match (context.kind, has_thing_model_type) {
+        (DocumentKind::ThingModel, false) => context.report(
+            "/@type",
+            format!("A Thing Model has to be of the type {}", THING_MODEL_TYPE),
+        ),
+        (DocumentKind::ThingDescription, true) => context.report(
+            "/@type",
+            format!(
+                "A Thing Description must not be of the type {}",
+                THING_MODEL_TYPE
+            ),
+        ),
+        _ => (),
+    }
+}
+
+fn validate_required_members(context: &mut Context) {
+    if !context.is_thing_description() {
+        return;
+    }
+
+    for (key, expected_type) in [
+        ("title", "a string"),
+        ("security", "a string or an array of strings"),
+        ("securityDefinitions", "an object"),
+    ] {
+        let is_valid = match (key, context.document.get(key)) {
+            (_, None) => {
+                context.report("", format!("The mandatory member \"{}\" is missing", key));
+                continue;
+            }
+            ("title", Some(value)) => value.is_string(),
+            ("security", Some(value)) => {
+                value.is_string()
+                    || value
+                        .as_array()
+                        .is_some_and(|x| !x.is_empty() && x.iter().all(Value::is_string))
+            }
+            (_, Some(value)) => value.is_object(),
+        };
+        if !is_valid {
+            context.report(
+                &member_pointer("", key),
+                format!("\"{}\" has to be {}", key, expected_type),
+            );
+        }
+    }
+}
+
+/// Reports all security scheme names that are not defined in the
+/// `securityDefinitions`.
+fn validate_security_names(context: &mut Context, security: &Value, pointer: &str) {
+    let definitions = context
+        .document
+        .get("securityDefinitions")
+        .and_then(Value::as_object);
+
+    for (name, name_pointer) in string_or_array(security, pointer) {
+        if !definitions.is_some_and(|x| x.contains_key(name)) {
+            context.report(
+                &name_pointer,
+                format!(
+                    "Security scheme \"{}\" is not defined in the securityDefinitions",
+                    name
+                ),
+            );
+        }
+    }
+}
+
+fn validate_security(context: &mut Context) {
+    let document = context.document;
+
+    if let Some(security) = document.get("security") {
+        validate_security_names(context, security, "/security");
+    }
+
+    let definitions = document
+        .get("securityDefinitions")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten();
+    for (name, definition) in definitions {
+        if definition.get("scheme").and_then(Value::as_str) != Some("combo") {
+            continue;
+        }
+        let definition_pointer = member_pointer("/securityDefinitions", name);
+        for key in ["oneOf", "allOf"] {
+            if let Some(names) = definition.get(key) {
+                validate_security_names(context, names, &member_pointer(&definition_pointer, key));
+            }
+        }
+    }
+}
+
+/// Validates the forms of the Thing or an affordance, which may only use the
+/// given `operations`.
+fn validate_forms(
+    context: &mut Context,
+    object: &Map<String, Value>,
+    pointer: &str,
+    operations: &[&str],
+) {
+    let forms_pointer = member_pointer(pointer, "forms");
+    let forms = object.get("forms").and_then(Value::as_array);
+
+    for (index, form) in forms.into_iter().flatten().enumerate() {
+        let form_pointer = format!("{}/{}", forms_pointer, index);
+        let form = match form.as_object() {
+            Some(form) => form,
+            None => {
+                context.report(&form_pointer, "A form has to be an object".to_string());
+                continue;
+            }
+        };
+
+        match form.get("href") {
+            Some(Value::String(_)) => (),
+            Some(_) => context.report(
+                &member_pointer(&form_pointer, "href"),
+                "The href of a form has to be a string".to_string(),
+            ),
+            None if context.is_thing_description() => {
+                context.report(&form_pointer, "A form has to provide an href".to_string())
+            }
+            None => (),
+        }
+
+        if let Some(security) = form.get("security") {
+            validate_security_names(
+                context,
+                security,
+                &member_pointer(&form_pointer, "security"),
+            );
+        }
+
+        let op_pointer = member_pointer(&form_pointer, "op");
+        for (op, pointer) in form
+            .get("op")
+            .map(|x| string_or_array(x, &op_pointer))
+            .unwrap_or_default()
+        {
+            if !operations.contains(&op) {
+                context.report(
+                    &pointer,
+                    format!(
+                        "Operation \"{}\" is not allowed here, expected one of {}",
+                        op,
+                        operations.join(", ")
+                    ),
+                );
+            } else if op == "writeproperty" && object.get("readOnly") == Some(&Value::Bool(true)) {
+                context.report(
+                    &pointer,
+                    "A readOnly property cannot be written".to_string(),
+                );
+            } else if op == "readproperty" && object.get("writeOnly") == Some(&Value::Bool(true)) {
+                context.report(&pointer, "A writeOnly property cannot be read".to_string());
+            }
+        }
+    }
+}
+
+fn validate_links(context: &mut Context) {
+    let links = context.document.get("links").and_then(Value::as_array);
+
+    for (index, link) in links.into_iter().flatten().enumerate() {
+        let link_pointer = format!("/links/{}", index);
+        match link.get("href") {
+            Some(Value::String(_)) => (),
+            Some(_) => context.report(
+                &member_pointer(&link_pointer, "href"),
+                "The href of a link has to be a string".to_string(),
+            ),
+            None if context.is_thing_description() => {
+                context.report(&link_pointer, "A link has to provide an href".to_string())
+            }
+            None => (),
+        }
+    }
+}
+
+fn validate_affordances(context: &mut Context, keyword: &str, operations: &[&str]) {
+    let affordances = context.document.get(keyword).and_then(Value::as_object);
+    let keyword_pointer = member_pointer("", keyword);
+
+    for (name, affordance) in affordances.into_iter().flatten() {
+        let affordance_pointer = member_pointer(&keyword_pointer, name);
+        let affordance = match affordance.as_object() {
+            Some(affordance) => affordance,
+            None => {
+                context.report(
+                    &affordance_pointer,
+                    "An interaction affordance has to be an object".to_string(),
+                );
+                continue;
+            }
+        };
+
+        let has_forms = affordance
+            .get("forms")
+            .and_then(Value::as_array)
+            .is_some_and(|x| !x.is_empty());
+        if context.is_thing_description() && !has_forms {
+            context.report(
+                &affordance_pointer,
+                "An interaction affordance has to provide at least one form".to_string(),
+            );
+        }
+        validate_forms(context, affordance, &affordance_pointer, operations);
+
+        if keyword == "properties" {
+            validate_data_schema(context, affordance, &affordance_pointer);
+        }
+        for schema in ["input", "output", "subscription", "data", "cancellation"] {
+            if let Some(data_schema) = affordance.get(schema).and_then(Value::as_object) {
+                let schema_pointer = member_pointer(&affordance_pointer, schema);
+                validate_data_schema(context, data_schema, &schema_pointer);
+            }
+        }
+    }
+}
+
+/// Validates a data schema and all data schemas nested within it.
+fn validate_data_schema(context: &mut Context, data_schema: &Map<String, Value>, pointer: &str) {
+    let is_true = |key: &str| data_schema.get(key) == Some(&Value::Bool(true));
+    if is_true("readOnly") && is_true("writeOnly") {
+        context.report(
+            pointer,
+            "A data schema cannot be both readOnly and writeOnly".to_string(),
+        );
+    }
+
+    let properties = data_schema.get("properties").and_then(Value::as_object);
+    if let Some(required) = data_schema.get("required").and_then(Value::as_array) {
+        for (index, name) in required.iter().enumerate() {
+            let is_defined = name
+                .as_str()
+                .is_some_and(|x| properties.is_some_and(|y| y.contains_key(x)));
+            if !is_defined {
+                context.report(
+                    &format!("{}/required/{}", pointer, index),
+                    format!("Required property {} is not defined", name),
+                );
+            }
+        }
+    }
+
+    let properties_pointer = member_pointer(pointer, "properties");
+    for (name, property) in properties.into_iter().flatten() {
+        if let Some(property) = property.as_object() {
+            validate_data_schema(
+                context,
+                property,
+                &member_pointer(&properties_pointer, name),
+            );
+        }
+    }
+
+    let items_pointer = member_pointer(pointer, "items");
+    match data_schema.get("items") {
+        Some(Value::Object(items)) => validate_data_schema(context, items, &items_pointer),
+        Some(Value::Array(items)) => {
+            for (index, item) in items.iter().enumerate() {
+                if let Some(item) = item.as_object() {
+                    validate_data_schema(context, item, &format!("{}/{}", items_pointer, index));
+                }
+            }
+        }
+        _ => (),
+    }
+
+    if let Some(alternatives) = data_schema.get("oneOf").and_then(Value::as_array) {
+        let one_of_pointer = member_pointer(pointer, "oneOf");
+        for (index, alternative) in alternatives.iter().enumerate() {
+            if let Some(alternative) = alternative.as_object() {
+                let alternative_pointer = format!("{}/{}", one_of_pointer, index);
+                validate_data_schema(context, alternative, &alternative_pointer);
+            }
+        }
+    }
+}
+
+/// Resolves a JSON pointer within the members of a document.
+fn resolve_pointer<'a>(document: &'a Map<String, Value>, pointer: &str) -> Option<&'a Value> {
+    let (key, remainder) = match pointer[1..].split_once('/') {
+        Some((key, remainder)) => (key, format!("/{}", remainder)),
+        None => (&pointer[1..], String::new()),
+    };
+    let key = unescape_pointer_token(key);
+
+    document.get(&key)?.pointer(&remainder)
+}
+
+/// Checks that all `tm:required` entries point to members of the Thing Model.
+fn validate_tm_required(context: &mut Context) {
+    let document = context.document;
+    let required = match document.get("tm:required") {
+        Some(Value::Array(required)) => required,
+        Some(_) => {
+            context.report("/tm:required", "tm:required has to be an array".to_string());
+            return;
+        }
+        None => return,
+    };
+
+    for (index, entry) in required.iter().enumerate() {
+        let target = entry.as_str().and_then(|x| x.strip_prefix('#'));
+        if !target.is_some_and(|x| x.starts_with('/') && resolve_pointer(document, x).is_some()) {
+            context.report(
+                &format!("/tm:required/{}", index),
+                format!(
+                    "tm:required entry {} does not point to a member of the Thing Model",
+                    entry
+                ),
+            );
+        }
+    }
+}
+
+/// Returns the names of the placeholders within a string or an error message
+/// if the string contains malformed placeholders.
+fn parse_placeholders(string: &str) -> std::result::Result<Vec<&str>, String> {
+    let mut names = Vec::new();
+    let mut remainder = string;
+
+    while let Some(start) = remainder.find("{{") {
+        let end = remainder[start..]
+            .find("}}")
+            .map(|x| start + x)
+            .ok_or_else(|| format!("Placeholder in \"{}\" is not closed", string))?;
+        let name = &remainder[start + 2..end];
+
+        if name.is_empty() || name.contains('{') || name.contains('}') {
+            return Err(format!("Malformed placeholder \"{{{{{}}}}}\"", name));
+        }
+        names.push(name);
+        remainder = &remainder[end + 2..];
+    }
+
+    if remainder.contains("}}") {
+        return Err(format!("Placeholder in \"{}\" is not opened", string));
+    }
+
+    Ok(names)
+}
+
+fn validate_placeholders(context: &mut Context, value: &Value, pointer: &str) {
+    match value {
+        Value::String(string) => match parse_placeholders(string) {
+            Err(message) => context.report(pointer, message),
+            Ok(names) if context.is_thing_description() => {
+                for name in names {
+                    context.report(
+                        pointer,
+                        format!("Unresolved placeholder \"{{{{{}}}}}\"", name),
+                    );
+                }
+            }
+            Ok(_) => (),
+        },
+        Value::Array(array) => {
+            for (index, item) in array.iter().enumerate() {
+                validate_placeholders(context, item, &format!("{}/{}", pointer, index));
+            }
+        }
+        Value::Object(object) => {
+            for (key, member) in object {
+                validate_placeholders(context, member, &member_pointer(pointer, key));
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_placeholders_test() {
+        assert_eq!(parse_placeholders("foo"), Ok(vec![]));
+        assert_eq!(
+            parse_placeholders("{{FOO}}/{{BAR}}"),
+            Ok(vec!["FOO", "BAR"])
+        );
+        assert!(parse_placeholders("{{FOO").is_err());
+        assert!(parse_placeholders("FOO}}").is_err());
+        assert!(parse_placeholders("{{}}").is_err());
+        assert!(parse_placeholders("{{{FOO}}}").is_err());
+    }
+
+    #[test]
+    fn valid_thing_description_test() {
+        let thing_description = json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1", {"saref": "https://w3id.org/saref#"}],
+            "title": "Lamp",
+            "security": ["basic_sc"],
+            "securityDefinitions": {"basic_sc": {"scheme": "basic"}},
+            "forms": [{"href": "https://example.org/all", "op": "readallproperties"}],
+            "properties": {
+                "status": {
+                    "type": "object",
+                    "readOnly": true,
+                    "required": ["on"],
+                    "properties": {"on": {"type": "boolean"}},
+                    "forms": [{"href": "https://example.org/status", "op": ["readproperty"]}]
+                }
+            },
+            "actions": {
+                "toggle": {
+                    "forms": [{"href": "https://example.org/toggle"}]
+                }
+            }
+        });
+
+        assert_eq!(validate_thing_description(&thing_description), vec![]);
+    }
+
+    #[test]
+    fn thing_description_violations_test() {
+        let thing_description = json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "@type": "tm:ThingModel",
+            "security": "nosec_sc",
+            "securityDefinitions": {
+                "nosec_sc": {"scheme": "nosec"},
+                "combo_sc": {"scheme": "combo", "oneOf": ["nosec_sc", "psk_sc"]}
+            },
+            "links": [{"rel": "item"}],
+            "properties": {
+                "status": {
+                    "type": "boolean",
+                    "readOnly": true,
+                    "writeOnly": true,
+                    "forms": [
+                        {"op": "invokeaction", "security": "basic_sc"},
+                        {"href": "{{BASE}}/status", "op": "writeproperty"}
+                    ]
+                }
+            },
+            "events": {
+                "overheating": {}
+            }
+        });
+
+        assert_eq!(
+            validate_thing_description(&thing_description),
+            vec![
+                Violation::new(
+                    "/@type",
+                    "A Thing Description must not be of the type tm:ThingModel".to_string()
+                ),
+                Violation::new("", "The mandatory member \"title\" is missing".to_string()),
+                Violation::new(
+                    "/securityDefinitions/combo_sc/oneOf/1",
+                    "Security scheme \"psk_sc\" is not defined in the securityDefinitions"
+                        .to_string()
+                ),
+                Violation::new("/links/0", "A link has to provide an href".to_string()),
+                Violation::new(
+                    "/properties/status/forms/0",
+                    "A form has to provide an href".to_string()
+                ),
+                Violation::new(
+                    "/properties/status/forms/0/security",
+                    "Security scheme \"basic_sc\" is not defined in the securityDefinitions"
+                        .to_string()
+                ),
+                Violation::new(
+                    "/properties/status/forms/0/op",
+                    "Operation \"invokeaction\" is not allowed here, expected one of \
+                    readproperty, writeproperty, observeproperty, unobserveproperty"
+                        .to_string()
+                ),
+                Violation::new(
+                    "/properties/status/forms/1/op",
+                    "A readOnly property cannot be written".to_string()
+                ),
+                Violation::new(
+                    "/properties/status",
+                    "A data schema cannot be both readOnly and writeOnly".to_string()
+                ),
+                Violation::new(
+                    "/events/overheating",
+                    "An interaction affordance has to provide at least one form".to_string()
+                ),
+                Violation::new(
+                    "/properties/status/forms/1/href",
+                    "Unresolved placeholder \"{{BASE}}\"".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn thing_model_violations_test() {
+        let thing_model = json!({
+            "@context": ["https://www.w3.org/2022/wot/td/v1.1"],
+            "@type": "Thing",
+            "tm:required": ["#/properties/status", "#/properties/missing"],
+            "description": "{{DESCRIPTION}",
+            "properties": {
+                "status": {
+                    "type": "boolean",
+                    "forms": [{"op": "readproperty"}],
+                    "minimum": "{{MINIMUM}}"
+                }
+            }
+        });
+
+        assert_eq!(
+            validate_thing_model(&thing_model),
+            vec![
+                Violation::new(
+                    "/@type",
+                    "A Thing Model has to be of the type tm:ThingModel".to_string()
+                ),
+                Violation::new(
+                    "/tm:required/1",
+                    "tm:required entry \"#/properties/missing\" does not point to a member \
+                    of the Thing Model"
+                        .to_string()
+                ),
+                Violation::new(
+                    "/description",
+                    "Placeholder in \"{{DESCRIPTION}\" is not closed".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use assert_json_diff::assert_json_include;
-use sdf_wot_converter::converter;
+use sdf_wot_converter::diagnostics::DiagnosticKind;
+use sdf_wot_converter::wot::td_from_sdf::{BindingOptions, Protocol};
+use sdf_wot_converter::wot::units::UnitVocabulary;
+use sdf_wot_converter::{converter, ConversionError};
 use serde_json::json;
+use std::fs;
 
 fn test_sdf_wot_conversion(sdf_input: &str, expected_result: serde_json::Value) {
     let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
-    let result_json: serde_json::Value = serde_json::from_str(result.as_str()).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
 
     assert_json_include!(actual: result_json, expected: expected_result);
 }
@@ -176,7 +180,7 @@ fn test_sdf_tm_event_conversion() {
 
 fn test_wot_tm_sdf_conversion(wot_tm_input: &str, expected_result: serde_json::Value) {
     let result = converter::convert_wot_tm_to_sdf(wot_tm_input.to_string()).unwrap();
-    let result_json: serde_json::Value = serde_json::from_str(result.as_str()).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
 
     assert_json_include!(actual: result_json, expected: expected_result);
 }
@@ -359,3 +363,2184 @@ fn test_tm_sdf_event_conversion() {
 
     test_wot_tm_sdf_conversion(wot_tm_input, expected_sdf_result);
 }
+
+#[test]
+fn test_sdf_tm_sdf_ref_conversion() {
+    let sdf_input = r##"{
+        "sdfObject": {
+            "foo": {
+                "sdfProperty": {
+                    "bar": {
+                        "sdfRef": "#/sdfObject/foo/sdfData/baz",
+                        "label": "Bar",
+                        "maximum": 5
+                    }
+                },
+                "sdfData": {
+                    "baz": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 9000
+                    }
+                }
+            }
+        }
+    }"##;
+    let expected_tm_result = json!(
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "@type": "Thing",
+            "properties": {
+                "fooBar": {
+                    "title": "Bar",
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 5
+                }
+            }
+        }
+    );
+
+    test_sdf_wot_conversion(sdf_input, expected_tm_result);
+}
+
+#[test]
+fn test_sdf_tm_dangling_sdf_ref_conversion() {
+    let sdf_input = r##"{
+        "sdfProperty": {
+            "foo": {
+                "sdfRef": "#/sdfData/bar"
+            }
+        }
+    }"##;
+
+    assert!(matches!(
+        converter::convert_sdf_to_wot_tm(sdf_input.to_string()),
+        Err(ConversionError::Reference(_))
+    ));
+}
+
+#[test]
+fn test_sdf_tm_parse_error_conversion() {
+    let sdf_input = r#"{
+    "info": {
+        "title": 42
+    }
+}"#;
+
+    let error = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap_err();
+
+    match error {
+        ConversionError::Parse {
+            pointer,
+            line,
+            column,
+            ..
+        } => {
+            assert_eq!(pointer.as_deref(), Some("/info/title"));
+            assert_eq!((line, column), (Some(3), Some(18)));
+        }
+        _ => panic!("Expected a parse error, got {:?}", error),
+    }
+}
+
+#[test]
+fn test_schema_error_positions() {
+    let sdf_input = r#"{
+    "sdfProperty": {
+        "foo": {
+            "readable": "yes"
+        }
+    }
+}"#;
+    let tm_input = r#"{
+    "@context": "https://www.w3.org/2022/wot/td/v1.1",
+    "@type": "tm:ThingModel",
+    "title": "Foo",
+    "properties": {
+        "foo": {"readOnly": "yes"}
+    }
+}"#;
+
+    let errors = [
+        converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap_err(),
+        converter::compare_sdf_models(sdf_input.to_string(), sdf_input.to_string()).unwrap_err(),
+        converter::convert_wot_tm_to_sdf(tm_input.to_string()).unwrap_err(),
+    ];
+    let positions: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            ConversionError::Parse { line, column, .. } => (*line, *column),
+            _ => panic!("Expected a parse error, got {:?}", error),
+        })
+        .collect();
+
+    assert_eq!(
+        positions,
+        vec![
+            (Some(4), Some(25)),
+            (Some(4), Some(25)),
+            (Some(6), Some(16))
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_json_conversion() {
+    let error = converter::convert_sdf_to_wot_tm("{\n  \"info\": {,\n}".to_string()).unwrap_err();
+
+    assert!(matches!(
+        error,
+        ConversionError::Parse {
+            line: Some(2),
+            column: Some(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_sdf_tm_sdfobject_level_example_conversion() {
+    let sdf_input = std::fs::read_to_string("examples/sdf/sdfobject-level-refs.sdf.json").unwrap();
+    let expected_tm_result = json!(
+        {
+            "properties": {
+                "LevelCurrentLevel": {
+                    "title": "CurrentLevel",
+                    "type": "number",
+                    "minimum": 0.0,
+                    "maximum": 254.0
+                }
+            },
+            "actions": {
+                "LevelStopwithOnOff": {
+                    "title": "StopwithOnOff",
+                    "input": {
+                        "type": "object"
+                    }
+                }
+            }
+        }
+    );
+
+    test_sdf_wot_conversion(&sdf_input, expected_tm_result);
+}
+
+#[test]
+fn test_sdf_tm_sdf_choice_enum_conversion() {
+    let sdf_input = r#"{
+        "sdfProperty": {
+            "foo": {
+                "type": "integer",
+                "sdfChoice": {
+                    "off": {
+                        "const": 0
+                    },
+                    "on": {
+                        "description": "The on choice.",
+                        "const": 1
+                    }
+                }
+            },
+            "bar": {
+                "type": "string",
+                "sdfChoice": {
+                    "high": {
+                        "description": "The high choice."
+                    },
+                    "low": {
+                        "const": "L"
+                    }
+                }
+            }
+        }
+    }"#;
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    let thing_model: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+
+    assert_eq!(
+        thing_model["properties"]["foo"],
+        json!({"type": "integer", "enum": [0, 1]})
+    );
+    assert_eq!(
+        thing_model["properties"]["bar"],
+        json!({"type": "string", "enum": ["high", "L"]})
+    );
+    assert!(result
+        .diagnostics
+        .iter()
+        .all(|x| !x.pointer.ends_with("/type")));
+}
+
+#[test]
+fn test_sdf_tm_sdf_choice_one_of_conversion() {
+    let sdf_input = r#"{
+        "sdfProperty": {
+            "foo": {
+                "sdfChoice": {
+                    "bar": {
+                        "type": "integer",
+                        "minimum": 5
+                    },
+                    "baz": {
+                        "description": "The baz choice.",
+                        "type": "string"
+                    }
+                }
+            },
+            "qux": {
+                "type": "integer",
+                "sdfChoice": {
+                    "auto": {
+                        "description": "Automatic mode."
+                    },
+                    "manual": {
+                        "const": 1
+                    }
+                }
+            }
+        }
+    }"#;
+    let expected_tm_result = json!(
+        {
+            "@context": [
+                "https://www.w3.org/2019/wot/td/v1",
+                {
+                    "sdf": "https://datatracker.ietf.org/doc/html/draft-ietf-asdf-sdf#"
+                }
+            ],
+            "properties": {
+                "foo": {
+                    "oneOf": [
+                        {
+                            "@type": "sdf:choice/bar",
+                            "title": "bar",
+                            "type": "integer",
+                            "minimum": 5
+                        },
+                        {
+                            "@type": "sdf:choice/baz",
+                            "title": "baz",
+                            "description": "The baz choice.",
+                            "type": "string"
+                        }
+                    ]
+                },
+                "qux": {
+                    "oneOf": [
+                        {
+                            "@type": "sdf:choice/auto",
+                            "title": "auto",
+                            "description": "Automatic mode.",
+                            "type": "integer"
+                        },
+                        {
+                            "@type": "sdf:choice/manual",
+                            "title": "manual",
+                            "type": "integer",
+                            "const": 1
+                        }
+                    ]
+                }
+            }
+        }
+    );
+
+    test_sdf_wot_conversion(sdf_input, expected_tm_result);
+}
+
+#[test]
+fn test_tm_sdf_one_of_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "@type": "Thing",
+            "properties": {
+                "foo": {
+                    "oneOf": [
+                        {
+                            "title": "bar",
+                            "type": "integer",
+                            "minimum": 5
+                        },
+                        {
+                            "type": "boolean"
+                        },
+                        {
+                            "@type": "sdf:choice/qux",
+                            "title": "Qux",
+                            "const": "q"
+                        }
+                    ]
+                }
+            }
+        }
+    "#;
+    let expected_sdf_result = json!({
+        "sdfProperty": {
+            "foo": {
+                "sdfChoice": {
+                    "bar": {
+                        "type": "integer",
+                        "minimum": 5
+                    },
+                    "choice2": {
+                        "type": "boolean"
+                    },
+                    "qux": {
+                        "label": "Qux",
+                        "const": "q"
+                    }
+                }
+            }
+        }
+    });
+
+    test_wot_tm_sdf_conversion(wot_tm_input, expected_sdf_result);
+}
+
+#[test]
+fn test_tm_sdf_enum_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "@type": "Thing",
+            "properties": {
+                "foo": {
+                    "type": "string",
+                    "enum": ["bar", "baz"]
+                },
+                "foobar": {
+                    "type": "integer",
+                    "enum": [1, 2]
+                }
+            }
+        }
+    "#;
+    let expected_sdf_result = json!({
+        "sdfProperty": {
+            "foo": {
+                "type": "string",
+                "sdfChoice": {
+                    "bar": {
+                        "type": "string",
+                        "const": "bar"
+                    },
+                    "baz": {
+                        "type": "string",
+                        "const": "baz"
+                    }
+                }
+            },
+            "foobar": {
+                "type": "integer",
+                "enum": [1, 2]
+            }
+        }
+    });
+
+    test_wot_tm_sdf_conversion(wot_tm_input, expected_sdf_result);
+}
+
+#[test]
+fn test_sdf_tm_constant_values_conversion() {
+    let sdf_input = r#"{
+        "sdfProperty": {
+            "foo": {
+                "type": "integer",
+                "enum": [1, 2, 3],
+                "default": 2
+            },
+            "bar": {
+                "type": "number",
+                "const": 5.5
+            },
+            "baz": {
+                "type": "string",
+                "enum": ["foo", "bar"],
+                "default": "foo"
+            },
+            "boo": {
+                "type": "boolean",
+                "default": true
+            },
+            "foobar": {
+                "type": "object",
+                "default": {"foo": 1}
+            },
+            "barfoo": {
+                "type": "array",
+                "default": []
+            }
+        }
+    }"#;
+    let expected_tm_result = json!(
+        {
+            "properties": {
+                "foo": {
+                    "type": "integer",
+                    "enum": [1, 2, 3],
+                    "default": 2
+                },
+                "bar": {
+                    "type": "number",
+                    "const": 5.5
+                },
+                "baz": {
+                    "type": "string",
+                    "enum": ["foo", "bar"],
+                    "default": "foo"
+                },
+                "boo": {
+                    "type": "boolean",
+                    "default": true
+                },
+                "foobar": {
+                    "type": "object",
+                    "default": {"foo": 1}
+                },
+                "barfoo": {
+                    "type": "array",
+                    "default": []
+                }
+            }
+        }
+    );
+
+    test_sdf_wot_conversion(sdf_input, expected_tm_result);
+}
+
+#[test]
+fn test_tm_sdf_object_array_constant_values_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "@type": "Thing",
+            "properties": {
+                "foo": {
+                    "type": "object",
+                    "const": {"bar": "baz"}
+                },
+                "bar": {
+                    "type": "array",
+                    "default": [1, 2]
+                }
+            }
+        }
+    "#;
+    let expected_sdf_result = json!({
+        "sdfProperty": {
+            "foo": {
+                "type": "object",
+                "const": {"bar": "baz"}
+            },
+            "bar": {
+                "type": "array",
+                "default": [1, 2]
+            }
+        }
+    });
+
+    test_wot_tm_sdf_conversion(wot_tm_input, expected_sdf_result);
+}
+
+#[test]
+fn test_sdf_tm_array_items_conversion() {
+    let sdf_input = r#"{
+        "sdfProperty": {
+            "single": {
+                "type": "array",
+                "items": {"type": "string", "maxLength": 3}
+            },
+            "tuple": {
+                "type": "array",
+                "items": [{"type": "integer"}, {"type": "boolean"}]
+            }
+        }
+    }"#;
+    let expected_properties = json!({
+        "single": {
+            "type": "array",
+            "items": {"type": "string", "maxLength": 3}
+        },
+        "tuple": {
+            "type": "array",
+            "items": [{"type": "integer"}, {"type": "boolean"}]
+        }
+    });
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_eq!(result_json["properties"], expected_properties);
+    assert!(result.diagnostics.is_empty());
+}
+
+#[test]
+fn test_tm_sdf_array_items_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "properties": {
+                "single": {
+                    "type": "array",
+                    "items": {"type": "number", "minimum": 0}
+                },
+                "tuple": {
+                    "type": "array",
+                    "items": [{"type": "string"}, {"type": "object"}]
+                }
+            }
+        }
+    "#;
+    let expected_properties = json!({
+        "single": {
+            "type": "array",
+            "items": {"type": "number", "minimum": 0.0}
+        },
+        "tuple": {
+            "type": "array",
+            "items": [{"type": "string"}, {"type": "object"}]
+        }
+    });
+
+    let result = converter::convert_wot_tm_to_sdf(wot_tm_input.to_string()).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_eq!(result_json["sdfProperty"], expected_properties);
+}
+
+#[test]
+fn test_sdf_tm_untyped_constant_values_conversion() {
+    let sdf_input = r#"{
+        "sdfProperty": {
+            "foo": {
+                "default": 3,
+                "minimum": 0
+            },
+            "bar": {
+                "enum": ["on", "off"],
+                "const": "on"
+            }
+        }
+    }"#;
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    let thing_model: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+
+    assert_eq!(thing_model["properties"]["foo"], json!({"default": 3}));
+    assert_eq!(
+        thing_model["properties"]["bar"],
+        json!({"const": "on", "enum": ["on", "off"]})
+    );
+    assert_eq!(result.diagnostics.len(), 1);
+    assert_eq!(result.diagnostics[0].pointer, "/sdfProperty/foo/minimum");
+}
+
+#[test]
+fn test_tm_sdf_untyped_constant_values_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "@type": "Thing",
+            "properties": {
+                "foo": {
+                    "default": 3
+                },
+                "bar": {
+                    "const": [1, "two"]
+                }
+            }
+        }
+    "#;
+    let expected_sdf_result = json!({
+        "sdfProperty": {
+            "foo": {
+                "default": 3
+            },
+            "bar": {
+                "const": [1, "two"]
+            }
+        }
+    });
+
+    test_wot_tm_sdf_conversion(wot_tm_input, expected_sdf_result);
+}
+
+#[test]
+fn test_sdf_tm_composed_conversion() {
+    let sdf_input = r#"{
+        "sdfProperty": {
+            "foo": {
+                "type": "string"
+            }
+        },
+        "sdfThing": {
+            "lamp": {
+                "label": "Lamp",
+                "sdfObject": {
+                    "switch": {
+                        "sdfAction": {
+                            "toggle": {}
+                        }
+                    }
+                }
+            }
+        },
+        "sdfObject": {
+            "sensor": {
+                "description": "A sensor.",
+                "sdfProperty": {
+                    "value": {
+                        "type": "number"
+                    }
+                }
+            }
+        }
+    }"#;
+
+    let result =
+        converter::convert_sdf_to_composed_wot_tms(sdf_input.to_string(), "model").unwrap();
+    let names: Vec<&str> = result
+        .output
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "model.tm.json",
+            "model_sensor.tm.json",
+            "model_lamp.tm.json",
+            "model_lamp_switch.tm.json"
+        ]
+    );
+
+    let expected_results = vec![
+        json!({
+            "properties": {
+                "foo": {
+                    "type": "string"
+                }
+            },
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./model_sensor.tm.json",
+                    "type": "application/tm+json",
+                    "instanceName": "sensor"
+                },
+                {
+                    "rel": "tm:submodel",
+                    "href": "./model_lamp.tm.json",
+                    "type": "application/tm+json",
+                    "instanceName": "lamp"
+                }
+            ]
+        }),
+        json!({
+            "title": "sensor",
+            "description": "A sensor.",
+            "properties": {
+                "value": {
+                    "type": "number"
+                }
+            }
+        }),
+        json!({
+            "title": "Lamp",
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./model_lamp_switch.tm.json",
+                    "instanceName": "switch"
+                }
+            ]
+        }),
+        json!({
+            "title": "switch",
+            "actions": {
+                "toggle": {}
+            }
+        }),
+    ];
+
+    for ((_, thing_model), expected_result) in result.output.iter().zip(expected_results) {
+        let result_json: serde_json::Value = serde_json::from_str(thing_model).unwrap();
+        assert_json_include!(actual: result_json, expected: expected_result);
+    }
+}
+
+#[test]
+fn test_sdf_tm_composed_name_collision() {
+    let sdf_input = r#"{
+        "sdfThing": {
+            "a": {
+                "sdfObject": {
+                    "b": {
+                        "sdfProperty": {
+                            "x": {}
+                        }
+                    }
+                }
+            }
+        },
+        "sdfObject": {
+            "a_b": {
+                "sdfProperty": {
+                    "y": {}
+                }
+            }
+        }
+    }"#;
+
+    let error =
+        converter::convert_sdf_to_composed_wot_tms(sdf_input.to_string(), "model").unwrap_err();
+
+    match error {
+        ConversionError::Invalid(message) => assert!(message.contains("\"model_a_b\"")),
+        _ => panic!("Expected an invalid input error, got {:?}", error),
+    }
+}
+
+#[test]
+fn test_tm_sdf_title_object_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "@type": "Thing",
+            "title": "Lamp",
+            "description": "A lamp.",
+            "properties": {
+                "foo": {
+                    "type": "boolean"
+                }
+            }
+        }
+    "#;
+    let expected_sdf_result = json!({
+        "sdfObject": {
+            "Lamp": {
+                "description": "A lamp.",
+                "sdfProperty": {
+                    "foo": {
+                        "type": "boolean"
+                    }
+                }
+            }
+        }
+    });
+
+    test_wot_tm_sdf_conversion(wot_tm_input, expected_sdf_result);
+}
+
+fn load_composed_test_model(href: &str) -> sdf_wot_converter::Result<String> {
+    let thing_model = match href {
+        "./lamp.tm.json" => json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "title": "Lamp",
+            "properties": {
+                "brightness": {
+                    "type": "integer"
+                }
+            },
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./switch.tm.json",
+                    "instanceName": "switch"
+                }
+            ]
+        }),
+        "./switch.tm.json" => json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "title": "Switch",
+            "actions": {
+                "toggle": {}
+            }
+        }),
+        "./cyclic.tm.json" => json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./cyclic.tm.json"
+                }
+            ]
+        }),
+        "./floors/ground.tm.json" => json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "title": "Ground Floor",
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./desk-lamp.tm.json",
+                    "instanceName": "deskLamp"
+                }
+            ]
+        }),
+        "./floors/desk-lamp.tm.json" => json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "title": "Desk Lamp",
+            "links": [
+                {
+                    "rel": "tm:extends",
+                    "href": "../common/dimmable.tm.json"
+                }
+            ]
+        }),
+        "./common/dimmable.tm.json" => json!({
+            "@context": ["https://www.w3.org/2019/wot/td/v1"],
+            "properties": {
+                "dimming": {
+                    "type": "number"
+                }
+            }
+        }),
+        _ => return Err(ConversionError::Io(format!("Unknown model {}", href))),
+    };
+
+    Ok(thing_model.to_string())
+}
+
+#[test]
+fn test_composed_tm_sdf_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "title": "Room",
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./lamp.tm.json",
+                    "instanceName": "ceilingLamp"
+                },
+                {
+                    "rel": "tm:submodel",
+                    "href": "./switch.tm.json"
+                }
+            ]
+        }
+    "#;
+    let expected_sdf_result = json!({
+        "sdfThing": {
+            "ceilingLamp": {
+                "label": "Lamp",
+                "sdfObject": {
+                    "switch": {
+                        "label": "Switch",
+                        "sdfAction": {
+                            "toggle": {}
+                        }
+                    },
+                    "Lamp": {
+                        "sdfProperty": {
+                            "brightness": {
+                                "type": "integer"
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "sdfObject": {
+            "switch": {
+                "label": "Switch",
+                "sdfAction": {
+                    "toggle": {}
+                }
+            }
+        }
+    });
+
+    let result = converter::convert_composed_wot_tm_to_sdf(
+        wot_tm_input.to_string(),
+        &load_composed_test_model,
+    )
+    .unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_json_include!(actual: result_json.clone(), expected: expected_sdf_result);
+    assert!(result_json.get("sdfProperty").is_none());
+}
+
+#[test]
+fn test_composed_tm_sdf_relative_href_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "title": "Building",
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./floors/ground.tm.json",
+                    "instanceName": "groundFloor"
+                }
+            ]
+        }
+    "#;
+    let expected_sdf_result = json!({
+        "sdfThing": {
+            "groundFloor": {
+                "label": "Ground Floor",
+                "sdfObject": {
+                    "deskLamp": {
+                        "label": "Desk Lamp",
+                        "sdfProperty": {
+                            "dimming": {
+                                "type": "number"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    let result = converter::convert_composed_wot_tm_to_sdf(
+        wot_tm_input.to_string(),
+        &load_composed_test_model,
+    )
+    .unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_json_include!(actual: result_json, expected: expected_sdf_result);
+}
+
+#[test]
+fn test_cyclic_composed_tm_sdf_conversion() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./cyclic.tm.json"
+                }
+            ]
+        }
+    "#;
+
+    let result = converter::convert_composed_wot_tm_to_sdf(
+        wot_tm_input.to_string(),
+        &load_composed_test_model,
+    );
+    assert!(matches!(result, Err(ConversionError::Reference(_))));
+}
+
+#[test]
+fn test_colliding_composed_tm_sdf_conversion() {
+    let duplicate_instance_names = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./switch.tm.json",
+                    "instanceName": "switch"
+                },
+                {
+                    "rel": "tm:submodel",
+                    "href": "./lamp.tm.json",
+                    "instanceName": "switch"
+                },
+                {
+                    "rel": "tm:submodel",
+                    "href": "./switch.tm.json",
+                    "instanceName": "switch"
+                }
+            ]
+        }
+    "#;
+    let title_and_instance_name = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "title": "Switch",
+            "properties": {
+                "status": {
+                    "type": "boolean"
+                }
+            },
+            "links": [
+                {
+                    "rel": "tm:submodel",
+                    "href": "./switch.tm.json",
+                    "instanceName": "Switch"
+                }
+            ]
+        }
+    "#;
+
+    for wot_tm_input in [duplicate_instance_names, title_and_instance_name] {
+        let result = converter::convert_composed_wot_tm_to_sdf(
+            wot_tm_input.to_string(),
+            &load_composed_test_model,
+        );
+        assert!(matches!(result, Err(ConversionError::Invalid(_))));
+    }
+}
+
+#[test]
+fn test_extended_tm_sdf_conversion() {
+    let wot_tm_input = fs::read_to_string("examples/wot/extends/dimmer.tm.json").unwrap();
+    let loader = |href: &str| -> sdf_wot_converter::Result<String> {
+        fs::read_to_string(format!("examples/wot/extends/{}", href)).map_err(|e| e.into())
+    };
+    let expected_sdf_result = json!({
+        "sdfObject": {
+            "Dimmer": {
+                "sdfProperty": {
+                    "status": {
+                        "type": "boolean",
+                        "writable": true
+                    },
+                    "level": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 100
+                    },
+                    "targetLevel": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 100,
+                        "description": "The level the dimmer is transitioning to."
+                    }
+                },
+                "sdfAction": {
+                    "toggle": {}
+                }
+            }
+        }
+    });
+
+    let result =
+        converter::convert_wot_tm_to_sdf_with_loader(wot_tm_input.clone(), &loader).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_json_include!(actual: result_json, expected: expected_sdf_result);
+    assert!(converter::convert_wot_tm_to_sdf(wot_tm_input).is_err());
+}
+
+#[test]
+fn test_local_tm_ref_sdf_conversion() {
+    let wot_tm_input = r##"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "properties": {
+                "foo": {
+                    "type": "string",
+                    "minLength": 3
+                },
+                "bar": {
+                    "tm:ref": "#/properties/foo",
+                    "maxLength": 5
+                }
+            }
+        }
+    "##;
+    let expected_sdf_result = json!({
+        "sdfProperty": {
+            "bar": {
+                "type": "string",
+                "minLength": 3,
+                "maxLength": 5
+            }
+        }
+    });
+
+    test_wot_tm_sdf_conversion(wot_tm_input, expected_sdf_result);
+}
+
+#[test]
+fn test_tm_td_instantiation() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "@type": ["tm:ThingModel", "saref:LightSwitch"],
+            "title": "Lamp {{LAMP_NUMBER}}",
+            "properties": {
+                "brightness": {
+                    "type": "integer",
+                    "maximum": "{{MAXIMUM}}",
+                    "forms": [
+                        {
+                            "href": "{{BASE_URI}}/brightness"
+                        }
+                    ]
+                }
+            }
+        }
+    "#;
+    let placeholders = vec![
+        ("LAMP_NUMBER".to_string(), json!(1)),
+        ("MAXIMUM".to_string(), json!(100)),
+        ("BASE_URI".to_string(), json!("https://example.org/lamp")),
+    ]
+    .into_iter()
+    .collect();
+    let expected_td_result = json!({
+        "@type": ["saref:LightSwitch"],
+        "title": "Lamp 1",
+        "security": "nosec_sc",
+        "securityDefinitions": {
+            "nosec_sc": {
+                "scheme": "nosec"
+            }
+        },
+        "properties": {
+            "brightness": {
+                "type": "integer",
+                "maximum": 100,
+                "forms": [
+                    {
+                        "href": "https://example.org/lamp/brightness"
+                    }
+                ]
+            }
+        }
+    });
+
+    let result = converter::convert_wot_tm_to_td(wot_tm_input.to_string(), &placeholders).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_json_include!(actual: result_json, expected: expected_td_result);
+}
+
+#[test]
+fn test_tm_td_instantiation_missing_placeholder() {
+    let wot_tm_input = r#"
+        {
+            "@context": [
+              "https://www.w3.org/2019/wot/td/v1"
+            ],
+            "title": "Lamp",
+            "actions": {
+                "toggle": {
+                    "forms": [
+                        {
+                            "href": "{{BASE_URI}}/toggle"
+                        }
+                    ]
+                }
+            }
+        }
+    "#;
+
+    let error =
+        converter::convert_wot_tm_to_td(wot_tm_input.to_string(), &Default::default()).unwrap_err();
+
+    assert!(matches!(error, ConversionError::Invalid(_)));
+    assert!(error
+        .to_string()
+        .contains("Unresolved placeholder \"{{BASE_URI}}\" at \"/actions/toggle/forms/0/href\""));
+}
+
+#[test]
+fn test_sdf_td_conversion() {
+    let sdf_input = r#"
+        {
+            "info": {
+                "title": "Lamp",
+                "version": "2022-01-01",
+                "copyright": "Copyright 2022 Example Corp. All rights reserved.",
+                "license": "https://example.com/license"
+            },
+            "sdfProperty": {
+                "brightness": {
+                    "type": "integer",
+                    "observable": true
+                },
+                "status": {
+                    "type": "boolean",
+                    "writable": false
+                }
+            },
+            "sdfEvent": {
+                "overheating": {}
+            }
+        }
+    "#;
+    let options = BindingOptions {
+        base: "http://example.org/lamp/".to_string(),
+        protocol: Protocol::Http,
+        security_scheme: "basic".to_string(),
+        units: UnitVocabulary::Symbol,
+    };
+    let expected_td_result = json!({
+        "title": "Lamp",
+        "base": "http://example.org/lamp/",
+        "security": "basic_sc",
+        "securityDefinitions": {
+            "basic_sc": {
+                "scheme": "basic"
+            }
+        },
+        "properties": {
+            "brightness": {
+                "forms": [
+                    {
+                        "href": "properties/brightness",
+                        "op": ["readproperty", "writeproperty"]
+                    },
+                    {
+                        "href": "properties/brightness",
+                        "op": "observeproperty",
+                        "subprotocol": "longpoll"
+                    }
+                ]
+            },
+            "status": {
+                "readOnly": true,
+                "forms": [
+                    {
+                        "href": "properties/status",
+                        "op": "readproperty"
+                    }
+                ]
+            }
+        },
+        "events": {
+            "overheating": {
+                "forms": [
+                    {
+                        "href": "events/overheating",
+                        "op": "subscribeevent"
+                    }
+                ]
+            }
+        }
+    });
+
+    let result = converter::convert_sdf_to_wot_td(sdf_input.to_string(), &options).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_json_include!(actual: result_json, expected: expected_td_result);
+}
+
+#[test]
+fn test_td_sdf_conversion() {
+    let wot_td_input = fs::read_to_string("examples/wot/example.td.json").unwrap();
+    let expected_sdf_result = json!({
+        "sdfObject": {
+            "MyLampThing": {
+                "sdfProperty": {
+                    "status": {
+                        "type": "string"
+                    }
+                },
+                "sdfAction": {
+                    "toggle": {}
+                },
+                "sdfEvent": {
+                    "overheating": {
+                        "sdfOutputData": {
+                            "type": "string"
+                        }
+                    }
+                }
+            }
+        }
+    });
+    let expected_mapping_result = json!({
+        "map": {
+            "#/sdfObject/MyLampThing": {
+                "td:security": "basic_sc",
+                "td:securityDefinitions": {
+                    "basic_sc": {
+                        "scheme": "basic",
+                        "in": "header"
+                    }
+                }
+            },
+            "#/sdfObject/MyLampThing/sdfEvent/overheating": {
+                "td:forms": [
+                    {
+                        "href": "https://mylamp.example.com/oh",
+                        "subprotocol": "longpoll"
+                    }
+                ]
+            }
+        }
+    });
+
+    let (sdf_model, sdf_mapping) = converter::convert_wot_td_to_sdf(wot_td_input)
+        .unwrap()
+        .output;
+    let sdf_model_json: serde_json::Value = serde_json::from_str(sdf_model.as_str()).unwrap();
+    let sdf_mapping_json: serde_json::Value = serde_json::from_str(sdf_mapping.as_str()).unwrap();
+
+    assert_json_include!(actual: sdf_model_json, expected: expected_sdf_result);
+    assert_json_include!(actual: sdf_mapping_json, expected: expected_mapping_result);
+}
+
+#[test]
+fn test_sdf_tm_conversion_with_mapping() {
+    let sdf_input = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+    let mapping_input = fs::read_to_string("examples/sdf/example.sdf-mapping.json").unwrap();
+    let expected_tm_result = json!({
+        "base": "coap://switch.example.com/",
+        "properties": {
+            "SwitchValue": {
+                "forms": [
+                    {
+                        "href": "value",
+                        "op": "readproperty",
+                        "cov:method": "GET"
+                    },
+                    {
+                        "href": "value",
+                        "op": "writeproperty",
+                        "cov:method": "PUT"
+                    }
+                ]
+            }
+        },
+        "actions": {
+            "SwitchToggle": {
+                "forms": [
+                    {
+                        "href": "toggle",
+                        "cov:method": "POST"
+                    }
+                ]
+            }
+        }
+    });
+
+    let result = converter::convert_sdf_to_wot_tm_with_mapping(sdf_input, mapping_input).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(result.output.as_str()).unwrap();
+
+    assert_json_include!(actual: result_json, expected: expected_tm_result);
+}
+
+#[test]
+fn test_sdf_tm_conversion_with_dangling_mapping() {
+    let sdf_input = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+    let mapping_input = json!({
+        "map": {
+            "#/sdfObject/Switch/sdfProperty/brightness": {
+                "td:href": "brightness"
+            }
+        }
+    });
+
+    let error = converter::convert_sdf_to_wot_tm_with_mapping(sdf_input, mapping_input.to_string())
+        .unwrap_err();
+
+    assert!(matches!(error, ConversionError::Mapping(_)));
+    assert_eq!(
+        error.to_string(),
+        "Mapping entry \"#/sdfObject/Switch/sdfProperty/brightness\" does not point to an existing definition!"
+    );
+}
+
+#[test]
+fn test_sdf_tm_conversion_diagnostics() {
+    let sdf_input = r#"
+    {
+        "info": {
+            "title": "Example",
+            "version": "2021-06-01",
+            "copyright": "Copyright 2021 Example Corp.",
+            "license": "BSD-3-Clause"
+        },
+        "sdfProduct": {},
+        "sdfProperty": {
+            "temperature": {
+                "type": "number",
+                "sdfType": "byte-string",
+                "nullable": false
+            }
+        }
+    }
+    "#;
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    let diagnostics: Vec<(&str, DiagnosticKind)> = result
+        .diagnostics
+        .iter()
+        .map(|x| (x.pointer.as_str(), x.kind))
+        .collect();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("/info/copyright", DiagnosticKind::Approximated),
+            ("/sdfProduct", DiagnosticKind::Dropped),
+            ("/sdfProperty/temperature/sdfType", DiagnosticKind::Dropped),
+            ("/sdfProperty/temperature/nullable", DiagnosticKind::Dropped),
+        ]
+    );
+    assert!(result.diagnostics.iter().all(|x| x.document.is_none()));
+}
+
+#[test]
+fn test_composed_tm_sdf_conversion_diagnostics() {
+    let wot_tm_input = json!({
+        "@context": ["https://www.w3.org/2019/wot/td/v1"],
+        "@type": "tm:ThingModel",
+        "title": "Room",
+        "links": [
+            {
+                "rel": "tm:submodel",
+                "href": "./lamp.tm.json",
+                "instanceName": "ceilingLamp"
+            }
+        ]
+    });
+    let loader = |href: &str| {
+        load_composed_test_model(href).map(|x| {
+            let mut thing_model: serde_json::Value = serde_json::from_str(&x).unwrap();
+            thing_model["titles"] = json!({"de": "Modell"});
+            thing_model.to_string()
+        })
+    };
+
+    let result =
+        converter::convert_composed_wot_tm_to_sdf(wot_tm_input.to_string(), &loader).unwrap();
+    let diagnostics: Vec<(Option<&str>, &str)> = result
+        .diagnostics
+        .iter()
+        .map(|x| (x.document.as_deref(), x.pointer.as_str()))
+        .collect();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            (None, "/@type"),
+            (Some("./lamp.tm.json"), "/titles"),
+            (Some("./switch.tm.json"), "/titles"),
+        ]
+    );
+}
+
+#[test]
+fn test_wot_tm_td_conversion_diagnostics() {
+    let wot_tm_input = json!({
+        "@context": ["https://www.w3.org/2019/wot/td/v1"],
+        "@type": "tm:ThingModel",
+        "title": "Lamp"
+    });
+
+    let result =
+        converter::convert_wot_tm_to_td(wot_tm_input.to_string(), &Default::default()).unwrap();
+
+    assert_eq!(result.diagnostics.len(), 1);
+    assert_eq!(result.diagnostics[0].pointer, "/security");
+    assert_eq!(result.diagnostics[0].kind, DiagnosticKind::Defaulted);
+}
+
+#[test]
+fn test_sdf_tm_comparison() {
+    let sdf_input = r#"{
+        "sdfObject": {
+            "switch": {
+                "sdfProperty": {
+                    "onOff": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "writable": true
+                    }
+                },
+                "sdfAction": {
+                    "toggle": {}
+                }
+            }
+        }
+    }"#;
+    let tm_input = r#"{
+        "@context": ["https://www.w3.org/2019/wot/td/v1"],
+        "@type": "Thing",
+        "properties": {
+            "onOff": {
+                "type": "integer",
+                "minimum": 1.0,
+                "observable": false,
+                "readOnly": false
+            }
+        },
+        "actions": {
+            "switch_toggle": {}
+        }
+    }"#;
+
+    let differences =
+        converter::compare_sdf_with_wot_tm(sdf_input.to_string(), tm_input.to_string()).unwrap();
+    let differences_json = serde_json::to_value(differences).unwrap();
+
+    assert_eq!(
+        differences_json,
+        json!([
+            {
+                "affordance": "/properties/switchOnOff",
+                "pointer": "/minimum",
+                "kind": "changed",
+                "first": 0.0,
+                "second": 1.0
+            }
+        ])
+    );
+}
+
+#[test]
+fn test_sdf_td_conversion_validity() {
+    let sdf_input = fs::read_to_string("examples/sdf/example.sdf.json").unwrap();
+    let options = BindingOptions {
+        base: "coap://example.org/".to_string(),
+        protocol: Protocol::Coap,
+        security_scheme: "psk".to_string(),
+        units: UnitVocabulary::Symbol,
+    };
+
+    let thing_description = converter::convert_sdf_to_wot_td(sdf_input, &options)
+        .unwrap()
+        .output;
+
+    assert_eq!(
+        converter::validate_wot_td(thing_description).unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn test_conversion_order_preservation() {
+    let sdf_input = r#"
+    {
+        "sdfObject": {
+            "lamp": {
+                "sdfProperty": {
+                    "zeta": {"type": "boolean"},
+                    "alpha": {"type": "integer"},
+                    "mode": {
+                        "sdfChoice": {
+                            "zeta": {"const": "z"},
+                            "alpha": {"const": "a"}
+                        }
+                    },
+                    "level": {
+                        "sdfChoice": {
+                            "zeta": {"type": "number"},
+                            "alpha": {"type": "integer"}
+                        }
+                    }
+                },
+                "sdfAction": {
+                    "toggle": {},
+                    "off": {},
+                    "on": {}
+                }
+            }
+        }
+    }
+    "#;
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string())
+        .unwrap()
+        .output;
+    let thing_model: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+    let keys = |value: &serde_json::Value| -> Vec<String> {
+        value.as_object().unwrap().keys().cloned().collect()
+    };
+    assert_eq!(
+        keys(&thing_model["properties"]),
+        ["lampZeta", "lampAlpha", "lampMode", "lampLevel"]
+    );
+    assert_eq!(
+        keys(&thing_model["actions"]),
+        ["lampToggle", "lampOff", "lampOn"]
+    );
+    assert_eq!(
+        thing_model["properties"]["lampMode"]["enum"],
+        json!(["z", "a"])
+    );
+    let titles: Vec<&serde_json::Value> = thing_model["properties"]["lampLevel"]["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| &x["title"])
+        .collect();
+    assert_eq!(titles, [&json!("zeta"), &json!("alpha")]);
+
+    let sdf_model: serde_json::Value = serde_json::from_str(
+        &converter::convert_wot_tm_to_sdf(result.clone())
+            .unwrap()
+            .output,
+    )
+    .unwrap();
+    assert_eq!(
+        keys(&sdf_model["sdfProperty"]),
+        ["lampZeta", "lampAlpha", "lampMode", "lampLevel"]
+    );
+    assert_eq!(
+        keys(&sdf_model["sdfProperty"]["lampLevel"]["sdfChoice"]),
+        ["zeta", "alpha"]
+    );
+    assert_eq!(
+        keys(&sdf_model["sdfAction"]),
+        ["lampToggle", "lampOff", "lampOn"]
+    );
+
+    assert_eq!(
+        converter::convert_sdf_to_wot_tm(sdf_input.to_string())
+            .unwrap()
+            .output,
+        result
+    );
+}
+
+#[test]
+fn test_sdf_tm_sdf_type_conversion() {
+    let sdf_input = r#"
+    {
+        "sdfProperty": {
+            "firmware": {
+                "type": "string",
+                "sdfType": "byte-string"
+            },
+            "timestamp": {
+                "type": "integer",
+                "sdfType": "unix-time"
+            }
+        }
+    }
+    "#;
+    let expected_tm_result = json!({
+        "@context": [
+            "https://www.w3.org/2019/wot/td/v1",
+            {
+                "sdf": "https://datatracker.ietf.org/doc/html/draft-ietf-asdf-sdf#"
+            }
+        ],
+        "properties": {
+            "firmware": {
+                "type": "string",
+                "contentEncoding": "base64"
+            },
+            "timestamp": {
+                "@type": "sdf:UnixTime",
+                "type": "integer",
+                "unit": "s"
+            }
+        }
+    });
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    assert!(result.diagnostics.is_empty());
+    let result_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+    assert_json_include!(actual: result_json, expected: expected_tm_result);
+
+    let sdf_result = converter::convert_wot_tm_to_sdf(result.output).unwrap();
+    assert!(sdf_result
+        .diagnostics
+        .iter()
+        .all(|x| !x.pointer.starts_with("/properties")));
+    let sdf_json: serde_json::Value = serde_json::from_str(&sdf_result.output).unwrap();
+    let sdf_expected: serde_json::Value = serde_json::from_str(sdf_input).unwrap();
+    assert_eq!(sdf_json, sdf_expected);
+}
+
+#[test]
+fn test_tm_sdf_sdf_type_conversion() {
+    let wot_tm_input = json!({
+        "@context": ["https://www.w3.org/2019/wot/td/v1"],
+        "@type": "tm:ThingModel",
+        "properties": {
+            "image": {
+                "type": "string",
+                "contentEncoding": "base64",
+                "readOnly": true
+            },
+            "lastSeen": {
+                "@type": ["sdf:UnixTime"],
+                "type": "number",
+                "unit": "ms"
+            }
+        }
+    });
+
+    let result = converter::convert_wot_tm_to_sdf(wot_tm_input.to_string()).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+
+    assert_eq!(
+        result_json,
+        json!({
+            "sdfProperty": {
+                "image": {
+                    "type": "string",
+                    "writable": false,
+                    "sdfType": "byte-string"
+                },
+                "lastSeen": {
+                    "type": "number",
+                    "unit": "ms",
+                    "sdfType": "unix-time"
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_sdf_tm_nullable_conversion() {
+    let sdf_input = json!({
+        "sdfProperty": {
+            "temperature": {
+                "type": "number",
+                "unit": "Cel",
+                "writable": false,
+                "nullable": true
+            },
+            "mode": {
+                "sdfChoice": {
+                    "auto": {"type": "string", "const": "auto"},
+                    "level": {"type": "integer", "minimum": 1}
+                },
+                "nullable": true
+            },
+            "name": {
+                "type": "string"
+            }
+        }
+    });
+    let expected_tm_result = json!({
+        "properties": {
+            "temperature": {
+                "readOnly": true,
+                "oneOf": [
+                    {"type": "number", "unit": "Cel"},
+                    {"type": "null"}
+                ]
+            },
+            "mode": {
+                "oneOf": [
+                    {"title": "auto", "type": "string", "const": "auto"},
+                    {"title": "level", "type": "integer", "minimum": 1},
+                    {"type": "null"}
+                ]
+            },
+            "name": {
+                "type": "string"
+            }
+        }
+    });
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    assert_eq!(result.diagnostics.len(), 1);
+    assert_eq!(result.diagnostics[0].pointer, "/sdfProperty/mode/sdfChoice");
+    let result_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+    assert_json_include!(actual: result_json, expected: expected_tm_result);
+    assert!(result_json["properties"]["name"].get("oneOf").is_none());
+
+    let sdf_result = converter::convert_wot_tm_to_sdf(result.output).unwrap();
+    let sdf_json: serde_json::Value = serde_json::from_str(&sdf_result.output).unwrap();
+    assert_eq!(sdf_json, sdf_input);
+}
+
+#[test]
+fn test_td_sdf_nullable_conversion() {
+    let wot_td_input = json!({
+        "@context": ["https://www.w3.org/2019/wot/td/v1"],
+        "title": "Sensor",
+        "securityDefinitions": {"nosec_sc": {"scheme": "nosec"}},
+        "security": "nosec_sc",
+        "properties": {
+            "humidity": {
+                "title": "Humidity",
+                "oneOf": [{"type": "null"}, {"type": "integer", "maximum": 100}],
+                "forms": [{"href": "https://example.org/humidity"}]
+            }
+        }
+    });
+
+    let result = converter::convert_wot_td_to_sdf(wot_td_input.to_string()).unwrap();
+    let (sdf_model, _) = result.output;
+    let sdf_json: serde_json::Value = serde_json::from_str(&sdf_model).unwrap();
+
+    assert_eq!(
+        sdf_json["sdfObject"]["Sensor"]["sdfProperty"]["humidity"],
+        json!({
+            "label": "Humidity",
+            "type": "integer",
+            "maximum": 100,
+            "nullable": true
+        })
+    );
+}
+
+#[test]
+fn test_sdf_tm_content_format_conversion() {
+    let sdf_input = json!({
+        "sdfProperty": {
+            "image": {
+                "type": "string",
+                "sdfType": "byte-string",
+                "contentFormat": "image/png"
+            },
+            "measurement": {
+                "type": "object",
+                "contentFormat": "application/senml+cbor"
+            }
+        },
+        "sdfAction": {
+            "configure": {
+                "sdfInputData": {
+                    "type": "object",
+                    "contentFormat": "application/cbor"
+                }
+            }
+        },
+        "sdfEvent": {
+            "overheated": {
+                "sdfOutputData": {
+                    "type": "array",
+                    "contentFormat": "application/senml+cbor"
+                }
+            }
+        }
+    });
+    let expected_tm_result = json!({
+        "properties": {
+            "image": {
+                "type": "string",
+                "contentEncoding": "base64",
+                "contentMediaType": "image/png"
+            },
+            "measurement": {
+                "type": "object",
+                "forms": [{"contentType": "application/senml+cbor"}]
+            }
+        },
+        "actions": {
+            "configure": {
+                "input": {"type": "object"},
+                "forms": [{"contentType": "application/cbor"}]
+            }
+        },
+        "events": {
+            "overheated": {
+                "data": {"type": "array"},
+                "forms": [{"contentType": "application/senml+cbor"}]
+            }
+        }
+    });
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    assert!(result.diagnostics.is_empty());
+    let result_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+    assert_json_include!(actual: result_json, expected: expected_tm_result);
+
+    let sdf_result = converter::convert_wot_tm_to_sdf(result.output).unwrap();
+    assert!(sdf_result.diagnostics.iter().all(|x| x.pointer == "/@type"));
+    let sdf_json: serde_json::Value = serde_json::from_str(&sdf_result.output).unwrap();
+    assert_eq!(sdf_json, sdf_input);
+}
+
+#[test]
+fn test_sdf_td_content_format_conversion() {
+    let sdf_input = json!({
+        "info": {
+            "title": "Sensor",
+            "version": "1.0.0",
+            "copyright": "Copyright 2026",
+            "license": "MIT"
+        },
+        "sdfObject": {
+            "sensor": {
+                "sdfProperty": {
+                    "measurement": {
+                        "type": "array",
+                        "contentFormat": "112",
+                        "writable": false
+                    },
+                    "name": {
+                        "type": "string",
+                        "contentFormat": "0"
+                    }
+                },
+                "sdfAction": {
+                    "calibrate": {
+                        "sdfInputData": {
+                            "type": "object",
+                            "contentFormat": "60"
+                        }
+                    }
+                },
+                "sdfEvent": {
+                    "alarm": {
+                        "sdfOutputData": {
+                            "type": "array",
+                            "contentFormat": "112"
+                        }
+                    }
+                }
+            }
+        }
+    });
+    let options = BindingOptions {
+        base: "coap://example.org".to_string(),
+        protocol: Protocol::Coap,
+        security_scheme: "nosec".to_string(),
+        units: UnitVocabulary::Symbol,
+    };
+
+    let result = converter::convert_sdf_to_wot_td(sdf_input.to_string(), &options).unwrap();
+    let td_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+
+    assert_eq!(
+        td_json["properties"]["sensorMeasurement"]["forms"],
+        json!([{
+            "href": "properties/sensorMeasurement",
+            "op": "readproperty",
+            "contentType": "application/senml+cbor"
+        }])
+    );
+    assert_eq!(
+        td_json["properties"]["sensorName"]["contentMediaType"],
+        json!("text/plain;charset=utf-8")
+    );
+    assert_eq!(
+        td_json["properties"]["sensorName"]["forms"][0]["contentType"],
+        json!("application/json")
+    );
+    assert_eq!(
+        td_json["actions"]["sensorCalibrate"]["forms"],
+        json!([{
+            "href": "actions/sensorCalibrate",
+            "op": "invokeaction",
+            "contentType": "application/cbor"
+        }])
+    );
+    assert_eq!(
+        td_json["events"]["sensorAlarm"]["forms"],
+        json!([{
+            "href": "events/sensorAlarm",
+            "op": "subscribeevent",
+            "contentType": "application/senml+cbor"
+        }])
+    );
+    assert!(result
+        .diagnostics
+        .iter()
+        .all(|x| !x.pointer.ends_with("/contentFormat")));
+
+    let (sdf_model, _) = converter::convert_wot_td_to_sdf(result.output)
+        .unwrap()
+        .output;
+    let sdf_json: serde_json::Value = serde_json::from_str(&sdf_model).unwrap();
+    let sdf_properties = &sdf_json["sdfObject"]["Sensor"]["sdfProperty"];
+
+    assert_eq!(
+        sdf_properties["sensorMeasurement"]["contentFormat"],
+        json!("application/senml+cbor")
+    );
+    assert_eq!(
+        sdf_properties["sensorName"]["contentFormat"],
+        json!("text/plain;charset=utf-8")
+    );
+    assert_eq!(
+        sdf_json["sdfObject"]["Sensor"]["sdfAction"]["sensorCalibrate"]["sdfInputData"]
+            ["contentFormat"],
+        json!("application/cbor")
+    );
+    assert_eq!(
+        sdf_json["sdfObject"]["Sensor"]["sdfEvent"]["sensorAlarm"]["sdfOutputData"]
+            ["contentFormat"],
+        json!("application/senml+cbor")
+    );
+}
+
+#[test]
+fn test_sdf_tm_format_conversion() {
+    let sdf_input = json!({
+        "sdfProperty": {
+            "timestamp": {"type": "string", "format": "date-time"},
+            "address": {"type": "string", "format": "ipv4"},
+            "pointer": {"type": "string", "format": "json-pointer"},
+            "color": {"type": "string", "format": "hex-color"}
+        }
+    });
+    let expected_tm_result = json!({
+        "properties": {
+            "timestamp": {"type": "string", "format": "date-time"},
+            "address": {"type": "string", "format": "ipv4"},
+            "pointer": {"type": "string", "format": "json-pointer"},
+            "color": {"type": "string", "format": "hex-color"}
+        }
+    });
+
+    let result = converter::convert_sdf_to_wot_tm(sdf_input.to_string()).unwrap();
+    assert!(result.diagnostics.is_empty());
+    let result_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+    assert_json_include!(actual: result_json, expected: expected_tm_result);
+
+    let sdf_result = converter::convert_wot_tm_to_sdf(result.output).unwrap();
+    let sdf_json: serde_json::Value = serde_json::from_str(&sdf_result.output).unwrap();
+    assert_eq!(sdf_json, sdf_input);
+
+    let diagnostics: Vec<(&str, DiagnosticKind)> = sdf_result
+        .diagnostics
+        .iter()
+        .filter(|x| x.pointer.starts_with("/properties"))
+        .map(|x| (x.pointer.as_str(), x.kind))
+        .collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("/properties/address/format", DiagnosticKind::Approximated),
+            ("/properties/pointer/format", DiagnosticKind::Approximated),
+            ("/properties/color/format", DiagnosticKind::Approximated),
+        ]
+    );
+}
+
+#[test]
+fn test_sdf_tm_unit_translation() {
+    let sdf_input = json!({
+        "sdfProperty": {
+            "temperature": {"type": "number", "unit": "Cel"},
+            "energy": {
+                "type": "object",
+                "properties": {
+                    "total": {"type": "number", "unit": "kWh"},
+                    "count": {"type": "integer", "unit": "beats"}
+                }
+            }
+        }
+    });
+
+    let thing_model = converter::convert_sdf_to_wot_tm(sdf_input.to_string())
+        .unwrap()
+        .output;
+    let result = converter::translate_wot_units(thing_model, UnitVocabulary::Om).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+    assert_json_include!(
+        actual: result_json,
+        expected: json!({
+            "@context": [
+                "https://www.w3.org/2019/wot/td/v1",
+                {"om": "http://www.ontology-of-units-of-measure.org/resource/om-2/"}
+            ],
+            "properties": {
+                "temperature": {"unit": "om:degreeCelsius"},
+                "energy": {
+                    "properties": {
+                        "total": {"unit": "om:kilowattHour"},
+                        "count": {"unit": "beats"}
+                    }
+                }
+            }
+        })
+    );
+
+    let sdf_result = converter::convert_wot_tm_to_sdf(result).unwrap();
+    let sdf_json: serde_json::Value = serde_json::from_str(&sdf_result.output).unwrap();
+    assert_eq!(sdf_json, sdf_input);
+}
+
+#[test]
+fn test_sdf_td_unit_translation() {
+    let sdf_input = json!({
+        "info": {
+            "title": "Thermometer",
+            "version": "1.0.0",
+            "copyright": "Copyright 2026",
+            "license": "MIT"
+        },
+        "sdfProperty": {
+            "temperature": {"type": "number", "unit": "Cel", "writable": false}
+        }
+    });
+    let options = BindingOptions {
+        base: "https://example.org/thermometer/".to_string(),
+        protocol: Protocol::Http,
+        security_scheme: "nosec".to_string(),
+        units: UnitVocabulary::Qudt,
+    };
+
+    let result = converter::convert_sdf_to_wot_td(sdf_input.to_string(), &options).unwrap();
+    let result_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+
+    assert_json_include!(
+        actual: result_json,
+        expected: json!({
+            "@context": [
+                "https://www.w3.org/2019/wot/td/v1",
+                {"qudt": "http://qudt.org/vocab/unit/"}
+            ],
+            "base": "https://example.org/thermometer/",
+            "security": "nosec_sc",
+            "securityDefinitions": {"nosec_sc": {"scheme": "nosec"}},
+            "properties": {
+                "temperature": {
+                    "unit": "qudt:DEG_C",
+                    "forms": [{
+                        "href": "properties/temperature",
+                        "op": "readproperty"
+                    }]
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_tm_sdf_unit_conversion() {
+    let wot_tm_input = json!({
+        "@context": [
+            "https://www.w3.org/2022/wot/td/v1.1",
+            {"unit": "http://qudt.org/vocab/unit/"}
+        ],
+        "@type": "tm:ThingModel",
+        "properties": {
+            "humidity": {"type": "number", "unit": "unit:PERCENT"},
+            "pressure": {
+                "type": "number",
+                "unit": "http://www.ontology-of-units-of-measure.org/resource/om-2/pascal"
+            },
+            "length": {"type": "number", "unit": "unit:FT"}
+        }
+    });
+
+    let result = converter::convert_wot_tm_to_sdf(wot_tm_input.to_string()).unwrap();
+    let sdf_json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+
+    assert_eq!(
+        sdf_json,
+        json!({
+            "sdfProperty": {
+                "humidity": {"type": "number", "unit": "%"},
+                "pressure": {"type": "number", "unit": "Pa"},
+                "length": {"type": "number", "unit": "http://qudt.org/vocab/unit/FT"}
+            }
+        })
+    );
+}
@@ -0,0 +1,127 @@
+use sdf_wot_converter::converter;
+use sdf_wot_converter::sdf::sdf_ref::resolve_sdf_refs;
+use sdf_wot_converter::wot::tm_resolver::resolve_thing_model;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Collects all files within a directory (and its subdirectories) whose names
+/// end with `suffix`.
+fn collect_examples(directory: &Path, suffix: &str, examples: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_examples(&path, suffix, examples);
+        } else if path.to_string_lossy().ends_with(suffix) {
+            examples.push(path);
+        }
+    }
+}
+
+/// Represents all numbers as floating point numbers, as converted documents
+/// do not distinguish between `1` and `1.0`.
+fn normalize_numbers(value: Value) -> Value {
+    match value {
+        Value::Number(number) => number
+            .as_f64()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        Value::Array(array) => Value::Array(array.into_iter().map(normalize_numbers).collect()),
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, normalize_numbers(value)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// SDF examples that are not valid SDF models and are therefore excluded from
+/// the round trip, together with the reason.
+const INVALID_SDF_EXAMPLES: [(&str, &str); 1] = [(
+    "sdfobject-level.sdf.json",
+    "uses the superseded array syntax for sdfInputData and string enum values \
+    for a number type",
+)];
+
+/// Returns the reason for excluding an SDF example from the round trip.
+fn get_exclusion_reason(path: &Path) -> Option<&'static str> {
+    INVALID_SDF_EXAMPLES
+        .iter()
+        .find(|(name, _)| path.ends_with(name))
+        .map(|(_, reason)| *reason)
+}
+
+fn assert_equivalent(path: &Path, actual: &str, expected: Value) {
+    let actual: Value = serde_json::from_str(actual).unwrap();
+    assert_eq!(
+        normalize_numbers(actual),
+        normalize_numbers(expected),
+        "{} does not survive the round trip",
+        path.display()
+    );
+}
+
+#[test]
+fn test_sdf_tm_sdf_round_trip() {
+    let mut examples = Vec::new();
+    collect_examples(Path::new("examples"), ".sdf.json", &mut examples);
+    assert!(!examples.is_empty());
+
+    for path in examples {
+        let input = fs::read_to_string(&path).unwrap();
+        let violations = converter::validate_sdf_model(input.clone()).unwrap();
+        if let Some(reason) = get_exclusion_reason(&path) {
+            assert!(
+                !violations.is_empty(),
+                "{} is excluded because it {}, but it is valid",
+                path.display(),
+                reason
+            );
+            continue;
+        }
+        assert!(
+            violations.is_empty(),
+            "{} is not a valid SDF model: {:?}",
+            path.display(),
+            violations
+        );
+        let expected = resolve_sdf_refs(&serde_json::from_str(&input).unwrap()).unwrap();
+
+        let thing_model = converter::convert_sdf_to_wot_tm_lossless(input)
+            .unwrap()
+            .output;
+        let result = converter::convert_wot_tm_to_sdf(thing_model).unwrap();
+
+        assert!(result.diagnostics.is_empty());
+        assert_equivalent(&path, &result.output, expected);
+    }
+}
+
+#[test]
+fn test_tm_sdf_tm_round_trip() {
+    let mut examples = Vec::new();
+    collect_examples(Path::new("examples"), ".tm.json", &mut examples);
+    assert!(!examples.is_empty());
+
+    for path in examples {
+        let directory = path.parent().unwrap().to_path_buf();
+        let loader = |href: &str| fs::read_to_string(directory.join(href)).map_err(|e| e.into());
+        let json_loader =
+            |href: &str| loader(href).map(|x| serde_json::from_str::<Value>(&x).unwrap());
+
+        let input = fs::read_to_string(&path).unwrap();
+        let expected =
+            resolve_thing_model(&serde_json::from_str(&input).unwrap(), &json_loader).unwrap();
+
+        let sdf_model = converter::convert_wot_tm_to_sdf_lossless_with_loader(input, &loader)
+            .unwrap()
+            .output;
+        let result = converter::convert_sdf_to_wot_tm(sdf_model).unwrap();
+
+        assert!(result.diagnostics.is_empty());
+        assert_equivalent(&path, &result.output, expected);
+    }
+}